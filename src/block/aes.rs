@@ -6,10 +6,7 @@
  
  *===================================================================*/
 
-use std::u128;
-
 pub struct AES {
-    key: [u32; 4],
     round_keys: [u128; 11],
 }
 
@@ -55,10 +52,7 @@ impl AES {
 
 
     pub fn new(key: u128) -> AES {
-        let key_u32_arr = Self::u128_to_u32_array(key); 
-
         Self {
-            key: key_u32_arr, 
             round_keys: Self::key_expansion(key),
         }
     }
@@ -151,6 +145,7 @@ impl AES {
         round_keys
     }
 
+    #[allow(clippy::needless_range_loop)]
     pub fn sub_bytes(&self, state: u128) -> u128 {
         let mut new_state: [u8; 16] = state.to_be_bytes();
 
@@ -161,6 +156,7 @@ impl AES {
         u128::from_be_bytes(new_state)
     }
 
+    #[allow(clippy::needless_range_loop)]
     pub fn shift_rows(&self, state: u128) -> u128 {
         let mut new_state: u128 = 0;
 
@@ -198,6 +194,7 @@ impl AES {
         new_state
     }
 
+    #[allow(clippy::needless_range_loop)]
     pub fn mix_columns(&self, state: u128) -> u128 {
         let mut new_state: u128 = 0;
 
@@ -234,6 +231,7 @@ impl AES {
         new_state
     }
 
+    #[allow(clippy::needless_range_loop)]
     pub fn inv_sub_bytes(&self, state: u128) -> u128 {
         let mut new_state: [u8; 16] = state.to_be_bytes();
 
@@ -248,6 +246,7 @@ impl AES {
         Self::INVERSE_AES_SBOX[(val >> 4) as usize][(val & 0x0F) as usize]
     }
 
+    #[allow(clippy::needless_range_loop)]
     pub fn inv_shift_rows(&self, state: u128) -> u128 {
         let mut new_state: u128 = 0;
 
@@ -285,6 +284,7 @@ impl AES {
         new_state
     }
 
+    #[allow(clippy::needless_range_loop)]
     pub fn inv_mix_columns(&self, state: u128) -> u128 {
         let mut new_state: u128 = 0;
 
@@ -400,6 +400,8 @@ impl AES {
 
 
 
+#[cfg(test)]
+#[allow(clippy::needless_range_loop)]
 fn print_arr_u128_hex(arr: [u128; 11]) {
     for i in 0..11 {
         println!("arr[{:2}] = 0x{:016X}", i, arr[i]);
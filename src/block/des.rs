@@ -6,11 +6,8 @@
 
  *===================================================================*/
 
-use std::usize;
-
 
 pub struct DES {
-    key: u64,
     round_keys: [u64; 16],
 }
 
@@ -152,9 +149,10 @@ impl DES {
     pub fn new(
         key: u64
     ) -> Self {
-        Self {key, round_keys: DES::generate_roundkeys(key)}
+        Self {round_keys: DES::generate_roundkeys(key)}
     }
 
+    #[allow(clippy::needless_range_loop)]
     pub fn generate_roundkeys(
         key: u64
     ) -> [u64; 16] {
@@ -292,6 +290,7 @@ impl DES {
 }
 
 
+#[cfg(test)]
 fn print_u64_array_hex(arr: &[u64; 16]) {
     for (i, &value) in arr.iter().enumerate() { 
         println!("arr[{:2}] = 0x{:016X}", i, value);
@@ -0,0 +1,791 @@
+/*===================================================================
+ * Author: denosauabh
+ * Description: Command-line interface of the `moc` binary, runs any cipher
+                of the crate on stdin (or a file) & writes the result to stdout (or a file)
+
+ * Usage:
+
+    moc <cipher> encrypt|decrypt [--key KEY] [--charset CHARSET] [--input FILE] [--output FILE] ...
+
+    echo -n "hello" | moc additive encrypt --key 3 --charset lowercase
+    moc aes encrypt --key 000102030405060708090a0b0c0d0e0f --mode cbc --iv 0 --format base64 --input msg.txt
+
+ *===================================================================*/
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::rc::Rc;
+use std::str::FromStr;
+
+use moc::block::{aes::AES, des::DES};
+use moc::mode_of_operations::{cbc::CBC, cfb::CFB, ctr::CTR, ecb::ECB, pbc::PBC, pfb::PFB};
+use moc::digraphic_substitution::{four_square::FourSquare, playfair::Playfair, two_square::TwoSquare};
+use moc::polyalphebatic_substitution::{alberti::AlbertiCipher, beaufort::{Beaufort, VariantBeaufort}, gronsfeld::Gronsfeld, one_time_pad::OneTimePad, porta::Porta, running_key::RunningKey, vigenere::VigenèreCipher};
+use moc::polyliteral::{adfgvx::ADFGVX, bifid::Bifid, biliteral::Biliteral, nihilist::Nihilist, trifid::Trifid};
+use moc::public_key::{pohlig_hellman::PohligHellman, rsa::RSA};
+use moc::simple_substitution::{additive::AdditiveCipher, affine::AffineCipher, hill::{HillCipher, HillPadding}, multiplicative::MultiplicativeCipher};
+use moc::stream::{autokey::{AutoKeyType, Autokey}, gromark::Gromark};
+use moc::transposition::{columnar::Columnar, disrupted::Disrupted, double_columnar::DoubleColumnar, geometric::{Geometric, GeometricTranspositionMethod}, myszkowski::Myszkowski, permutation::Permutation, rail_fence::RailFence, route::{Corner, Rotation, Route, RouteCipher}, scytale::Scytale};
+use moc::utils::{bignum::BigUint, char_set::CharSet, cipher::Cipher, encoding::Encoding, error::Error, polybius::{PolybiusSquare, SquareOptions}};
+
+pub const USAGE: &str = "\
+usage: moc <cipher> encrypt|decrypt [options]
+
+reads the text from stdin (or --input FILE) and writes the result to stdout (or --output FILE)
+
+ciphers & their options:
+    additive, multiplicative         --key N
+    affine                           --key K,M
+    hill                             --key K1,K2,...,Kn² (row by row) [--padding CHAR]
+    alberti                          --disk CHARS --period N
+    vigenere, beaufort               --key WORD
+    variant-beaufort, porta          --key WORD (porta needs a charset of even length)
+    gronsfeld                        --key DIGITS
+    running-key                      --key TEXT | --key-file FILE (at least as long as the text)
+    one-time-pad                     --pad FILE (encrypt writes a new random pad, decrypt reads it)
+    adfgvx                           --key WORD [--passphrase TEXT] (keys the 6×6 square)
+    adfgx                            --key WORD [--passphrase TEXT] (keys the 5×5 square, J merged into I)
+    autokey                          --key IV [--mode plaintext|ciphertext]
+    gromark                          --key DIGITS (5 digits, e.g. 12029)
+    playfair                         --key WORD [--merge FROMINTO|none] [--filler CHAR]
+    two-square, four-square          --key WORD --key2 WORD [--merge FROMINTO|none] [--filler CHAR]
+                                         A..Z with J merged into I & X as filler unless --charset is given
+    biliteral                        [--passphrase TEXT] (keys the 5×5 square)
+    bifid                            --key WORD [--period N] [--merge FROMINTO|none]
+    trifid                           --key WORD [--period N] (A..Z & + unless --charset is given)
+    nihilist                         --key WORD --key2 WORD (square keyword & additive key) [--merge FROMINTO|none]
+    columnar, permutation            --key WORD
+    myszkowski, disrupted            --key WORD
+    double-columnar                  --key WORD --key2 WORD
+    scytale                          --rows N
+    rail-fence                       --rows N [--offset N]
+    geometric                        --rows N --route ROUTE
+                                         (alternate-)horizontal, (alternate-)vertical, (alternate-)diagonal,
+                                         spiral-clockwise, spiral-counter-clockwise
+    route                            --rows N --write PATH --read PATH
+                                         (alternate-)rows, (alternate-)columns, (alternate-)diagonals,
+                                         spiral-CORNER-clockwise|counter-clockwise (CORNER top-left, top-right,
+                                         bottom-left, bottom-right) or cells ROW:COL,ROW:COL,...
+    pohlig-hellman                   --key E
+    rsa                              --key P,Q (input is whitespace separated numbers)
+    des                              --key HEX [--format hex|base64]
+    aes                              --key HEX [--mode ecb|cbc|cfb|pfb|pbc|ctr] [--iv HEX] [--nonce HEX] [--format hex|base64]
+                                         (pbc can only encrypt)
+
+common options:
+    --charset NAME|CHARS    lowercase, uppercase, numbers, ascii, printable (default) or the characters themselves
+    --input FILE            read the text from FILE instead of stdin
+    --output FILE           write the result to FILE instead of stdout
+";
+
+#[derive(Debug)]
+pub enum CliError {
+    Usage(String),
+    Io(io::Error),
+    Cipher(Error),
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Usage(reason) => write!(f, "{}", reason),
+            CliError::Io(err) => write!(f, "{}", err),
+            CliError::Cipher(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl From<io::Error> for CliError {
+    fn from(err: io::Error) -> Self {
+        CliError::Io(err)
+    }
+}
+
+impl From<Error> for CliError {
+    fn from(err: Error) -> Self {
+        CliError::Cipher(err)
+    }
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Direction {
+    Encrypt,
+    Decrypt,
+}
+
+pub struct Args {
+    pub cipher: String,
+    pub direction: Direction,
+    options: HashMap<String, String>,
+}
+
+impl Args {
+    pub fn parse(args: &[String]) -> Result<Self, CliError> {
+        let (cipher, direction) = match args {
+            [cipher, direction, ..] => (cipher.to_lowercase(), direction.as_str()),
+            _ => return Err(CliError::Usage("expected a cipher & encrypt|decrypt".to_string())),
+        };
+
+        let direction = match direction {
+            "encrypt" | "enc" | "e" => Direction::Encrypt,
+            "decrypt" | "dec" | "d" => Direction::Decrypt,
+            _ => return Err(CliError::Usage(format!("expected encrypt|decrypt, found {:?}", direction))),
+        };
+
+        let mut options = HashMap::new();
+        let mut rest = args[2..].iter();
+
+        while let Some(arg) = rest.next() {
+            let name = arg
+                .strip_prefix("--")
+                .ok_or(CliError::Usage(format!("expected an --option, found {:?}", arg)))?;
+            let value = rest
+                .next()
+                .ok_or(CliError::Usage(format!("--{} needs a value", name)))?;
+
+            options.insert(name.to_string(), value.to_string());
+        }
+
+        Ok(Self { cipher, direction, options })
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.options.get(name).map(|s| s.as_str())
+    }
+
+    fn require(&self, name: &str) -> Result<&str, CliError> {
+        self.get(name)
+            .ok_or(CliError::Usage(format!("{} needs --{}", self.cipher, name)))
+    }
+
+    fn number<T: FromStr>(&self, name: &str) -> Result<T, CliError> {
+        let value = self.require(name)?;
+        value
+            .trim()
+            .parse::<T>()
+            .map_err(|_| CliError::Usage(format!("--{} expects a number, found {:?}", name, value)))
+    }
+
+    fn numbers<T: FromStr>(&self, name: &str) -> Result<Vec<T>, CliError> {
+        let value = self.require(name)?;
+        value
+            .split(',')
+            .map(|n| n.trim().parse::<T>())
+            .collect::<Result<Vec<T>, _>>()
+            .map_err(|_| CliError::Usage(format!("--{} expects comma separated numbers, found {:?}", name, value)))
+    }
+
+    fn hex<T>(&self, name: &str, parse: fn(&str, u32) -> Result<T, std::num::ParseIntError>) -> Result<T, CliError> {
+        let value = self.require(name)?;
+        parse(value.trim().trim_start_matches("0x"), 16)
+            .map_err(|_| CliError::Usage(format!("--{} expects a hex number, found {:?}", name, value)))
+    }
+
+    fn char_set(&self) -> CharSet {
+        match self.get("charset") {
+            None | Some("printable") => CharSet::new(),
+            Some("lowercase") => CharSet::from_alphabet_lowercase(),
+            Some("uppercase") => CharSet::from_range('A', 'Z'),
+            Some("numbers") => CharSet::from_numbers(),
+            Some("ascii") => CharSet::from_ascii(),
+            Some(chars) => CharSet::from_string(chars),
+        }
+    }
+
+    fn is_base64(&self) -> Result<bool, CliError> {
+        match self.get("format") {
+            None | Some("hex") => Ok(false),
+            Some("base64") => Ok(true),
+            Some(format) => Err(CliError::Usage(format!("--format expects hex|base64, found {:?}", format))),
+        }
+    }
+}
+
+
+pub fn run(args: &[String]) -> Result<(), CliError> {
+    if args.is_empty() || args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        print!("{}", USAGE);
+        return Ok(());
+    }
+
+    let args = Args::parse(args)?;
+
+    let input = match args.get("input") {
+        Some(path) => fs::read_to_string(path)?,
+        None => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            input
+        },
+    };
+
+    // a trailing newline (from `echo` or an editor) is never part of the message
+    let input = input.strip_suffix('\n').unwrap_or(&input);
+    let input = input.strip_suffix('\r').unwrap_or(input);
+
+    let output = execute(&args, input)?;
+
+    match args.get("output") {
+        Some(path) => fs::write(path, output)?,
+        None => writeln!(io::stdout(), "{}", output)?,
+    }
+
+    Ok(())
+}
+
+pub fn execute(args: &Args, input: &str) -> Result<String, CliError> {
+    match args.cipher.as_str() {
+        // simple substitution
+        "additive" => {
+            let cipher = AdditiveCipher::new(args.char_set());
+            apply(&cipher, args, input, &args.number("key")?)
+        },
+        "multiplicative" => {
+            let cipher = MultiplicativeCipher::new(args.char_set());
+            apply(&cipher, args, input, &args.number("key")?)
+        },
+        "affine" => {
+            let cipher = AffineCipher::new(args.char_set());
+            match args.numbers::<i32>("key")?[..] {
+                [k, m] => apply(&cipher, args, input, &(k, m)),
+                _ => Err(CliError::Usage("affine --key expects K,M".to_string())),
+            }
+        },
+        "hill" => {
+            let cipher = match args.get("padding") {
+                Some(padding) => {
+                    let mut chars = padding.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(c), None) => HillCipher::with_padding(args.char_set(), HillPadding::Char(c))?,
+                        _ => return Err(CliError::Usage("hill --padding expects a single character".to_string())),
+                    }
+                },
+                None => HillCipher::new(args.char_set()),
+            };
+
+            // the n×n key matrix, row by row
+            let numbers = args.numbers::<i32>("key")?;
+            let n = (1..=numbers.len())
+                .find(|n| n * n == numbers.len())
+                .ok_or(CliError::Usage("hill --key expects n×n comma separated numbers".to_string()))?;
+            let key = numbers.chunks(n).map(|row| row.to_vec()).collect::<Vec<Vec<i32>>>();
+
+            apply(&cipher, args, input, &key)
+        },
+
+        // polyalphabetic substitution
+        "alberti" => {
+            let disk = CharSet::from_string(args.require("disk")?);
+            let cipher = AlbertiCipher::new(args.char_set(), disk, args.number("period")?)?;
+            apply(&cipher, args, input, &())
+        },
+        "vigenere" => {
+            let cipher = VigenèreCipher::new(args.char_set());
+            apply(&cipher, args, input, &args.require("key")?.to_string())
+        },
+        "beaufort" => apply(&Beaufort::new(args.char_set()), args, input, &args.require("key")?.to_string()),
+        "variant-beaufort" => apply(&VariantBeaufort::new(args.char_set()), args, input, &args.require("key")?.to_string()),
+        "gronsfeld" => apply(&Gronsfeld::new(args.char_set()), args, input, &args.require("key")?.to_string()),
+        "porta" => apply(&Porta::new(args.char_set())?, args, input, &args.require("key")?.to_string()),
+        "running-key" => {
+            let key = match args.get("key-file") {
+                Some(path) => fs::read_to_string(path)?,
+                None => args.require("key")?.to_string(),
+            };
+            apply(&RunningKey::new(args.char_set()), args, input, &key)
+        },
+        "one-time-pad" => {
+            let path = args.require("pad")?;
+            let mut pad = OneTimePad::new(args.char_set());
+
+            Ok(match args.direction {
+                Direction::Encrypt => {
+                    // a pad file is never overwritten, that would be the way to reuse it
+                    if fs::metadata(path).is_ok() {
+                        return Err(CliError::Usage(format!("pad {:?} already exists, one-time pads are used once", path)));
+                    }
+
+                    let (encrypted, key) = pad.encrypt(input, &mut rand::thread_rng())?;
+                    fs::write(path, key)?;
+                    encrypted
+                },
+                Direction::Decrypt => pad.decrypt(input, &fs::read_to_string(path)?)?,
+            })
+        },
+
+        // digraphic substitution
+        "playfair" => {
+            let cipher = Playfair::with_options(digraph_options(args)?)?;
+            apply(&cipher, args, input, &args.require("key")?.to_string())
+        },
+        "two-square" => {
+            let key = (args.require("key")?.to_string(), args.require("key2")?.to_string());
+            apply(&TwoSquare::with_options(digraph_options(args)?)?, args, input, &key)
+        },
+        "four-square" => {
+            let key = (args.require("key")?.to_string(), args.require("key2")?.to_string());
+            apply(&FourSquare::with_options(digraph_options(args)?)?, args, input, &key)
+        },
+
+        // polyliteral
+        "biliteral" => {
+            let cipher = match args.get("passphrase") {
+                Some(passphrase) => {
+                    let char_set = CharSet::from_range('A', 'Z');
+                    let keyword = PolybiusSquare::keyword(passphrase, &char_set);
+                    Biliteral::with_square(PolybiusSquare::with_merge(&keyword, &char_set, Some(('J', 'I')))?)
+                },
+                None => Biliteral::new(),
+            };
+            apply(&cipher, args, input, &())
+        },
+        "adfgvx" => {
+            let cipher = match args.get("passphrase") {
+                Some(passphrase) => ADFGVX::with_keyword(passphrase)?,
+                None => ADFGVX::new(None, None, None)?,
+            };
+            apply(&cipher, args, input, &args.require("key")?.to_string())
+        },
+        "adfgx" => {
+            let cipher = ADFGVX::adfgx(args.get("passphrase").unwrap_or(""))?;
+            apply(&cipher, args, input, &args.require("key")?.to_string())
+        },
+        "bifid" => {
+            let period = if args.get("period").is_some() { args.number("period")? } else { 0 };
+            apply(&Bifid::with_options(square_options(args)?, period), args, input, &args.require("key")?.to_string())
+        },
+        "trifid" => {
+            let period = if args.get("period").is_some() { args.number("period")? } else { 0 };
+            let cipher = match args.get("charset") {
+                Some(_) => Trifid::with_options(args.char_set(), period)?,
+                None => Trifid::new(period),
+            };
+            apply(&cipher, args, input, &args.require("key")?.to_string())
+        },
+        "nihilist" => {
+            let key = (args.require("key")?.to_string(), args.require("key2")?.to_string());
+            apply(&Nihilist::with_options(square_options(args)?)?, args, input, &key)
+        },
+
+        // stream
+        "autokey" => {
+            let mode = match args.get("mode") {
+                None | Some("plaintext") => AutoKeyType::Plaintext,
+                Some("ciphertext") => AutoKeyType::Ciphertext,
+                Some(mode) => return Err(CliError::Usage(format!("autokey --mode expects plaintext|ciphertext, found {:?}", mode))),
+            };
+            let cipher = Autokey::new(mode, args.char_set());
+            apply(&cipher, args, input, &args.require("key")?.to_string())
+        },
+        "gromark" => {
+            let key = args.require("key")?
+                .chars()
+                .map(|c| c.to_digit(10).map(|d| d as usize))
+                .collect::<Option<Vec<usize>>>()
+                .ok_or(CliError::Usage("gromark --key expects digits".to_string()))?;
+            apply(&Gromark::new(args.char_set()), args, input, &key)
+        },
+
+        // transposition
+        "columnar" => apply(&Columnar::new(), args, input, &args.require("key")?.to_string()),
+        "permutation" => apply(&Permutation::new(), args, input, &args.require("key")?.to_string()),
+        "double-columnar" => {
+            let key = (args.require("key")?.to_string(), args.require("key2")?.to_string());
+            apply(&DoubleColumnar::new(), args, input, &key)
+        },
+        "myszkowski" => apply(&Myszkowski::new(), args, input, &args.require("key")?.to_string()),
+        "disrupted" => apply(&Disrupted::new(), args, input, &args.require("key")?.to_string()),
+        "scytale" => apply(&Scytale::new(args.number("rows")?)?, args, input, &()),
+        "rail-fence" => {
+            let offset = if args.get("offset").is_some() { args.number("offset")? } else { 0 };
+            apply(&RailFence::with_offset(args.number("rows")?, offset)?, args, input, &())
+        },
+        "geometric" => {
+            let method = match args.require("route")? {
+                "horizontal" => GeometricTranspositionMethod::Horizontal,
+                "alternate-horizontal" => GeometricTranspositionMethod::AlternateHorizontal,
+                "vertical" => GeometricTranspositionMethod::Vertical,
+                "alternate-vertical" => GeometricTranspositionMethod::AlternateVertical,
+                "diagonal" => GeometricTranspositionMethod::Diagonal,
+                "alternate-diagonal" => GeometricTranspositionMethod::AlternateDiagonal,
+                "spiral-clockwise" => GeometricTranspositionMethod::SpiralClockwise,
+                "spiral-counter-clockwise" => GeometricTranspositionMethod::SpiralCounterClockwise,
+                route => return Err(CliError::Usage(format!("unknown geometric --route {:?}", route))),
+            };
+            apply(&Geometric::new(method, args.number("rows")?)?, args, input, &())
+        },
+        "route" => {
+            let cipher = RouteCipher::new(args.number("rows")?, route(args.require("write")?)?, route(args.require("read")?)?)?;
+            apply(&cipher, args, input, &())
+        },
+
+        // public key
+        "pohlig-hellman" => {
+            let cipher = PohligHellman::<u64>::new(args.char_set())?;
+            let key = args.number("key")?;
+            Ok(match args.direction {
+                Direction::Encrypt => cipher.encrypt(input, key)?,
+                Direction::Decrypt => cipher.decrypt(input, key)?,
+            })
+        },
+        "rsa" => {
+            let (p, q) = match &args.numbers::<BigUint>("key")?[..] {
+                [p, q] => (p.clone(), q.clone()),
+                _ => return Err(CliError::Usage("rsa --key expects P,Q".to_string())),
+            };
+            let rsa = RSA::new(p, q)?;
+
+            let numbers = input
+                .split_whitespace()
+                .map(|n| n.parse::<BigUint>().map_err(|_| CliError::Usage(format!("rsa expects numbers, found {:?}", n))))
+                .collect::<Result<Vec<BigUint>, CliError>>()?;
+
+            // anything from n up would silently be reduced mod n
+            let public_key = rsa.public_key();
+            if let Some(n) = numbers.iter().find(|&n| *n >= public_key.n) {
+                return Err(match args.direction {
+                    Direction::Encrypt => Error::MessageTooLong { max: public_key.size() - 1, found: n.to_bytes_be().len() },
+                    Direction::Decrypt => Error::MalformedCiphertext(format!("{} is not smaller than the modulus {}", n, public_key.n)),
+                }.into());
+            }
+
+            Ok(numbers
+                .into_iter()
+                .map(|n| match args.direction {
+                    Direction::Encrypt => rsa.encrypt(n),
+                    Direction::Decrypt => rsa.decrypt(n),
+                }.to_string())
+                .collect::<Vec<String>>()
+                .join(" "))
+        },
+
+        // block
+        "des" => des(args, input),
+        "aes" => aes(args, input),
+
+        cipher => Err(CliError::Usage(format!("unknown cipher {:?}", cipher))),
+    }
+}
+
+fn apply<C: Cipher>(cipher: &C, args: &Args, input: &str, key: &C::Key) -> Result<String, CliError> {
+    Ok(match args.direction {
+        Direction::Encrypt => cipher.encrypt(input, key)?,
+        Direction::Decrypt => cipher.decrypt(input, key)?,
+    })
+}
+
+/// charset & merged letters (from, into) of the Polybius square ciphers, A..Z with J merged into I by default
+fn square_options(args: &Args) -> Result<SquareOptions, CliError> {
+    let custom = args.get("charset").is_some();
+    let char_set = if custom { args.char_set() } else { CharSet::from_range('A', 'Z') };
+
+    let merge = match args.get("merge") {
+        None if !custom => Some(('J', 'I')),
+        None | Some("none") => None,
+        Some(merge) => match merge.chars().collect::<Vec<char>>()[..] {
+            [from, into] => Some((from, into)),
+            _ => return Err(CliError::Usage("--merge expects two characters, FROM & INTO".to_string())),
+        },
+    };
+
+    Ok(SquareOptions::new(char_set, merge)?)
+}
+
+/// the square of the digraphic ciphers, X as filler by default
+fn digraph_options(args: &Args) -> Result<SquareOptions, CliError> {
+    let filler = match args.get("filler").map(|f| f.chars().collect::<Vec<char>>()) {
+        None => 'X',
+        Some(filler) if filler.len() == 1 => filler[0],
+        Some(_) => return Err(CliError::Usage("--filler expects a single character".to_string())),
+    };
+
+    Ok(square_options(args)?.with_filler(filler)?)
+}
+
+/// named path of the route cipher, e.g. `spiral-top-right-clockwise`, or custom cells `0:0,0:1,...`
+fn route(name: &str) -> Result<Route, CliError> {
+    let spiral = |corner: &str, rotation: Rotation| {
+        let corner = match corner {
+            "top-left" => Corner::TopLeft,
+            "top-right" => Corner::TopRight,
+            "bottom-left" => Corner::BottomLeft,
+            "bottom-right" => Corner::BottomRight,
+            corner => return Err(CliError::Usage(format!("unknown spiral corner {:?}", corner))),
+        };
+        Ok(Route::Spiral { corner, rotation })
+    };
+
+    Ok(match name {
+        "rows" => Route::Rows,
+        "alternate-rows" => Route::AlternateRows,
+        "columns" => Route::Columns,
+        "alternate-columns" => Route::AlternateColumns,
+        "diagonals" => Route::Diagonals,
+        "alternate-diagonals" => Route::AlternateDiagonals,
+        _ if name.starts_with("spiral-") => {
+            let name = &name["spiral-".len()..];
+
+            if let Some(corner) = name.strip_suffix("-counter-clockwise") {
+                spiral(corner, Rotation::CounterClockwise)?
+            } else if let Some(corner) = name.strip_suffix("-clockwise") {
+                spiral(corner, Rotation::Clockwise)?
+            } else {
+                return Err(CliError::Usage(format!("spiral route {:?} must end in -clockwise or -counter-clockwise", name)));
+            }
+        },
+        _ => {
+            let cells = name
+                .split(',')
+                .map(|cell| {
+                    let (r, c) = cell.trim().split_once(':')?;
+                    Some((r.parse().ok()?, c.parse().ok()?))
+                })
+                .collect::<Option<Vec<(usize, usize)>>>()
+                .ok_or(CliError::Usage(format!("unknown route {:?}", name)))?;
+            Route::Custom(cells)
+        },
+    })
+}
+
+/// DES encrypts 8 byte blocks, each block is encrypted on its own (ECB) after PKCS#7 padding
+fn des(args: &Args, input: &str) -> Result<String, CliError> {
+    let des = DES::new(args.hex("key", u64::from_str_radix)?);
+
+    match args.direction {
+        Direction::Encrypt => {
+            // pad with n bytes of value n, a full block of them if the input already fills its blocks
+            let padding = 8 - input.len() % 8;
+            let mut padded = input.as_bytes().to_vec();
+            padded.extend(std::iter::repeat_n(padding as u8, padding));
+
+            let bytes = padded
+                .chunks(8)
+                .flat_map(|chunk| des.encrypt(u64::from_be_bytes(chunk.try_into().unwrap())).to_be_bytes())
+                .collect::<Vec<u8>>();
+
+            format_bytes(args, &bytes)
+        },
+        Direction::Decrypt => {
+            let bytes = parse_bytes(args, input)?;
+
+            if bytes.is_empty() || !bytes.len().is_multiple_of(8) {
+                return Err(Error::MalformedCiphertext("DES ciphertext must be 8 byte blocks".to_string()).into());
+            }
+
+            let mut decrypted = bytes
+                .chunks(8)
+                .flat_map(|chunk| des.decrypt(u64::from_be_bytes(chunk.try_into().unwrap())).to_be_bytes())
+                .collect::<Vec<u8>>();
+
+            let padding = decrypted[decrypted.len() - 1] as usize;
+            if !(1..=8).contains(&padding) || decrypted[decrypted.len() - padding..].iter().any(|&byte| byte as usize != padding) {
+                return Err(Error::MalformedCiphertext("invalid PKCS#7 padding, wrong key?".to_string()).into());
+            }
+            decrypted.truncate(decrypted.len() - padding);
+
+            Ok(String::from_utf8_lossy(&decrypted).to_string())
+        },
+    }
+}
+
+/// AES through one of the modes of operation, which all work on hex strings
+fn aes(args: &Args, input: &str) -> Result<String, CliError> {
+    let aes = Rc::new(AES::new(args.hex("key", u128::from_str_radix)?));
+    let cipher = {
+        let aes = Rc::clone(&aes);
+        move |block| aes.encrypt(block)
+    };
+
+    let mode = args.get("mode").unwrap_or("ecb");
+
+    match args.direction {
+        Direction::Encrypt => {
+            let hex = match mode {
+                "ecb" => ECB::new(0x00, cipher, move |block| aes.decrypt(block)).encrypt(input),
+                "cbc" => CBC::new(args.hex("iv", u128::from_str_radix)?, cipher, move |block| aes.decrypt(block)).encrypt(input),
+                "cfb" => CFB::new(args.hex("iv", u128::from_str_radix)?, cipher).encrypt(input),
+                "pfb" => PFB::new(args.hex("iv", u128::from_str_radix)?, cipher).encrypt(input),
+                "pbc" => PBC::new(args.hex("iv", u128::from_str_radix)?, cipher).encrypt(input),
+                "ctr" => CTR::new(args.hex("nonce", u64::from_str_radix)?, cipher).encrypt(input),
+                mode => return Err(CliError::Usage(format!("unknown aes --mode {:?}", mode))),
+            };
+
+            format_bytes(args, &Encoding::hex_decode(&hex)?)
+        },
+        Direction::Decrypt => {
+            let hex = Encoding::hex_encode(&parse_bytes(args, input)?);
+
+            Ok(match mode {
+                "ecb" => ECB::new(0x00, cipher, move |block| aes.decrypt(block)).decrypt(&hex)?,
+                "cbc" => CBC::new(args.hex("iv", u128::from_str_radix)?, cipher, move |block| aes.decrypt(block)).decrypt(&hex)?,
+                "cfb" => CFB::new(args.hex("iv", u128::from_str_radix)?, cipher).decrypt(&hex)?,
+                "pfb" => PFB::new(args.hex("iv", u128::from_str_radix)?, cipher).decrypt(&hex)?,
+                "pbc" => return Err(CliError::Usage("aes --mode pbc can only encrypt".to_string())),
+                "ctr" => CTR::new(args.hex("nonce", u64::from_str_radix)?, cipher).decrypt(&hex)?,
+                mode => return Err(CliError::Usage(format!("unknown aes --mode {:?}", mode))),
+            })
+        },
+    }
+}
+
+fn format_bytes(args: &Args, bytes: &[u8]) -> Result<String, CliError> {
+    Ok(if args.is_base64()? { Encoding::base64_encode(bytes) } else { Encoding::hex_encode(bytes) })
+}
+
+fn parse_bytes(args: &Args, input: &str) -> Result<Vec<u8>, CliError> {
+    let input = input.trim();
+    Ok(if args.is_base64()? { Encoding::base64_decode(input)? } else { Encoding::hex_decode(input)? })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Args {
+        Args::parse(&s.split(' ').map(String::from).collect::<Vec<String>>()).unwrap()
+    }
+
+    fn round_trip(encrypt: &str, decrypt: &str, text: &str) -> String {
+        let encrypted = execute(&args(encrypt), text).unwrap();
+        assert_ne!(encrypted, text);
+
+        execute(&args(decrypt), &encrypted).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let parsed = args("vigenere encrypt --key lemon --charset lowercase");
+        assert_eq!(parsed.cipher, "vigenere");
+        assert_eq!(parsed.direction, Direction::Encrypt);
+        assert_eq!(parsed.get("key"), Some("lemon"));
+        assert_eq!(parsed.get("charset"), Some("lowercase"));
+
+        let to_args = |s: &str| s.split(' ').map(String::from).collect::<Vec<String>>();
+        assert!(matches!(Args::parse(&to_args("additive")), Err(CliError::Usage(_))));
+        assert!(matches!(Args::parse(&to_args("additive shift --key 3")), Err(CliError::Usage(_))));
+        assert!(matches!(Args::parse(&to_args("additive encrypt --key")), Err(CliError::Usage(_))));
+        assert!(matches!(Args::parse(&to_args("additive encrypt key 3")), Err(CliError::Usage(_))));
+    }
+
+    #[test]
+    fn test_classical() {
+        assert_eq!(execute(&args("additive encrypt --key 3 --charset lowercase"), "hello").unwrap(), "khoor");
+        assert_eq!(execute(&args("vigenere decrypt --key oculorhinolaringology --charset lowercase"), "ovnlqbpvthznzouz").unwrap(), "attackingtonight");
+        assert_eq!(execute(&args("beaufort encrypt --key FORTIFICATION --charset uppercase"), "DEFENDTHEEAST").unwrap(), "CKMPVCPVWPIWU");
+        assert_eq!(execute(&args("porta decrypt --key FORTIFICATION --charset uppercase"), "SYNNJSCVRNRLA").unwrap(), "DEFENDTHEEAST");
+        assert_eq!(execute(&args("gronsfeld encrypt --key 31415 --charset uppercase"), "ATTACKATDAWN").unwrap(), "DUXBHNBXEFZO");
+        assert_eq!(round_trip("variant-beaufort encrypt --key lemon", "variant-beaufort decrypt --key lemon", "attack at dawn"), "attack at dawn");
+        assert_eq!(execute(&args("running-key encrypt --key itwasthebestoftimes --charset lowercase"), "attackatdawn").unwrap(), "impaudhxeeog");
+        assert_eq!(execute(&args("affine encrypt --key 7,3 --charset numbers"), "123").unwrap(), "074");
+        assert_eq!(execute(&args("biliteral encrypt"), "HELLO").unwrap(), "2315313134");
+        assert_eq!(execute(&args("biliteral encrypt --passphrase polybius"), "PLAY").unwrap(), "11132414");
+        assert_eq!(execute(&args("playfair encrypt --key PLAYFAIREXAMPLE"), "HIDETHEGOLDINTHETREESTUMP").unwrap(), "BMODZBXDNABEKUDMUIXMMOUVIF");
+        assert_eq!(
+            execute(&args("four-square decrypt --key EXAMPLE --key2 KEYWORD --charset ABCDEFGHIJKLMNOPRSTUVWXYZ"), "FYGMKYHOBXMFKKKIMD").unwrap(),
+            "HELPMEOBIWANKENOBI"
+        );
+        assert_eq!(round_trip("two-square encrypt --key EXAMPLE --key2 KEYWORD", "two-square decrypt --key EXAMPLE --key2 KEYWORD", "ATTACKATDAWN"), "ATTACKATDAWN");
+        assert_eq!(execute(&args("rail-fence encrypt --rows 3"), "HELLO").unwrap(), "HOELL");
+        assert_eq!(execute(&args("rail-fence decrypt --rows 3 --offset 2"), "LOELWRDHOL").unwrap(), "HELLOWORLD");
+
+        let text = "WEAREDISCOVEREDFLEEATONCE";
+        assert_eq!(round_trip("geometric encrypt --rows 4 --route spiral-clockwise", "geometric decrypt --rows 4 --route spiral-clockwise", text), text);
+        assert_eq!(execute(&args("myszkowski encrypt --key TOMATO"), "WEAREDISCOVEREDFLEEATONCE").unwrap(), "ROFOACDTEDSEEEACWEIVRLENE");
+        assert_eq!(round_trip("double-columnar encrypt --key ZEBRAS --key2 STRIPE", "double-columnar decrypt --key ZEBRAS --key2 STRIPE", text), text);
+        assert_eq!(round_trip("disrupted encrypt --key ZEBRAS", "disrupted decrypt --key ZEBRAS", text), text);
+        assert_eq!(execute(&args("route encrypt --rows 2 --write rows --read 1:1,0:0,1:0,0:1"), "ABCD").unwrap(), "DACB");
+        assert_eq!(
+            round_trip(
+                "route encrypt --rows 5 --write alternate-columns --read spiral-bottom-right-counter-clockwise",
+                "route decrypt --rows 5 --write alternate-columns --read spiral-bottom-right-counter-clockwise",
+                text,
+            ),
+            text
+        );
+        assert_eq!(round_trip("columnar encrypt --key ZEBRAS", "columnar decrypt --key ZEBRAS", text), text);
+        assert_eq!(round_trip("adfgvx encrypt --key PRIVACY", "adfgvx decrypt --key PRIVACY", "ATTACKAT1200AM"), "ATTACKAT1200AM");
+        assert_eq!(
+            round_trip("adfgvx encrypt --key PRIVACY --passphrase secret", "adfgvx decrypt --key PRIVACY --passphrase secret", "ATTACKAT1200AM"),
+            "ATTACKAT1200AM"
+        );
+        assert_eq!(
+            execute(&args("adfgx encrypt --key CARGO --passphrase BTALPDHOZKQFVSNGICUXMREWY"), "ATTACKATONCE").unwrap(),
+            "FAXDFADDDGDGFFFAFAXAFAFX"
+        );
+        assert_eq!(execute(&args("bifid encrypt --key BGWKZQPNDSIOAXEFCLUMTHYVR"), "FLEEATONCE").unwrap(), "UAEOLWRINS");
+        assert_eq!(execute(&args("trifid decrypt --key FELIXMARIEDELASTELLE --period 5"), "FMJFVOISSUFTFPUFEQQC").unwrap(), "AIDETOILECIELTAIDERA");
+        assert_eq!(round_trip("nihilist encrypt --key ZEBRAS --key2 RUSSIAN", "nihilist decrypt --key ZEBRAS --key2 RUSSIAN", "DYNAMITE"), "DYNAMITE");
+        assert_eq!(round_trip("hill encrypt --key 3,5,6,1 --charset uppercase", "hill decrypt --key 3,5,6,1 --charset uppercase", "JACK"), "JACK");
+        assert_eq!(execute(&args("hill encrypt --key 6,24,1,13,16,10,20,17,15 --charset lowercase --padding x"), "actca").unwrap().len(), 6);
+        assert_eq!(round_trip("gromark encrypt --key 12029 --charset uppercase", "gromark decrypt --key 12029 --charset uppercase", text), text);
+        assert_eq!(round_trip("autokey encrypt --key vic --mode ciphertext", "autokey decrypt --key vic --mode ciphertext", "attack at dawn"), "attack at dawn");
+    }
+
+    #[test]
+    fn test_one_time_pad() {
+        let path = std::env::temp_dir().join(format!("moc-pad-{}", std::process::id()));
+        let path = path.to_str().unwrap();
+        let _ = fs::remove_file(path);
+
+        let encrypted = execute(&args(&format!("one-time-pad encrypt --pad {} --charset lowercase", path)), "attackatdawn").unwrap();
+        assert_eq!(fs::read_to_string(path).unwrap().len(), 12);
+        assert_eq!(execute(&args(&format!("one-time-pad decrypt --pad {} --charset lowercase", path)), &encrypted).unwrap(), "attackatdawn");
+
+        // the pad isn't overwritten by a second message
+        assert!(matches!(execute(&args(&format!("one-time-pad encrypt --pad {} --charset lowercase", path)), "retreat"), Err(CliError::Usage(_))));
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_block() {
+        let text = "Attack Berlin at Dawn";
+        let key = "--key 0123456789abcdef0123456789abcdef";
+
+        let encrypted = execute(&args(&format!("aes encrypt {} --mode cbc --iv fedcba9876543210fedcba9876543210", key)), text).unwrap();
+        assert_eq!(encrypted, "b9844b42a00e0a83cbb0ccc346daceeb658fcf011af943fc02a8fc61050ff1a0");
+
+        for mode in ["ecb", "cbc --iv 1", "cfb --iv 2", "pfb --iv 3", "ctr --nonce 4"] {
+            let encrypt = format!("aes encrypt {} --mode {} --format base64", key, mode);
+            let decrypt = format!("aes decrypt {} --mode {} --format base64", key, mode);
+            assert_eq!(round_trip(&encrypt, &decrypt, text), text);
+        }
+
+        assert!(matches!(execute(&args(&format!("aes decrypt {} --mode pbc --iv 1", key)), &encrypted), Err(CliError::Usage(_))));
+
+        assert_eq!(round_trip("des encrypt --key 133457799BBCDFF1", "des decrypt --key 133457799BBCDFF1", text), text);
+        assert_eq!(round_trip("des encrypt --key 133457799BBCDFF1", "des decrypt --key 133457799BBCDFF1", "8 bytes\0"), "8 bytes\0");
+        assert!(matches!(
+            execute(&args("des decrypt --key 133457799BBCDFF1"), &execute(&args("des encrypt --key 0E329232EA6D0D73"), text).unwrap()),
+            Err(CliError::Cipher(Error::MalformedCiphertext(_)))
+        ));
+    }
+
+    #[test]
+    fn test_public_key() {
+        assert_eq!(execute(&args("rsa encrypt --key 61,53"), "65 2022").unwrap(), "2790 2094");
+        assert_eq!(execute(&args("rsa decrypt --key 61,53"), "2790 2094").unwrap(), "65 2022");
+        assert!(matches!(execute(&args("rsa encrypt --key 61,53"), "65 3233"), Err(CliError::Cipher(Error::MessageTooLong { .. }))));
+        assert!(matches!(execute(&args("rsa decrypt --key 61,53"), "3300"), Err(CliError::Cipher(Error::MalformedCiphertext(_)))));
+
+        assert_eq!(
+            execute(&args("pohlig-hellman encrypt --key 769 --charset lowercase"), "powertothepeople").unwrap(),
+            "10872142021919680818197307942378"
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(execute(&args("enigma encrypt --key 3"), "hello"), Err(CliError::Usage(_))));
+        assert!(matches!(execute(&args("additive encrypt --charset lowercase"), "hello"), Err(CliError::Usage(_))));
+        assert!(matches!(execute(&args("additive encrypt --key x"), "hello"), Err(CliError::Usage(_))));
+        assert!(matches!(
+            execute(&args("additive encrypt --key 3 --charset lowercase"), "Hello"),
+            Err(CliError::Cipher(Error::UnknownCharacter { character: 'H', position: Some(0) }))
+        ));
+    }
+}
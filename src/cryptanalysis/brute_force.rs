@@ -0,0 +1,162 @@
+/*===================================================================
+ * Author: denosauabh
+ * Description: Exhaustive key search on the Additive, Multiplicative & Affine ciphers
+ * Reference: https://en.wikipedia.org/wiki/Affine_cipher#Cryptanalysis
+
+ * The keyspaces over a charset of length m are tiny:
+    - Additive        m keys
+    - Multiplicative  φ(m) keys, only units mod m (gcd(k, m) = 1) can be decrypted
+    - Affine          φ(m) * m keys
+
+ * Every key is tried, its plaintext scored with the n-gram fitness of the language.
+*===================================================================*/
+
+use crate::cryptanalysis::candidate::Candidate;
+use crate::simple_substitution::{additive::AdditiveCipher, affine::AffineCipher, multiplicative::MultiplicativeCipher};
+use crate::utils::analysis::NgramModel;
+use crate::utils::char_set::CharSet;
+use crate::utils::cipher::Cipher;
+use crate::utils::error::Error;
+use crate::utils::mod_arithmetic::ModArithmetic;
+
+pub struct BruteForce {
+    char_set: CharSet,
+    model: NgramModel,
+}
+
+impl BruteForce {
+    /// `char_set` is the charset of the cipher, `model` scores the candidate plaintexts
+    pub fn new(char_set: CharSet, model: NgramModel) -> Self {
+        Self { char_set, model }
+    }
+
+    /// English quadgram fitness
+    pub fn english(char_set: CharSet) -> Self {
+        Self::new(char_set, NgramModel::english(4).expect("4-grams of the English sample"))
+    }
+
+    /// keys with a multiplicative inverse modulo the charset length
+    pub fn units(&self) -> Vec<i32> {
+        let m = self.char_set.len() as i32;
+        (1..m).filter(|&k| ModArithmetic::euclidean_gcd(k, m) == 1).collect()
+    }
+
+    pub fn additive(&self, ciphertext: &str, top_k: usize) -> Result<Vec<Candidate<i32>>, Error> {
+        let cipher = AdditiveCipher::new(self.char_set.clone());
+        self.search(&cipher, ciphertext, 0..self.char_set.len() as i32, top_k)
+    }
+
+    pub fn multiplicative(&self, ciphertext: &str, top_k: usize) -> Result<Vec<Candidate<i32>>, Error> {
+        let cipher = MultiplicativeCipher::new(self.char_set.clone());
+        self.search(&cipher, ciphertext, self.units(), top_k)
+    }
+
+    /// keys are `(k, m)` of `C = kP + m`
+    pub fn affine(&self, ciphertext: &str, top_k: usize) -> Result<Vec<Candidate<(i32, i32)>>, Error> {
+        let cipher = AffineCipher::new(self.char_set.clone());
+        let shifts = self.char_set.len() as i32;
+
+        let keys = self
+            .units()
+            .into_iter()
+            .flat_map(|k| (0..shifts).map(move |m| (k, m)));
+
+        self.search(&cipher, ciphertext, keys, top_k)
+    }
+
+    /// decrypts with every key, keeps the `top_k` best scoring plaintexts
+    pub fn search<C: Cipher>(
+        &self,
+        cipher: &C,
+        ciphertext: &str,
+        keys: impl IntoIterator<Item = C::Key>,
+        top_k: usize,
+    ) -> Result<Vec<Candidate<C::Key>>, Error> {
+        self.char_set.check(ciphertext)?;
+
+        let mut candidates = keys
+            .into_iter()
+            .map(|key| {
+                let plaintext = cipher.decrypt(ciphertext, &key)?;
+                let score = self.model.score(&plaintext);
+
+                Ok(Candidate::new(key, plaintext, score))
+            })
+            .collect::<Result<Vec<Candidate<C::Key>>, Error>>()?;
+
+        Candidate::rank(&mut candidates);
+        candidates.truncate(top_k);
+
+        Ok(candidates)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAINTEXT: &str = "defend the east wall of the castle";
+
+    #[test]
+    fn test_units() {
+        let brute_force = BruteForce::english(CharSet::from_alphabet_lowercase());
+        assert_eq!(brute_force.units(), vec![1, 3, 5, 7, 9, 11, 15, 17, 19, 21, 23, 25]);
+
+        let brute_force = BruteForce::english(CharSet::from_numbers());
+        assert_eq!(brute_force.units(), vec![1, 3, 7, 9]);
+    }
+
+    #[test]
+    fn test_additive() {
+        let char_set = CharSet::from_string("abcdefghijklmnopqrstuvwxyz ");
+        let ciphertext = AdditiveCipher::new(char_set.clone()).encrypt(PLAINTEXT, 11).unwrap();
+
+        let candidates = BruteForce::english(char_set).additive(&ciphertext, 3).unwrap();
+
+        assert_eq!(candidates.len(), 3);
+        assert_eq!(candidates[0].key, 11);
+        assert_eq!(candidates[0].plaintext, PLAINTEXT);
+    }
+
+    #[test]
+    fn test_multiplicative() {
+        let char_set = CharSet::from_alphabet_lowercase();
+        let text = "meetmebytheoldbridgeinthemorning";
+        let ciphertext = MultiplicativeCipher::new(char_set.clone()).encrypt(text, 7).unwrap();
+
+        let candidates = BruteForce::english(char_set.clone()).multiplicative(&ciphertext, 20).unwrap();
+
+        // only the 12 units are tried
+        assert_eq!(candidates.len(), 12);
+        assert_eq!(candidates[0].key, 7);
+        assert_eq!(candidates[0].plaintext, text);
+
+        // 'a' is index 0, a fixed point of every key
+        let text = "attackthecastleatdawnwithallthearmies";
+        let ciphertext = MultiplicativeCipher::new(char_set.clone()).encrypt(text, 11).unwrap();
+
+        let candidates = BruteForce::english(char_set).multiplicative(&ciphertext, 1).unwrap();
+        assert_eq!(candidates[0].key, 11);
+        assert_eq!(candidates[0].plaintext, text);
+    }
+
+    #[test]
+    fn test_affine() {
+        let char_set = CharSet::from_string("abcdefghijklmnopqrstuvwxyz ");
+        let ciphertext = AffineCipher::new(char_set.clone()).encrypt(PLAINTEXT, 17, 20).unwrap();
+
+        let candidates = BruteForce::english(char_set).affine(&ciphertext, 5).unwrap();
+
+        assert_eq!(candidates.len(), 5);
+        assert_eq!(candidates[0].key, (17, 20));
+        assert_eq!(candidates[0].plaintext, PLAINTEXT);
+        assert!(candidates.windows(2).all(|w| w[0].score <= w[1].score));
+    }
+
+    #[test]
+    fn test_unknown_character() {
+        let brute_force = BruteForce::english(CharSet::from_alphabet_lowercase());
+        assert!(brute_force.additive("Abc", 1).is_err());
+    }
+}
@@ -0,0 +1,24 @@
+/*===================================================================
+ * Author: denosauabh
+ * Description: Result of an attack, a key with the plaintext it decrypts to
+ *===================================================================*/
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candidate<K> {
+    pub key: K,
+    pub plaintext: String,
+
+    /// how far the plaintext is from the language, the lower the better
+    pub score: f64,
+}
+
+impl<K> Candidate<K> {
+    pub fn new(key: K, plaintext: String, score: f64) -> Self {
+        Self { key, plaintext, score }
+    }
+
+    /// sorts from the best (lowest score) to the worst candidate
+    pub fn rank(candidates: &mut [Candidate<K>]) {
+        candidates.sort_by(|a, b| a.score.total_cmp(&b.score));
+    }
+}
@@ -0,0 +1,4 @@
+pub mod candidate;
+pub mod vigenere;
+pub mod brute_force;
+pub mod transposition;
\ No newline at end of file
@@ -0,0 +1,361 @@
+/*===================================================================
+ * Author: denosauabh
+ * Description: Ciphertext-only attack on the Columnar & Permutation transpositions
+ * Reference: https://en.wikipedia.org/wiki/Transposition_cipher#Detection_and_cryptanalysis
+              http://practicalcryptography.com/cryptanalysis/stochastic-searching/cryptanalysis-columnar-transposition-cipher/
+
+ * A transposition keeps the letters, only their order is lost. For every candidate key length:
+
+ * 1. anagramming, the ciphertext is cut into its columns (the letters which end up in the
+      same position of a row / block). Two columns which are neighbours in the plaintext form
+      common digrams on every row, the chain of best scoring neighbours is the starting key.
+
+ * 2. simulated annealing, the key is changed at random (swap, reverse or rotate columns),
+      better plaintexts (n-gram fitness) are kept, worse ones only with a probability which
+      falls with the temperature, so the search can leave a local optimum early on.
+
+ * Keys are the numeric keys of `Columnar::permute_decrypt` & `Permutation::permute`,
+ * `key[i]` is the (1 based) position of the i-th plaintext column in the ciphertext.
+*===================================================================*/
+
+use std::collections::HashMap;
+
+use rand::Rng;
+
+use crate::cryptanalysis::candidate::Candidate;
+use crate::transposition::{columnar::Columnar, permutation::Permutation};
+use crate::utils::analysis::NgramModel;
+use crate::utils::error::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranspositionKind {
+    /// columns of a grid read off in key order, `Columnar`
+    Columnar,
+
+    /// every block of key length permuted, `Permutation`
+    Permutation,
+}
+
+pub struct TranspositionBreaker {
+    model: NgramModel,
+    digrams: NgramModel,
+    max_key_len: usize,
+}
+
+impl TranspositionBreaker {
+    /// annealing steps for every key length
+    const ITERATIONS: usize = 3000;
+
+    /// starting temperature, in units of the model score
+    const TEMPERATURE: f64 = 0.2;
+
+    /// longest key `new` accepts, anagramming an irregular grid of n columns tries every split
+    /// into long & short columns, up to C(n, n / 2) of them (12870 for 16, 155 million for 30)
+    pub const MAX_KEY_LEN: usize = 16;
+
+    /// `model` scores the plaintexts, `digrams` (2-grams) links the columns when anagramming
+    pub fn new(model: NgramModel, digrams: NgramModel, max_key_len: usize) -> Result<Self, Error> {
+        if digrams.n() != 2 {
+            return Err(Error::InvalidKey(format!("anagramming needs a 2-gram model, not {}-grams", digrams.n())));
+        }
+        if !(2..=Self::MAX_KEY_LEN).contains(&max_key_len) {
+            return Err(Error::InvalidKey(format!("maximum key length must be between 2 and {}", Self::MAX_KEY_LEN)));
+        }
+
+        Ok(Self { model, digrams, max_key_len })
+    }
+
+    /// English quadgram fitness, keys up to 12 columns
+    pub fn english() -> Self {
+        Self {
+            model: NgramModel::english(4).expect("4-grams of the English sample"),
+            digrams: NgramModel::english(2).expect("2-grams of the English sample"),
+            max_key_len: 12,
+        }
+    }
+
+    /// key lengths possible for the ciphertext, a permutation ciphertext is made of whole blocks
+    pub fn key_lengths(&self, ciphertext: &str, kind: TranspositionKind) -> Vec<usize> {
+        let len = ciphertext.chars().count();
+
+        (2..=self.max_key_len.min(len))
+            .filter(|&n| kind == TranspositionKind::Columnar || len.is_multiple_of(n))
+            .collect()
+    }
+
+    pub fn decrypt(&self, ciphertext: &str, kind: TranspositionKind, key: &[usize]) -> Result<String, Error> {
+        match kind {
+            TranspositionKind::Columnar => Columnar::new().permute_decrypt(ciphertext, key.to_vec()),
+            TranspositionKind::Permutation => Permutation::new().permute(ciphertext, key.to_vec()),
+        }
+    }
+
+    /// starting key of `length` columns from the digrams of neighbouring columns.
+    /// The first `len % length` plaintext columns of an irregular columnar grid are one letter longer,
+    /// which of the ciphertext columns they are is unknown, so every choice is tried, hence `MAX_KEY_LEN`.
+    pub fn anagram(&self, ciphertext: &str, kind: TranspositionKind, length: usize) -> Result<Vec<usize>, Error> {
+        let text = ciphertext.chars().collect::<Vec<char>>();
+
+        if length == 0 || length > text.len() {
+            return Err(Error::InvalidKeyLength { expected: text.len(), found: length });
+        }
+        if length > Self::MAX_KEY_LEN {
+            return Err(Error::InvalidKeyLength { expected: Self::MAX_KEY_LEN, found: length });
+        }
+
+        let (rows, long) = (text.len() / length, text.len() % length);
+
+        if kind == TranspositionKind::Permutation && long != 0 {
+            return Err(Error::MalformedCiphertext(format!("{} characters aren't whole blocks of {}", text.len(), length)));
+        }
+
+        let mut digrams = HashMap::new();
+
+        let (order, _) = Self::long_columns(length, long)
+            .into_iter()
+            .map(|is_long| {
+                let columns = match kind {
+                    TranspositionKind::Columnar => Self::cut(&text, rows, &is_long),
+                    TranspositionKind::Permutation => (0..length).map(|c| text.iter().skip(c).step_by(length).copied().collect()).collect(),
+                };
+
+                self.chain(&columns, &is_long, long, &mut digrams)
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .expect("at least one choice of long columns");
+
+        Ok(order.into_iter().map(|c| c + 1).collect())
+    }
+
+    /// every way to pick the `long` ciphertext columns out of `length`, `is_long[c]`
+    fn long_columns(length: usize, long: usize) -> Vec<Vec<bool>> {
+        if long == 0 || long == length {
+            return vec![vec![long != 0; length]];
+        }
+
+        let mut without = Self::long_columns(length - 1, long);
+        let with = Self::long_columns(length - 1, long - 1);
+
+        without.iter_mut().for_each(|choice| choice.push(false));
+        without.extend(with.into_iter().map(|mut choice| {
+            choice.push(true);
+            choice
+        }));
+
+        without
+    }
+
+    /// the ciphertext cut into its columns, a long one has `rows + 1` letters
+    fn cut(text: &[char], rows: usize, is_long: &[bool]) -> Vec<Vec<char>> {
+        let mut start = 0;
+
+        is_long
+            .iter()
+            .map(|&long| {
+                let end = start + rows + usize::from(long);
+                let column = text[start..end].to_vec();
+                start = end;
+                column
+            })
+            .collect()
+    }
+
+    /// greedy chain of best scoring neighbours from every first column, the cheapest one wins.
+    /// The `long` long columns come first, (order, cost)
+    fn chain(&self, columns: &[Vec<char>], is_long: &[bool], long: usize, digrams: &mut HashMap<(char, char), f64>) -> (Vec<usize>, f64) {
+        let length = columns.len();
+
+        // link[a][b], how well column b follows column a (lower is better)
+        let link = columns
+            .iter()
+            .map(|a| {
+                columns
+                    .iter()
+                    .map(|b| {
+                        a.iter()
+                            .zip(b)
+                            .map(|(&x, &y)| *digrams.entry((x, y)).or_insert_with(|| self.digrams.score(&format!("{}{}", x, y))))
+                            .sum::<f64>()
+                    })
+                    .collect::<Vec<f64>>()
+            })
+            .collect::<Vec<Vec<f64>>>();
+
+        // the k-th plaintext column is long when k < long
+        let fits = |c: usize, k: usize| long == 0 || is_long[c] == (k < long);
+
+        (0..length)
+            .filter(|&first| fits(first, 0))
+            .map(|first| {
+                let mut order = vec![first];
+                let mut cost = 0.0;
+
+                while order.len() < length {
+                    let last = order[order.len() - 1];
+                    let next = (0..length)
+                        .filter(|&c| !order.contains(&c) && fits(c, order.len()))
+                        .min_by(|&a, &b| link[last][a].total_cmp(&link[last][b]))
+                        .expect("unused column");
+
+                    cost += link[last][next];
+                    order.push(next);
+                }
+
+                (order, cost)
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .expect("at least one column")
+    }
+
+    /// simulated annealing from the given key, returns the best key seen
+    pub fn anneal(&self, ciphertext: &str, kind: TranspositionKind, key: Vec<usize>, rng: &mut impl Rng) -> Result<Candidate<Vec<usize>>, Error> {
+        let plaintext = self.decrypt(ciphertext, kind, &key)?;
+        let score = self.model.score(&plaintext);
+        let mut current = Candidate::new(key, plaintext, score);
+        let mut best = current.clone();
+
+        if current.key.len() < 2 {
+            return Ok(best);
+        }
+
+        for i in 0..Self::ITERATIONS {
+            let temperature = Self::TEMPERATURE * (1.0 - i as f64 / Self::ITERATIONS as f64);
+
+            let key = Self::mutate(&current.key, rng);
+            let plaintext = self.decrypt(ciphertext, kind, &key)?;
+            let score = self.model.score(&plaintext);
+
+            let accept = score < current.score
+                || (temperature > 0.0 && rng.gen::<f64>() < ((current.score - score) / temperature).exp());
+
+            if accept {
+                current = Candidate::new(key, plaintext, score);
+
+                if current.score < best.score {
+                    best = current.clone();
+                }
+            }
+        }
+
+        Ok(best)
+    }
+
+    /// anagrams & anneals every possible key length, keeps the `count` best keys
+    pub fn break_cipher(&self, ciphertext: &str, kind: TranspositionKind, count: usize, rng: &mut impl Rng) -> Result<Vec<Candidate<Vec<usize>>>, Error> {
+        let lengths = self.key_lengths(ciphertext, kind);
+
+        if lengths.is_empty() {
+            return Err(Error::MalformedCiphertext("ciphertext is too short to transpose".to_string()));
+        }
+
+        let mut candidates = lengths
+            .into_iter()
+            .map(|length| {
+                let key = self.anagram(ciphertext, kind, length)?;
+                self.anneal(ciphertext, kind, key, rng)
+            })
+            .collect::<Result<Vec<Candidate<Vec<usize>>>, Error>>()?;
+
+        Candidate::rank(&mut candidates);
+        candidates.truncate(count);
+
+        Ok(candidates)
+    }
+
+    /// swaps two columns, reverses or rotates a run of columns
+    fn mutate(key: &[usize], rng: &mut impl Rng) -> Vec<usize> {
+        let mut key = key.to_vec();
+        let (a, b) = (rng.gen_range(0..key.len()), rng.gen_range(0..key.len()));
+        let (start, end) = (a.min(b), a.max(b) + 1);
+
+        match rng.gen_range(0..3) {
+            0 => key.swap(a, b),
+            1 => key[start..end].reverse(),
+            _ => key[start..end].rotate_left(1),
+        }
+
+        key
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    const PLAINTEXT: &str = "itisatruthuniversallyacknowledgedthatasinglemaninpossessionofagoodfortunemustbeinwantofawife\
+        howeverlittleknownthefeelingsorviewsofsuchamanmaybeonhisfirstenteringaneighbourhood";
+
+    #[test]
+    fn test_key_lengths() {
+        let breaker = TranspositionBreaker::english();
+
+        assert_eq!(breaker.key_lengths("abcdefghijkl", TranspositionKind::Permutation), vec![2, 3, 4, 6, 12]);
+        assert_eq!(breaker.key_lengths("abcdefg", TranspositionKind::Columnar), vec![2, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn test_anagram() {
+        let breaker = TranspositionBreaker::english();
+        let key = vec![3, 1, 4, 2, 5];
+
+        // 35 rows of a regular grid are enough digrams to put the columns in order
+        let text = &PLAINTEXT[..175];
+        let ciphertext = Columnar::new().permute_encrypt(text, key.clone()).unwrap();
+        assert_eq!(breaker.anagram(&ciphertext, TranspositionKind::Columnar, 5).unwrap(), key);
+
+        assert!(breaker.anagram(&ciphertext, TranspositionKind::Columnar, 0).is_err());
+
+        // irregular grid, 34 rows & 3 long columns, the split is found along with the order
+        let text = &PLAINTEXT[..173];
+        let ciphertext = Columnar::new().permute_encrypt(text, key.clone()).unwrap();
+        assert_eq!(breaker.anagram(&ciphertext, TranspositionKind::Columnar, 5).unwrap(), key);
+
+        assert!(breaker.anagram(&ciphertext, TranspositionKind::Permutation, 5).is_err());
+    }
+
+    #[test]
+    fn test_break_columnar() {
+        let breaker = TranspositionBreaker::english();
+        let mut rng = StdRng::seed_from_u64(7);
+
+        // irregular grid
+        let key = Columnar::new().parse_key("ZEBRAS");
+        let ciphertext = Columnar::new().permute_encrypt(PLAINTEXT, key.clone()).unwrap();
+
+        let candidates = breaker.break_cipher(&ciphertext, TranspositionKind::Columnar, 3, &mut rng).unwrap();
+
+        assert_eq!(candidates.len(), 3);
+        assert_eq!(candidates[0].key, key);
+        assert_eq!(candidates[0].plaintext, PLAINTEXT);
+        assert!(candidates.windows(2).all(|w| w[0].score <= w[1].score));
+    }
+
+    #[test]
+    fn test_break_permutation() {
+        let breaker = TranspositionBreaker::english();
+        let mut rng = StdRng::seed_from_u64(7);
+
+        // whole blocks, a permutation loses the characters of an incomplete one
+        let text = &PLAINTEXT[..156];
+        let ciphertext = Permutation::new().encrypt(text, "CIPHER").unwrap();
+
+        let candidates = breaker.break_cipher(&ciphertext, TranspositionKind::Permutation, 1, &mut rng).unwrap();
+
+        assert_eq!(candidates[0].key, Permutation::new().parse_key("CIPHER"));
+        assert_eq!(candidates[0].plaintext, text);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut rng = StdRng::seed_from_u64(7);
+
+        assert!(TranspositionBreaker::new(NgramModel::english(4).unwrap(), NgramModel::english(3).unwrap(), 10).is_err());
+        assert!(TranspositionBreaker::new(NgramModel::english(4).unwrap(), NgramModel::english(2).unwrap(), 1).is_err());
+        assert!(TranspositionBreaker::new(NgramModel::english(4).unwrap(), NgramModel::english(2).unwrap(), 30).is_err());
+        assert!(TranspositionBreaker::new(NgramModel::english(4).unwrap(), NgramModel::english(2).unwrap(), TranspositionBreaker::MAX_KEY_LEN).is_ok());
+        assert!(TranspositionBreaker::english().anagram(&PLAINTEXT[..45], TranspositionKind::Columnar, 30).is_err());
+        assert!(TranspositionBreaker::english().break_cipher("a", TranspositionKind::Columnar, 1, &mut rng).is_err());
+    }
+}
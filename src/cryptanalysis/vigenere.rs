@@ -0,0 +1,342 @@
+/*===================================================================
+ * Author: denosauabh
+ * Description: Ciphertext-only attack on the Vigenère Cipher & the other periodic ciphers
+                (Beaufort, Variant Beaufort, Gronsfeld, Porta) with `with_tableau`
+ * Reference: https://en.wikipedia.org/wiki/Vigenère_cipher#Cryptanalysis
+              https://en.wikipedia.org/wiki/Kasiski_examination
+
+ * 1. key length
+    - Kasiski: distances between repeated n-grams are multiples of the key length
+    - Friedman: `L ≈ (κp - κr) * N / ((N - 1) * κo - κr * N + κp)`,
+      κp IoC of the language, κr = 1/m, κo IoC of the N characters of the ciphertext
+    - every column (every L-th character) of the right length is a Caesar shift of the language,
+      so its IoC is close to κp
+
+ * 2. key, every column is a Caesar shift (a row of the tableau), the key character whose row
+      gives the lowest χ² against the language frequencies is the key character of that column.
+      Porta keys come out with the first letter of every pair (A for A/B, C for C/D, ...)
+
+ * The charset of the cipher is the charset of the reference frequencies,
+ * ciphertext characters outside of it are ignored.
+*===================================================================*/
+
+use std::collections::HashMap;
+
+use crate::cryptanalysis::candidate::Candidate;
+use crate::polyalphebatic_substitution::periodic::{PeriodicCipher, Tableau};
+use crate::utils::analysis::{Analysis, Frequencies};
+use crate::utils::char_set::CharSet;
+use crate::utils::error::Error;
+
+/// a key length with the evidence for it
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyLength {
+    pub length: usize,
+
+    /// average IoC of the columns
+    pub ioc: f64,
+
+    /// fraction of the repeated n-gram distances which are a multiple of the length
+    pub kasiski: f64,
+}
+
+pub struct VigenèreBreaker {
+    reference: Frequencies,
+    max_key_len: usize,
+    tableau: Tableau,
+}
+
+impl VigenèreBreaker {
+    const KASISKI_NGRAM: usize = 3;
+
+    /// how many of the most likely key lengths are tried by `break_cipher`
+    const KEY_LENGTHS_TRIED: usize = 5;
+
+    pub fn new(reference: Frequencies, max_key_len: usize) -> Result<Self, Error> {
+        if max_key_len == 0 {
+            return Err(Error::InvalidKey("maximum key length must be at least 1".to_string()));
+        }
+
+        Ok(Self { reference, max_key_len, tableau: Tableau::Vigenère })
+    }
+
+    pub fn english() -> Self {
+        Self { reference: Frequencies::english(), max_key_len: 20, tableau: Tableau::Vigenère }
+    }
+
+    /// attacks another periodic cipher, Vigenère by default
+    pub fn with_tableau(mut self, tableau: Tableau) -> Self {
+        self.tableau = tableau;
+        self
+    }
+
+    fn char_set(&self) -> &CharSet {
+        self.reference.char_set()
+    }
+
+    /// Kasiski examination, number of repeated n-gram distances each key length divides,
+    /// sorted by votes (then by length)
+    pub fn kasiski(&self, ciphertext: &str) -> Vec<(usize, usize)> {
+        let chars = Analysis::filter(ciphertext, self.char_set());
+        let mut positions: HashMap<&[char], Vec<usize>> = HashMap::new();
+
+        for (i, ngram) in chars.windows(Self::KASISKI_NGRAM).enumerate() {
+            positions.entry(ngram).or_default().push(i);
+        }
+
+        let distances = positions
+            .values()
+            .flat_map(|p| p.windows(2).map(|w| w[1] - w[0]).collect::<Vec<usize>>())
+            .collect::<Vec<usize>>();
+
+        let mut votes = (2..=self.max_key_len)
+            .map(|length| (length, distances.iter().filter(|&&d| d.is_multiple_of(length)).count()))
+            .filter(|&(_, count)| count > 0)
+            .collect::<Vec<(usize, usize)>>();
+
+        votes.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        votes
+    }
+
+    /// Friedman test, estimated key length (not rounded), `None` if the text looks random
+    pub fn friedman(&self, ciphertext: &str) -> Option<f64> {
+        let kp = self.reference.index_of_coincidence();
+        let kr = 1.0 / self.char_set().len() as f64;
+        let ko = Analysis::index_of_coincidence(ciphertext, self.char_set());
+        let n = Analysis::filter(ciphertext, self.char_set()).len() as f64;
+
+        let denominator = (n - 1.0) * ko - kr * n + kp;
+
+        if n < 2.0 || denominator <= 0.0 {
+            return None;
+        }
+
+        Some((kp - kr) * n / denominator)
+    }
+
+    /// average IoC of the columns of the ciphertext split by `length`
+    pub fn column_ioc(&self, ciphertext: &str, length: usize) -> f64 {
+        let columns = Self::columns(&Analysis::filter(ciphertext, self.char_set()), length);
+
+        columns
+            .iter()
+            .map(|column| Analysis::index_of_coincidence(column, self.char_set()))
+            .sum::<f64>() / length as f64
+    }
+
+    /// all key lengths up to the maximum, from the most to the least likely.
+    /// Ranked by how close the column IoC is to the language (relative to random text),
+    /// with the Kasiski fraction as a bonus.
+    pub fn key_lengths(&self, ciphertext: &str) -> Vec<KeyLength> {
+        let len = Analysis::filter(ciphertext, self.char_set()).len();
+        let kasiski = self.kasiski(ciphertext);
+        let total_votes = kasiski.iter().map(|&(_, votes)| votes).max().unwrap_or(0);
+
+        let kp = self.reference.index_of_coincidence();
+        let kr = 1.0 / self.char_set().len() as f64;
+
+        let mut lengths = (1..=self.max_key_len.min(len / 2).max(1))
+            .map(|length| KeyLength {
+                length,
+                ioc: self.column_ioc(ciphertext, length),
+                kasiski: kasiski
+                    .iter()
+                    .find(|&&(l, _)| l == length)
+                    .map_or(0.0, |&(_, votes)| votes as f64 / total_votes as f64),
+            })
+            .collect::<Vec<KeyLength>>();
+
+        let score = |k: &KeyLength| (k.ioc - kp).abs() / (kp - kr) - 0.5 * k.kasiski;
+        lengths.sort_by(|a, b| score(a).total_cmp(&score(b)).then(a.length.cmp(&b.length)));
+
+        lengths
+    }
+
+    /// recovers the key of the given length, column by column
+    pub fn break_key(&self, ciphertext: &str, length: usize) -> Result<Candidate<String>, Error> {
+        if length == 0 {
+            return Err(Error::InvalidKeyLength { expected: 1, found: 0 });
+        }
+
+        let char_set = self.char_set();
+        let chars = Analysis::filter(ciphertext, char_set);
+        let columns = Self::columns(&chars, length);
+
+        let key = columns
+            .iter()
+            .map(|column| {
+                let mut best = (0, f64::INFINITY);
+
+                for k in 0..char_set.len() {
+                    let chi = Analysis::chi_squared(&self.decrypt_column(column, k)?, &self.reference);
+                    if chi < best.1 {
+                        best = (k, chi);
+                    }
+                }
+
+                char_set.char_at(best.0)
+            })
+            .collect::<Result<String, Error>>()?;
+
+        let key = Self::shortest_period(&key);
+
+        let ciphertext = chars.iter().collect::<String>();
+        let plaintext = PeriodicCipher::new(char_set.clone(), self.tableau).decrypt(&ciphertext, &key)?;
+        let score = Analysis::chi_squared(&plaintext, &self.reference) / plaintext.chars().count().max(1) as f64;
+
+        Ok(Candidate::new(key, plaintext, score))
+    }
+
+    /// ranked candidate keys & plaintexts, from the most likely key lengths
+    pub fn break_cipher(&self, ciphertext: &str, count: usize) -> Result<Vec<Candidate<String>>, Error> {
+        if Analysis::filter(ciphertext, self.char_set()).is_empty() {
+            return Err(Error::MalformedCiphertext("no character of the charset in the ciphertext".to_string()));
+        }
+
+        let mut candidates: Vec<Candidate<String>> = Vec::new();
+
+        let lengths = self
+            .key_lengths(ciphertext)
+            .iter()
+            .take(count.max(Self::KEY_LENGTHS_TRIED))
+            .map(|key_length| key_length.length)
+            .collect::<Vec<usize>>();
+
+        // a multiple of a more likely length can only repeat its key, with fewer characters per column
+        let lengths = lengths
+            .iter()
+            .enumerate()
+            .filter(|&(i, &length)| !lengths[..i].iter().any(|&d| d > 1 && length.is_multiple_of(d)))
+            .map(|(_, &length)| length);
+
+        for length in lengths {
+            let candidate = self.break_key(ciphertext, length)?;
+
+            // multiples of the key length give the same key
+            if !candidates.iter().any(|c| c.key == candidate.key) {
+                candidates.push(candidate);
+            }
+        }
+
+        Candidate::rank(&mut candidates);
+        candidates.truncate(count);
+
+        Ok(candidates)
+    }
+
+    /// every `length`-th character, starting from 0..length
+    fn columns(chars: &[char], length: usize) -> Vec<String> {
+        (0..length)
+            .map(|start| chars.iter().skip(start).step_by(length).collect())
+            .collect()
+    }
+
+    /// decrypts a column with the row of the key character `k`
+    fn decrypt_column(&self, column: &str, k: usize) -> Result<String, Error> {
+        let char_set = self.char_set();
+        let m = char_set.len();
+
+        char_set
+            .indices_of(column)?
+            .iter()
+            .map(|&c| char_set.char_at(self.tableau.decrypt(c, k, m)))
+            .collect()
+    }
+
+    /// "lemonlemon" -> "lemon"
+    fn shortest_period(key: &str) -> String {
+        let chars = key.chars().collect::<Vec<char>>();
+
+        (1..=chars.len())
+            .find(|&p| chars.len().is_multiple_of(p) && (p..chars.len()).all(|i| chars[i] == chars[i - p]))
+            .map_or(key.to_string(), |p| chars[..p].iter().collect())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAINTEXT: &str = "it is a truth universally acknowledged that a single man in possession of a good fortune \
+        must be in want of a wife however little known the feelings or views of such a man may be on his first \
+        entering a neighbourhood this truth is so well fixed in the minds of the surrounding families that he is \
+        considered as the rightful property of some one or other of their daughters";
+
+    fn encrypt(char_set: CharSet, text: &str, key: &str) -> String {
+        encrypt_with(Tableau::Vigenère, char_set, text, key)
+    }
+
+    fn encrypt_with(tableau: Tableau, char_set: CharSet, text: &str, key: &str) -> String {
+        PeriodicCipher::new(char_set.clone(), tableau).encrypt(&Analysis::filter(text, &char_set).iter().collect::<String>(), key).unwrap()
+    }
+
+    #[test]
+    fn test_key_length() {
+        let breaker = VigenèreBreaker::english();
+        let ciphertext = encrypt(CharSet::from_alphabet_lowercase(), PLAINTEXT, "lemon");
+
+        assert_eq!(breaker.key_lengths(&ciphertext)[0].length, 5);
+        assert!(breaker.kasiski(&ciphertext).iter().take(3).any(|&(length, _)| length == 5));
+
+        // Friedman is only a rough estimate on a short text, but tells a Caesar shift from a longer key
+        assert!(breaker.friedman(&ciphertext).unwrap() > 2.0);
+
+        let caesar = encrypt(CharSet::from_alphabet_lowercase(), PLAINTEXT, "k");
+        let friedman = breaker.friedman(&caesar).unwrap();
+        assert!((0.5..1.5).contains(&friedman), "{}", friedman);
+    }
+
+    #[test]
+    fn test_break_cipher() {
+        let breaker = VigenèreBreaker::english();
+
+        for key in ["lemon", "cryptograph", "k"] {
+            let ciphertext = encrypt(CharSet::from_alphabet_lowercase(), PLAINTEXT, key);
+            let candidates = breaker.break_cipher(&ciphertext, 3).unwrap();
+
+            assert!(candidates.len() <= 3);
+            assert_eq!(candidates[0].key, key);
+            assert_eq!(candidates[0].plaintext, PLAINTEXT.replace(' ', ""));
+            assert!(candidates.windows(2).all(|w| w[0].score <= w[1].score));
+        }
+    }
+
+    #[test]
+    fn test_other_tableaux() {
+        for (tableau, key) in [(Tableau::Beaufort, "lemon"), (Tableau::VariantBeaufort, "secret"), (Tableau::Porta, "kemom")] {
+            let breaker = VigenèreBreaker::english().with_tableau(tableau);
+            let ciphertext = encrypt_with(tableau, CharSet::from_alphabet_lowercase(), PLAINTEXT, key);
+            let candidates = breaker.break_cipher(&ciphertext, 1).unwrap();
+
+            assert_eq!(candidates[0].key, key, "{:?}", tableau);
+            assert_eq!(candidates[0].plaintext, PLAINTEXT.replace(' ', ""));
+        }
+
+        // a Porta key comes out with the first letter of every pair
+        let breaker = VigenèreBreaker::english().with_tableau(Tableau::Porta);
+        let ciphertext = encrypt_with(Tableau::Porta, CharSet::from_alphabet_lowercase(), PLAINTEXT, "lemon");
+        assert_eq!(breaker.break_cipher(&ciphertext, 1).unwrap()[0].key, "kemom");
+    }
+
+    #[test]
+    fn test_custom_char_set() {
+        // space is part of the charset, with frequencies measured on a corpus
+        let char_set = CharSet::from_string("abcdefghijklmnopqrstuvwxyz ");
+        let reference = Frequencies::from_text(PLAINTEXT, char_set.clone()).unwrap();
+        let breaker = VigenèreBreaker::new(reference, 10).unwrap();
+
+        let ciphertext = encrypt(char_set, PLAINTEXT, "key s");
+        let candidates = breaker.break_cipher(&ciphertext, 1).unwrap();
+
+        assert_eq!(candidates[0].key, "key s");
+        assert_eq!(candidates[0].plaintext, PLAINTEXT);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(VigenèreBreaker::new(Frequencies::english(), 0).is_err());
+        assert!(VigenèreBreaker::english().break_cipher("1234", 1).is_err());
+        assert!(VigenèreBreaker::english().break_key("abc", 0).is_err());
+    }
+}
@@ -0,0 +1,148 @@
+/*===================================================================
+ * Four-Square Cipher
+ * Author: denosauabh
+ * Description: Implementation of the Four-Square Cipher, a digraphic substitution
+                on four Polybius squares, two plain & two keyed
+ * Reference: https://en.wikipedia.org/wiki/Four-square_cipher
+
+ * keywords "EXAMPLE" & "KEYWORD", A..Z without Q:
+
+    a b c d e   E X A M P
+    f g h i j   L B C D F
+    k l m n o   G H I J K
+    p r s t u   N O R S T
+    v w x y z   U V W Y Z
+
+    K E Y W O   a b c d e
+    R D A B C   f g h i j
+    F G H I J   k l m n o
+    L M N P S   p r s t u
+    T U V X Z   v w x y z
+
+ * The first letter of a pair is found in the top left plain square, the second in the bottom right.
+ * They are replaced by the letters of the top right & bottom left keyed squares
+ * in the other corners of their rectangle.
+*===================================================================*/
+
+use crate::utils::cipher::Cipher;
+use crate::utils::error::Error;
+use crate::utils::polybius::{PolybiusSquare, SquareOptions};
+
+pub struct FourSquare {
+    options: SquareOptions,
+}
+
+impl FourSquare {
+    pub fn new() -> Self {
+        Self { options: SquareOptions::default() }
+    }
+
+    /// the options need a filler
+    pub fn with_options(options: SquareOptions) -> Result<Self, Error> {
+        options.filler()?;
+        Ok(Self { options })
+    }
+
+    /// pads an odd length text with the filler
+    pub fn encrypt(&self, text: &str, top_right: &str, bottom_left: &str) -> Result<String, Error> {
+        let mut chars = text.chars().collect::<Vec<char>>();
+
+        if !chars.len().is_multiple_of(2) {
+            chars.push(self.options.filler()?);
+        }
+
+        let plain = self.options.plain();
+        let top_right = self.options.keyed(top_right)?;
+        let bottom_left = self.options.keyed(bottom_left)?;
+
+        Self::substitute(&chars, (plain, plain), (&top_right, &bottom_left))
+    }
+
+    pub fn decrypt(&self, text: &str, top_right: &str, bottom_left: &str) -> Result<String, Error> {
+        let chars = text.chars().collect::<Vec<char>>();
+
+        if !chars.len().is_multiple_of(2) {
+            return Err(Error::MalformedCiphertext("ciphertext must have an even length".to_string()));
+        }
+
+        let plain = self.options.plain();
+        let top_right = self.options.keyed(top_right)?;
+        let bottom_left = self.options.keyed(bottom_left)?;
+
+        Self::substitute(&chars, (&top_right, &bottom_left), (plain, plain))
+    }
+
+    /// finds the pair in the `from` squares, replaces it by the rectangle corners in the `to` squares
+    fn substitute(
+        chars: &[char],
+        from: (&PolybiusSquare, &PolybiusSquare),
+        to: (&PolybiusSquare, &PolybiusSquare),
+    ) -> Result<String, Error> {
+        let mut substituted = String::with_capacity(chars.len());
+
+        for (i, pair) in chars.chunks(2).enumerate() {
+            let (r1, c1) = from.0.position(pair[0]).map_err(|_| Error::UnknownCharacter { character: pair[0], position: Some(i * 2) })?;
+            let (r2, c2) = from.1.position(pair[1]).map_err(|_| Error::UnknownCharacter { character: pair[1], position: Some(i * 2 + 1) })?;
+
+            substituted.push(to.0.char_at(r1, c2)?);
+            substituted.push(to.1.char_at(r2, c1)?);
+        }
+
+        Ok(substituted)
+    }
+}
+
+impl Default for FourSquare {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Cipher for FourSquare {
+    /// keywords of the top right & bottom left square
+    type Key = (String, String);
+
+    fn encrypt(&self, text: &str, key: &(String, String)) -> Result<String, Error> {
+        self.encrypt(text, &key.0, &key.1)
+    }
+
+    fn decrypt(&self, text: &str, key: &(String, String)) -> Result<String, Error> {
+        self.decrypt(text, &key.0, &key.1)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::char_set::CharSet;
+
+    fn four_square() -> FourSquare {
+        let options = SquareOptions::new(CharSet::from_string("ABCDEFGHIJKLMNOPRSTUVWXYZ"), None).unwrap();
+        FourSquare::with_options(options.with_filler('X').unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_encrypt() {
+        let encrypted = four_square().encrypt("HELPMEOBIWANKENOBI", "EXAMPLE", "KEYWORD").unwrap();
+        assert_eq!(encrypted, "FYGMKYHOBXMFKKKIMD");
+    }
+
+    #[test]
+    fn test_decrypt() {
+        let decrypted = four_square().decrypt("FYGMKYHOBXMFKKKIMD", "EXAMPLE", "KEYWORD").unwrap();
+        assert_eq!(decrypted, "HELPMEOBIWANKENOBI");
+
+        assert!(four_square().decrypt("FYG", "EXAMPLE", "KEYWORD").is_err());
+        assert!(four_square().decrypt("FQ", "EXAMPLE", "KEYWORD").is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let four_square = FourSquare::new();
+        let encrypted = four_square.encrypt("ATTACKATDAWN", "PLAYFAIR", "CIPHER").unwrap();
+        assert_eq!(four_square.decrypt(&encrypted, "PLAYFAIR", "CIPHER").unwrap(), "ATTACKATDAWN");
+
+        assert!(FourSquare::with_options(SquareOptions::new(CharSet::from_range('A', 'Y'), None).unwrap()).is_err());
+    }
+}
@@ -0,0 +1,3 @@
+pub mod playfair;
+pub mod two_square;
+pub mod four_square;
\ No newline at end of file
@@ -0,0 +1,209 @@
+/*===================================================================
+ * Playfair Cipher
+ * Author: denosauabh
+ * Description: Implementation of the Playfair Cipher, a digraphic substitution on a keyed Polybius square
+ * Reference: https://en.wikipedia.org/wiki/Playfair_cipher
+
+ * The text is split into pairs (digraphs), a doubled letter is split with the filler
+   (a doubled filler with the letter after it in the square) & an odd length padded with it:
+
+    HIDETHEGOLDINTHETREESTUMP -> HI DE TH EG OL DI NT HE TR EX ES TU MP
+
+ * Every pair is replaced using the square:
+    - same row     letters to their right (wrapping around)
+    - same column  letters below them (wrapping around)
+    - otherwise    the other corners of their rectangle, each letter keeps its row
+
+ * Decryption doesn't remove the fillers, they can't be told from the text.
+*===================================================================*/
+
+use crate::utils::cipher::Cipher;
+use crate::utils::error::Error;
+use crate::utils::polybius::{PolybiusSquare, SquareOptions};
+
+pub struct Playfair {
+    options: SquareOptions,
+}
+
+impl Playfair {
+    pub fn new() -> Self {
+        Self { options: SquareOptions::default() }
+    }
+
+    /// the options need a filler
+    pub fn with_options(options: SquareOptions) -> Result<Self, Error> {
+        options.filler()?;
+        Ok(Self { options })
+    }
+
+    pub fn square(&self, keyword: &str) -> Result<PolybiusSquare, Error> {
+        self.options.keyed(keyword)
+    }
+
+    pub fn encrypt(&self, text: &str, keyword: &str) -> Result<String, Error> {
+        let square = self.square(keyword)?;
+
+        self.digraphs(&square, text)?
+            .into_iter()
+            .map(|(a, b)| self.substitute(&square, a, b, true))
+            .collect::<Result<Vec<String>, Error>>()
+            .map(|pairs| pairs.concat())
+    }
+
+    pub fn decrypt(&self, text: &str, keyword: &str) -> Result<String, Error> {
+        let square = self.square(keyword)?;
+        let chars = text.chars().collect::<Vec<char>>();
+
+        if !chars.len().is_multiple_of(2) {
+            return Err(Error::MalformedCiphertext("ciphertext must have an even length".to_string()));
+        }
+
+        chars
+            .chunks(2)
+            .map(|pair| {
+                if square.normalize(pair[0]) == square.normalize(pair[1]) {
+                    return Err(Error::MalformedCiphertext(format!("{}{} is a doubled letter", pair[0], pair[1])));
+                }
+
+                self.substitute(&square, pair[0], pair[1], false)
+            })
+            .collect::<Result<Vec<String>, Error>>()
+            .map(|pairs| pairs.concat())
+    }
+
+    /// pairs of the text, doubled letters split & the last one padded with the filler
+    pub fn digraphs(&self, square: &PolybiusSquare, text: &str) -> Result<Vec<(char, char)>, Error> {
+        let mut chars = Vec::new();
+
+        for (i, c) in text.chars().enumerate() {
+            if !square.contains(c) {
+                return Err(Error::UnknownCharacter { character: c, position: Some(i) });
+            }
+            chars.push(square.normalize(c));
+        }
+
+        let mut digraphs = Vec::with_capacity(chars.len() / 2 + 1);
+        let mut i = 0;
+
+        while i < chars.len() {
+            let a = chars[i];
+
+            match chars.get(i + 1) {
+                Some(&b) if b != a => {
+                    digraphs.push((a, b));
+                    i += 2;
+                },
+                _ => {
+                    digraphs.push((a, self.filler_for(square, a)?));
+                    i += 1;
+                },
+            }
+        }
+
+        Ok(digraphs)
+    }
+
+    /// the filler, or the letter after it when the filler itself is doubled
+    fn filler_for(&self, square: &PolybiusSquare, c: char) -> Result<char, Error> {
+        let filler = self.options.filler()?;
+
+        if c != filler {
+            return Ok(filler);
+        }
+
+        let chars = square.chars();
+        let i = chars.iter().position(|&x| x == filler).expect("filler is part of the square");
+
+        Ok(chars[(i + 1) % chars.len()])
+    }
+
+    /// encrypting moves right / down, decrypting left / up
+    fn substitute(&self, square: &PolybiusSquare, a: char, b: char, encrypt: bool) -> Result<String, Error> {
+        let (rows, cols) = (square.rows(), square.cols());
+        let (r1, c1) = square.position(a)?;
+        let (r2, c2) = square.position(b)?;
+
+        let (x, y) = if r1 == r2 {
+            let shift = if encrypt { 1 } else { cols - 1 };
+            (square.char_at(r1, (c1 + shift) % cols)?, square.char_at(r2, (c2 + shift) % cols)?)
+        } else if c1 == c2 {
+            let shift = if encrypt { 1 } else { rows - 1 };
+            (square.char_at((r1 + shift) % rows, c1)?, square.char_at((r2 + shift) % rows, c2)?)
+        } else {
+            (square.char_at(r1, c2)?, square.char_at(r2, c1)?)
+        };
+
+        Ok(format!("{}{}", x, y))
+    }
+}
+
+impl Default for Playfair {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Cipher for Playfair {
+    /// keyword of the square
+    type Key = String;
+
+    fn encrypt(&self, text: &str, key: &String) -> Result<String, Error> {
+        self.encrypt(text, key)
+    }
+
+    fn decrypt(&self, text: &str, key: &String) -> Result<String, Error> {
+        self.decrypt(text, key)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::char_set::CharSet;
+
+    const KEYWORD: &str = "PLAYFAIREXAMPLE";
+
+    #[test]
+    fn test_digraphs() {
+        let playfair = Playfair::new();
+        let square = playfair.square(KEYWORD).unwrap();
+
+        let digraphs = playfair.digraphs(&square, "TREESJ").unwrap();
+        assert_eq!(digraphs, vec![('T', 'R'), ('E', 'X'), ('E', 'S'), ('I', 'X')]);
+
+        // a doubled filler is split with the letter after it (X, M)
+        let digraphs = playfair.digraphs(&square, "XXX").unwrap();
+        assert_eq!(digraphs, vec![('X', 'M'), ('X', 'M'), ('X', 'M')]);
+    }
+
+    #[test]
+    fn test_encrypt() {
+        let playfair = Playfair::new();
+        let encrypted = playfair.encrypt("HIDETHEGOLDINTHETREESTUMP", KEYWORD).unwrap();
+        assert_eq!(encrypted, "BMODZBXDNABEKUDMUIXMMOUVIF");
+    }
+
+    #[test]
+    fn test_decrypt() {
+        let playfair = Playfair::new();
+        let decrypted = playfair.decrypt("BMODZBXDNABEKUDMUIXMMOUVIF", KEYWORD).unwrap();
+        assert_eq!(decrypted, "HIDETHEGOLDINTHETREXESTUMP");
+
+        assert!(playfair.decrypt("BMO", KEYWORD).is_err());
+        assert!(playfair.decrypt("BB", KEYWORD).is_err());
+        assert!(playfair.decrypt("B1", KEYWORD).is_err());
+    }
+
+    #[test]
+    fn test_options() {
+        // lowercase, q left out instead of merging j, z as filler
+        let options = SquareOptions::new(CharSet::from_string("abcdefghijklmnoprstuvwxyz"), None).unwrap();
+        let playfair = Playfair::with_options(options.clone().with_filler('z').unwrap()).unwrap();
+        let encrypted = playfair.encrypt("balloon", "monarchy").unwrap();
+        assert_eq!(playfair.decrypt(&encrypted, "monarchy").unwrap(), "balzloon");
+
+        assert!(playfair.encrypt("quit", "monarchy").is_err());
+        assert!(Playfair::with_options(options).is_err());
+    }
+}
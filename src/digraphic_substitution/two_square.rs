@@ -0,0 +1,138 @@
+/*===================================================================
+ * Two-Square Cipher
+ * Author: denosauabh
+ * Description: Implementation of the (vertical) Two-Square Cipher, a digraphic substitution
+                on two keyed Polybius squares, one above the other
+ * Reference: https://en.wikipedia.org/wiki/Two-square_cipher
+
+ * keywords "EXAMPLE" & "KEYWORD", A..Z without Q:
+
+    E X A M P
+    L B C D F
+    G H I J K       the first letter of a pair is found in the top square,
+    N O R S T       the second in the bottom one
+    U V W Y Z
+
+    K E Y W O
+    R D A B C
+    F G H I J
+    L M N P S
+    T U V X Z
+
+ * Each letter is replaced by the letter of its own square, on its own row, in the column
+ * of the other letter. Letters on the same column are left as they are, the cipher is its own inverse.
+*===================================================================*/
+
+use crate::utils::cipher::Cipher;
+use crate::utils::error::Error;
+use crate::utils::polybius::SquareOptions;
+
+pub struct TwoSquare {
+    options: SquareOptions,
+}
+
+impl TwoSquare {
+    pub fn new() -> Self {
+        Self { options: SquareOptions::default() }
+    }
+
+    /// the options need a filler
+    pub fn with_options(options: SquareOptions) -> Result<Self, Error> {
+        options.filler()?;
+        Ok(Self { options })
+    }
+
+    /// pads an odd length text with the filler
+    pub fn encrypt(&self, text: &str, top: &str, bottom: &str) -> Result<String, Error> {
+        let mut chars = text.chars().collect::<Vec<char>>();
+
+        if !chars.len().is_multiple_of(2) {
+            chars.push(self.options.filler()?);
+        }
+
+        self.substitute(&chars, top, bottom)
+    }
+
+    pub fn decrypt(&self, text: &str, top: &str, bottom: &str) -> Result<String, Error> {
+        let chars = text.chars().collect::<Vec<char>>();
+
+        if !chars.len().is_multiple_of(2) {
+            return Err(Error::MalformedCiphertext("ciphertext must have an even length".to_string()));
+        }
+
+        self.substitute(&chars, top, bottom)
+    }
+
+    fn substitute(&self, chars: &[char], top: &str, bottom: &str) -> Result<String, Error> {
+        let top = self.options.keyed(top)?;
+        let bottom = self.options.keyed(bottom)?;
+
+        let mut substituted = String::with_capacity(chars.len());
+
+        for (i, pair) in chars.chunks(2).enumerate() {
+            let (r1, c1) = top.position(pair[0]).map_err(|_| Error::UnknownCharacter { character: pair[0], position: Some(i * 2) })?;
+            let (r2, c2) = bottom.position(pair[1]).map_err(|_| Error::UnknownCharacter { character: pair[1], position: Some(i * 2 + 1) })?;
+
+            substituted.push(top.char_at(r1, c2)?);
+            substituted.push(bottom.char_at(r2, c1)?);
+        }
+
+        Ok(substituted)
+    }
+}
+
+impl Default for TwoSquare {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Cipher for TwoSquare {
+    /// keywords of the top & bottom square
+    type Key = (String, String);
+
+    fn encrypt(&self, text: &str, key: &(String, String)) -> Result<String, Error> {
+        self.encrypt(text, &key.0, &key.1)
+    }
+
+    fn decrypt(&self, text: &str, key: &(String, String)) -> Result<String, Error> {
+        self.decrypt(text, &key.0, &key.1)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::char_set::CharSet;
+
+    fn two_square() -> TwoSquare {
+        let options = SquareOptions::new(CharSet::from_string("ABCDEFGHIJKLMNOPRSTUVWXYZ"), None).unwrap();
+        TwoSquare::with_options(options.with_filler('X').unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_encrypt() {
+        let encrypted = two_square().encrypt("HELPMEOBIWANKENOBI", "EXAMPLE", "KEYWORD").unwrap();
+        assert_eq!(encrypted, "HEDLXWSDJYANHOTKDG");
+
+        // odd length
+        assert_eq!(two_square().encrypt("HEL", "EXAMPLE", "KEYWORD").unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_decrypt() {
+        let decrypted = two_square().decrypt("HEDLXWSDJYANHOTKDG", "EXAMPLE", "KEYWORD").unwrap();
+        assert_eq!(decrypted, "HELPMEOBIWANKENOBI");
+
+        assert!(two_square().decrypt("HED", "EXAMPLE", "KEYWORD").is_err());
+        assert!(two_square().decrypt("QE", "EXAMPLE", "KEYWORD").is_err());
+    }
+
+    #[test]
+    fn test_merge() {
+        let two_square = TwoSquare::new();
+        let encrypted = two_square.encrypt("JUSTICE", "EXAMPLE", "KEYWORD").unwrap();
+        assert_eq!(two_square.decrypt(&encrypted, "EXAMPLE", "KEYWORD").unwrap(), "IUSTICEX");
+    }
+}
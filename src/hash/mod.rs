@@ -0,0 +1,27 @@
+pub mod sha1;
+pub mod sha256;
+
+/// common interface of the hash functions, so the RSA padding schemes
+/// (OAEP, PSS & their mask generation function) can be generic over the hash
+pub trait HashFunction {
+    /// length of the digest in bytes
+    const OUTPUT_LEN: usize;
+
+    /// DER encoded `DigestInfo` up to the digest, the algorithm identifier PKCS #1 v1.5 signatures embed
+    const DIGEST_INFO_PREFIX: &'static [u8];
+
+    fn digest(data: &[u8]) -> Vec<u8>;
+}
+
+/// Merkle–Damgård padding of SHA-1 & SHA-256, `1`, zeros & the 64-bit big-endian length in bits
+pub(crate) fn pad(data: &[u8]) -> Vec<u8> {
+    let mut padded = data.to_vec();
+    padded.push(0x80);
+
+    while padded.len() % 64 != 56 {
+        padded.push(0);
+    }
+
+    padded.extend_from_slice(&((data.len() as u64).wrapping_mul(8)).to_be_bytes());
+    padded
+}
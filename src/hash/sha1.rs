@@ -0,0 +1,91 @@
+/*===================================================================
+ * Implementation of SHA-1 (Secure Hash Algorithm 1)
+ * Author: denosauabh
+ * Reference: https://en.wikipedia.org/wiki/SHA-1
+ * Description: Implementation of SHA-1 using paper - https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.180-4.pdf
+
+ * Broken for collisions, kept since it is the default hash of OAEP & PSS in PKCS #1
+   and most published test vectors use it.
+ *===================================================================*/
+
+use crate::hash::{pad, HashFunction};
+
+pub struct Sha1;
+
+impl Sha1 {
+    const H: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+    pub fn hash(data: &[u8]) -> [u8; 20] {
+        let mut state = Self::H;
+
+        for block in pad(data).chunks(64) {
+            Self::compress(&mut state, block);
+        }
+
+        let mut digest = [0u8; 20];
+        for (chunk, word) in digest.chunks_mut(4).zip(state) {
+            chunk.copy_from_slice(&word.to_be_bytes());
+        }
+
+        digest
+    }
+
+    // the message schedule reads back earlier words, indices read closer to FIPS 180-4
+    #[allow(clippy::needless_range_loop)]
+    fn compress(state: &mut [u32; 5], block: &[u8]) {
+        let mut w = [0u32; 80];
+        for (i, word) in block.chunks(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e] = *state;
+
+        for i in 0..80 {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5a827999),
+                20..=39 => (b ^ c ^ d, 0x6ed9eba1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8f1bbcdc),
+                _ => (b ^ c ^ d, 0xca62c1d6),
+            };
+
+            let t = a.rotate_left(5).wrapping_add(f).wrapping_add(e).wrapping_add(k).wrapping_add(w[i]);
+            (e, d, c, b, a) = (d, c, b.rotate_left(30), a, t);
+        }
+
+        for (word, x) in state.iter_mut().zip([a, b, c, d, e]) {
+            *word = word.wrapping_add(x);
+        }
+    }
+}
+
+impl HashFunction for Sha1 {
+    const OUTPUT_LEN: usize = 20;
+
+    /// OID 1.3.14.3.2.26
+    const DIGEST_INFO_PREFIX: &'static [u8] = &[0x30, 0x21, 0x30, 0x09, 0x06, 0x05, 0x2b, 0x0e, 0x03, 0x02, 0x1a, 0x05, 0x00, 0x04, 0x14];
+
+    fn digest(data: &[u8]) -> Vec<u8> {
+        Self::hash(data).to_vec()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::encoding::Encoding;
+
+    #[test]
+    fn test_sha1() {
+        // FIPS 180-4 examples
+        assert_eq!(Encoding::hex_encode(&Sha1::hash(b"")), "da39a3ee5e6b4b0d3255bfef95601890afd80709");
+        assert_eq!(Encoding::hex_encode(&Sha1::hash(b"abc")), "a9993e364706816aba3e25717850c26c9cd0d89d");
+        assert_eq!(Encoding::hex_encode(&Sha1::hash(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq")), "84983e441c3bd26ebaae4aa1f95129e5e54670f1");
+
+        assert_eq!(Encoding::hex_encode(&Sha1::hash(&[b'a'; 56])), "c2db330f6083854c99d4b5bfb6e8f29f201be699");
+        assert_eq!(Encoding::hex_encode(&Sha1::hash(&[b'a'; 1000])), "291e9a6c66994949b57ba5e650361e98fc36b1ba");
+    }
+}
@@ -0,0 +1,30 @@
+/*===================================================================
+ * Author: denosauabh
+ * Description: moc - a collection of classical & modern ciphers
+
+ * Every cipher lives in the module of its family, the `prelude` re-exports
+ * the ciphers together with the shared `Cipher` trait, `CharSet` & `Error`:
+
+    use moc::prelude::*;
+
+    let additive = AdditiveCipher::new(CharSet::from_alphabet_lowercase());
+    assert_eq!(additive.encrypt("hello", 3)?, "khoor");
+
+ *===================================================================*/
+
+
+pub mod simple_substitution;
+pub mod polyalphebatic_substitution;
+pub mod digraphic_substitution;
+pub mod transposition;
+pub mod polyliteral;
+pub mod block;
+pub mod stream;
+pub mod mode_of_operations;
+pub mod public_key;
+pub mod hash;
+
+pub mod utils;
+pub mod cryptanalysis;
+
+pub mod prelude;
@@ -1,15 +1,16 @@
-mod simple_substitution;
-mod polyalphebatic_substitution;
-mod transposition;
-mod polyliteral;
-mod block;
-mod stream;
-mod mode_of_operations;
-mod public_key;
+mod cli;
 
-mod utils;
 
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
 
-fn main() {}
+    if let Err(err) = cli::run(&args) {
+        eprintln!("moc: {}", err);
 
+        if let cli::CliError::Usage(_) = err {
+            eprintln!("\n{}", cli::USAGE);
+        }
 
+        std::process::exit(1);
+    }
+}
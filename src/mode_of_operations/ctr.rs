@@ -4,6 +4,8 @@
  * Reference: https://en.wikipedia.org/wiki/Block_cipher_mode_of_operation
 *===================================================================*/
 
+use crate::utils::error::Error;
+
 pub struct CTR {
     nonce: u64,
     counter: u64,
@@ -55,7 +57,7 @@ impl CTR {
         self.process(plaintext)
     }
 
-    pub fn decrypt(&mut self, ciphertext: &str) -> Result<String, Box<dyn std::error::Error>> {
+    pub fn decrypt(&mut self, ciphertext: &str) -> Result<String, Error> {
         let encrypted_blocks: Result<Vec<u128>, _> = ciphertext
             .chars()
             .collect::<Vec<char>>()
@@ -63,7 +65,8 @@ impl CTR {
             .map(|chunk| u128::from_str_radix(&chunk.iter().collect::<String>(), 16))
             .collect();
 
-        let encrypted_blocks = encrypted_blocks?;
+        let encrypted_blocks = encrypted_blocks
+            .map_err(|e| Error::MalformedCiphertext(format!("expected hex blocks, {}", e)))?;
 
         let decrypted_blocks: Vec<u128> = encrypted_blocks
             .iter()
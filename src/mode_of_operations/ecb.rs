@@ -4,19 +4,24 @@
  * Reference: https://en.wikipedia.org/wiki/Block_cipher_mode_of_operation
 *===================================================================*/
 
+use crate::utils::error::Error;
+
 pub struct ECB {
     padding: u8,
     block_cipher: Box<dyn Fn(u128) -> u128>,
+    block_decipher: Box<dyn Fn(u128) -> u128>,
 }
 
 impl ECB {
     pub fn new(
         padding: u8,
         block_cipher: impl Fn(u128) -> u128 + 'static,
+        block_decipher: impl Fn(u128) -> u128 + 'static,
     ) -> ECB {
         ECB {
             padding,
             block_cipher: Box::new(block_cipher),
+            block_decipher: Box::new(block_decipher),
         }
     }
 
@@ -48,23 +53,62 @@ impl ECB {
             .map(|&block| format!("{:032x}", block))
             .collect()
     }
+
+    pub fn decrypt(&self, ciphertext: &str) -> Result<String, Error> {
+        let encrypted_blocks: Result<Vec<u128>, _> = ciphertext
+            .chars()
+            .collect::<Vec<char>>()
+            .chunks(32)
+            .map(|chunk| u128::from_str_radix(&chunk.iter().collect::<String>(), 16))
+            .collect();
+
+        let encrypted_blocks = encrypted_blocks
+            .map_err(|e| Error::MalformedCiphertext(format!("expected hex blocks, {}", e)))?;
+
+        // Decrypt each block
+        let mut decrypted: Vec<u8> = encrypted_blocks
+            .iter()
+            .flat_map(|&block| (self.block_decipher)(block).to_be_bytes())
+            .collect();
+
+        // Strip the padding, which only ever fills the end of the last block
+        let last_block = decrypted.len().saturating_sub(16);
+        while decrypted.len() > last_block && decrypted.last() == Some(&self.padding) {
+            decrypted.pop();
+        }
+
+        Ok(decrypted.iter().map(|&byte| byte as char).collect())
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::rc::Rc;
+
     use super::*;
     use crate::block::aes::AES;
 
     #[test]
     fn test_ecb() {
         let key = 0x0123456789abcdef0123456789abcdef;
-        let aes = AES::new(key);
-        let ecb = ECB::new(0x00, move |block| aes.encrypt(block));
+        let aes = Rc::new(AES::new(key));
+        let ecb = ECB::new(
+            0x00,
+            {
+                let aes = Rc::clone(&aes);
+                move |block| aes.encrypt(block)
+            },
+            move |block| aes.decrypt(block)
+        );
 
         let plaintext = "Attack Berlin at Dawn";
         let ciphertext = ecb.encrypt(plaintext);
         
         let expected = "c877851e6ac018a48678f3ea4c628d58db7a30bdbb32cf9cd7422f1ab98bb62b";
         assert_eq!(ciphertext, expected);
+
+        // Test decryption
+        let decrypted = ecb.decrypt(&ciphertext).unwrap();
+        assert_eq!(decrypted, plaintext);
     }
 }
@@ -4,6 +4,8 @@
  * Reference: https://en.wikipedia.org/wiki/Block_cipher_mode_of_operation
 *===================================================================*/
 
+use crate::utils::error::Error;
+
 pub struct PBC {
     iv: u128,
     block_cipher: Box<dyn Fn(u128) -> u128>,
@@ -47,8 +49,8 @@ impl PBC {
             .collect()
     }
 
-    pub fn decrypt(&self, ciphertext: &str) -> Result<String, Box<dyn std::error::Error>> {
-        todo!("Implement PBC decryption")
+    pub fn decrypt(&self, _ciphertext: &str) -> Result<String, Error> {
+        Err(Error::Unsupported("PBC decryption is not implemented yet".to_string()))
     }
 }
 
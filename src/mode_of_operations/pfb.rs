@@ -4,6 +4,8 @@
  * Reference: https://en.wikipedia.org/wiki/Block_cipher_mode_of_operation
 *===================================================================*/
 
+use crate::utils::error::Error;
+
 pub struct PFB {
     iv: u128,
     block_cipher: Box<dyn Fn(u128) -> u128>,
@@ -47,7 +49,7 @@ impl PFB {
             .collect()
     }
 
-    pub fn decrypt(&self, ciphertext: &str) -> Result<String, Box<dyn std::error::Error>> {
+    pub fn decrypt(&self, ciphertext: &str) -> Result<String, Error> {
         let encrypted_blocks: Result<Vec<u128>, _> = ciphertext
             .chars()
             .collect::<Vec<char>>()
@@ -55,7 +57,8 @@ impl PFB {
             .map(|chunk| u128::from_str_radix(&chunk.iter().collect::<String>(), 16))
             .collect();
 
-        let encrypted_blocks = encrypted_blocks?;
+        let encrypted_blocks = encrypted_blocks
+            .map_err(|e| Error::MalformedCiphertext(format!("expected hex blocks, {}", e)))?;
 
         let mut decrypted_blocks: Vec<u128> = Vec::with_capacity(encrypted_blocks.len());
         let mut previous_block = self.iv;
@@ -5,6 +5,8 @@
 *===================================================================*/
 
 use crate::utils::char_set::CharSet;
+use crate::utils::cipher::Cipher;
+use crate::utils::error::Error;
 
 
 pub struct AlbertiCipher {
@@ -16,43 +18,58 @@ pub struct AlbertiCipher {
 
 
 impl AlbertiCipher {
-    pub fn new(char_set: CharSet, disk: CharSet, period_len: u32) -> Self {
+    pub fn new(char_set: CharSet, disk: CharSet, period_len: u32) -> Result<Self, Error> {
         if disk.len() != char_set.len() {
-            panic!("Disk charset must be equal to the main charset");
+            return Err(Error::InvalidCharSet("disk charset must be equal to the main charset".to_string()));
         }
 
-        Self { char_set, disk, period_len }
+        if period_len == 0 {
+            return Err(Error::InvalidKey("period length must be greater than 0".to_string()));
+        }
+
+        Ok(Self { char_set, disk, period_len })
     }
 
-    pub fn encrypt(&self, text: &str) -> String {
-        text.chars()
+    pub fn encrypt(&self, text: &str) -> Result<String, Error> {
+        self.char_set
+            .indices_of(text)?
+            .into_iter()
             .enumerate()
-            .map(|(i, c)| {
-                let pi = self.char_set.index_of(c);
-                
+            .map(|(i, pi)| {
                 let period = i / (self.period_len as usize);
-                
 
-                self.disk.char_at(pi + period )
+                self.disk.char_at((pi + period) % self.disk.len())
             })
             .collect()
     }
 
-    pub fn decrypt(&self, text: &str) -> String {
-        text.chars()
+    pub fn decrypt(&self, text: &str) -> Result<String, Error> {
+        self.disk
+            .indices_of(text)?
+            .into_iter()
             .enumerate()
-            .map(|(i, c)| {
-                let di = self.disk.index_of(c);
-                
+            .map(|(i, di)| {
                 let period = i / (self.period_len as usize);
-                
 
-                self.char_set.char_at(di - period)
+                self.char_set.char_at((di + self.char_set.len() - period % self.char_set.len()) % self.char_set.len())
             })
             .collect()
     }
 }
 
+impl Cipher for AlbertiCipher {
+    /// the disk & period are part of the constructor
+    type Key = ();
+
+    fn encrypt(&self, text: &str, _key: &()) -> Result<String, Error> {
+        self.encrypt(text)
+    }
+
+    fn decrypt(&self, text: &str, _key: &()) -> Result<String, Error> {
+        self.decrypt(text)
+    }
+}
+
 
 
 #[cfg(test)]
@@ -63,9 +80,9 @@ mod tests {
     fn test_encrypt() {
         let char_set = CharSet::from_string("ABCDEFGHIJKLMNOPQRSTUVWXYZ");
         let disk = CharSet::from_string(/**/"CDEFGHIJKLMNOPQRSTUVWXYZAB");
-        let alberti = AlbertiCipher::new(char_set, disk, 4);
+        let alberti = AlbertiCipher::new(char_set, disk, 4).unwrap();
 
-        let encrypted = alberti.encrypt("ZABCDEF");
+        let encrypted = alberti.encrypt("ZABCDEF").unwrap();
         assert_eq!(encrypted, "BCDEGHI");
     }
 
@@ -73,9 +90,27 @@ mod tests {
     fn test_decrypt() {
         let char_set = CharSet::from_string("ABCDEFGHIJKLMNOPQRSTUVWXYZ");
         let disk = CharSet::from_string(/**/"CDEFGHIJKLMNOPQRSTUVWXYZAB");
-        let alberti = AlbertiCipher::new(char_set, disk, 4);
+        let alberti = AlbertiCipher::new(char_set, disk, 4).unwrap();
 
-        let decrypted = alberti.decrypt("BCDEGHI");
+        let decrypted = alberti.decrypt("BCDEGHI").unwrap();
         assert_eq!(decrypted, "ZABCDEF");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_wraps_around_disk() {
+        let char_set = CharSet::from_string("ABCDEFGHIJKLMNOPQRSTUVWXYZ");
+        let disk = CharSet::from_string(/**/"CDEFGHIJKLMNOPQRSTUVWXYZAB");
+        let alberti = AlbertiCipher::new(char_set, disk, 1).unwrap();
+
+        let encrypted = alberti.encrypt("ZZZ").unwrap();
+        assert_eq!(encrypted, "BCD");
+        assert_eq!(alberti.decrypt(&encrypted).unwrap(), "ZZZ");
+    }
+
+    #[test]
+    fn test_disk_length_mismatch() {
+        let char_set = CharSet::from_string("ABCDEFGHIJKLMNOPQRSTUVWXYZ");
+        let disk = CharSet::from_string("CDEFG");
+        assert!(AlbertiCipher::new(char_set, disk, 4).is_err());
+    }
+}
@@ -0,0 +1,106 @@
+/*===================================================================
+ * Author: denosauabh
+ * Description: Implementation of the Beaufort Cipher (c = k - p) & the Variant Beaufort Cipher (c = p - k)
+ * Reference: https://en.wikipedia.org/wiki/Beaufort_cipher
+
+ * Beaufort is its own inverse, encrypting & decrypting are the same operation.
+ * Variant Beaufort encrypts the way Vigenère decrypts (& the other way around).
+*===================================================================*/
+
+use crate::polyalphebatic_substitution::periodic::{PeriodicCipher, Tableau};
+use crate::utils::{char_set::CharSet, cipher::Cipher, error::Error};
+
+pub struct Beaufort {
+    periodic: PeriodicCipher,
+}
+
+impl Beaufort {
+    pub fn new(char_set: CharSet) -> Self {
+        Self { periodic: PeriodicCipher::new(char_set, Tableau::Beaufort) }
+    }
+
+    pub fn encrypt(&self, text: &str, key: &str) -> Result<String, Error> {
+        self.periodic.encrypt(text, key)
+    }
+
+    pub fn decrypt(&self, text: &str, key: &str) -> Result<String, Error> {
+        self.periodic.decrypt(text, key)
+    }
+}
+
+impl Cipher for Beaufort {
+    type Key = String;
+
+    fn encrypt(&self, text: &str, key: &String) -> Result<String, Error> {
+        self.encrypt(text, key)
+    }
+
+    fn decrypt(&self, text: &str, key: &String) -> Result<String, Error> {
+        self.decrypt(text, key)
+    }
+}
+
+pub struct VariantBeaufort {
+    periodic: PeriodicCipher,
+}
+
+impl VariantBeaufort {
+    pub fn new(char_set: CharSet) -> Self {
+        Self { periodic: PeriodicCipher::new(char_set, Tableau::VariantBeaufort) }
+    }
+
+    pub fn encrypt(&self, text: &str, key: &str) -> Result<String, Error> {
+        self.periodic.encrypt(text, key)
+    }
+
+    pub fn decrypt(&self, text: &str, key: &str) -> Result<String, Error> {
+        self.periodic.decrypt(text, key)
+    }
+}
+
+impl Cipher for VariantBeaufort {
+    type Key = String;
+
+    fn encrypt(&self, text: &str, key: &String) -> Result<String, Error> {
+        self.encrypt(text, key)
+    }
+
+    fn decrypt(&self, text: &str, key: &String) -> Result<String, Error> {
+        self.decrypt(text, key)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polyalphebatic_substitution::vigenere::VigenèreCipher;
+
+    const PLAINTEXT: &str = "DEFENDTHEEASTWALLOFTHECASTLE";
+
+    #[test]
+    fn test_beaufort() {
+        let beaufort = Beaufort::new(CharSet::from_range('A', 'Z'));
+
+        let encrypted = beaufort.encrypt(PLAINTEXT, "FORTIFICATION").unwrap();
+        assert_eq!(encrypted, "CKMPVCPVWPIWUJOGIUAPVWRIWUUK");
+        assert_eq!(beaufort.decrypt(&encrypted, "FORTIFICATION").unwrap(), PLAINTEXT);
+
+        // reciprocal
+        assert_eq!(beaufort.encrypt(&encrypted, "FORTIFICATION").unwrap(), PLAINTEXT);
+        assert!(beaufort.encrypt(PLAINTEXT, "").is_err());
+    }
+
+    #[test]
+    fn test_variant_beaufort() {
+        let char_set = CharSet::from_range('A', 'Z');
+        let variant = VariantBeaufort::new(char_set.clone());
+        let vigenere = VigenèreCipher::new(char_set);
+
+        let encrypted = variant.encrypt(PLAINTEXT, "FORTIFICATION").unwrap();
+        assert_eq!(encrypted, vigenere.decrypt(PLAINTEXT, "FORTIFICATION").unwrap());
+        assert_eq!(variant.decrypt(&encrypted, "FORTIFICATION").unwrap(), PLAINTEXT);
+
+        assert_eq!(variant.encrypt("HELLO", "B").unwrap(), "GDKKN");
+    }
+}
@@ -0,0 +1,85 @@
+/*===================================================================
+ * Author: denosauabh
+ * Description: Implementation of the Gronsfeld Cipher, Vigenère with a numeric key
+ * Reference: https://en.wikipedia.org/wiki/Gronsfeld_cipher
+
+ * Every digit of the key is a shift, "31415" over A..Z is the Vigenère key "DBEBF".
+*===================================================================*/
+
+use crate::polyalphebatic_substitution::periodic::{PeriodicCipher, Tableau};
+use crate::utils::{char_set::CharSet, cipher::Cipher, error::Error};
+
+pub struct Gronsfeld {
+    periodic: PeriodicCipher,
+}
+
+impl Gronsfeld {
+    pub fn new(char_set: CharSet) -> Self {
+        Self { periodic: PeriodicCipher::new(char_set, Tableau::Vigenère) }
+    }
+
+    pub fn encrypt(&self, text: &str, key: &str) -> Result<String, Error> {
+        self.periodic.encrypt_with(text, &Self::parse_key(key)?)
+    }
+
+    pub fn decrypt(&self, text: &str, key: &str) -> Result<String, Error> {
+        self.periodic.decrypt_with(text, &Self::parse_key(key)?)
+    }
+
+    /// the digits of the key
+    fn parse_key(key: &str) -> Result<Vec<usize>, Error> {
+        key.chars()
+            .enumerate()
+            .map(|(i, c)| {
+                c.to_digit(10)
+                    .map(|d| d as usize)
+                    .ok_or(Error::UnknownCharacter { character: c, position: Some(i) })
+            })
+            .collect()
+    }
+}
+
+impl Cipher for Gronsfeld {
+    /// digits, e.g. "31415"
+    type Key = String;
+
+    fn encrypt(&self, text: &str, key: &String) -> Result<String, Error> {
+        self.encrypt(text, key)
+    }
+
+    fn decrypt(&self, text: &str, key: &String) -> Result<String, Error> {
+        self.decrypt(text, key)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polyalphebatic_substitution::vigenere::VigenèreCipher;
+
+    #[test]
+    fn test_encrypt() {
+        let gronsfeld = Gronsfeld::new(CharSet::from_range('A', 'Z'));
+
+        assert_eq!(gronsfeld.encrypt("ATTACKATDAWN", "31415").unwrap(), "DUXBHNBXEFZO");
+        assert_eq!(
+            gronsfeld.encrypt("ATTACKATDAWN", "31415").unwrap(),
+            VigenèreCipher::new(CharSet::from_range('A', 'Z')).encrypt("ATTACKATDAWN", "DBEBF").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_decrypt() {
+        let gronsfeld = Gronsfeld::new(CharSet::from_range('A', 'Z'));
+        assert_eq!(gronsfeld.decrypt("DUXBHNBXEFZO", "31415").unwrap(), "ATTACKATDAWN");
+    }
+
+    #[test]
+    fn test_invalid_key() {
+        let gronsfeld = Gronsfeld::new(CharSet::from_range('A', 'Z'));
+
+        assert!(gronsfeld.encrypt("ATTACK", "").is_err());
+        assert_eq!(gronsfeld.encrypt("ATTACK", "31A"), Err(Error::UnknownCharacter { character: 'A', position: Some(2) }));
+    }
+}
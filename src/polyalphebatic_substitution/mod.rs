@@ -1,2 +1,8 @@
 pub mod alberti;
-pub mod vigenere;
\ No newline at end of file
+pub mod periodic;
+pub mod vigenere;
+pub mod beaufort;
+pub mod gronsfeld;
+pub mod porta;
+pub mod running_key;
+pub mod one_time_pad;
\ No newline at end of file
@@ -0,0 +1,177 @@
+/*===================================================================
+ * Author: denosauabh
+ * Description: One-Time Pad over a charset, a running key of random characters used once
+ * Reference: https://en.wikipedia.org/wiki/One-time_pad
+
+ * The key is as long as the text & every character is drawn uniformly from the charset,
+   so every plaintext of that length is equally likely for a given ciphertext.
+ * It only holds as long as a pad is never used twice: two ciphertexts under the same pad
+   subtract to the difference of the two plaintexts, a running key cipher of language on language.
+ * A `OneTimePad` remembers the pads it encrypted with for the rest of the session & refuses
+   a given pad overlapping one of them, i.e. starting it or starting with it.
+ * Drawn pads stay uniform: one that happens to overlap a used pad is an error, never drawn
+   again, as redrawing would make the remaining pads more likely. Decryption doesn't use up a pad.
+*===================================================================*/
+
+use std::collections::HashSet;
+
+use rand::Rng;
+
+use crate::polyalphebatic_substitution::periodic::{PeriodicCipher, Tableau};
+use crate::utils::{char_set::CharSet, error::Error};
+
+pub struct OneTimePad {
+    periodic: PeriodicCipher,
+
+    /// pads encrypted with in this session
+    used: HashSet<Vec<usize>>,
+}
+
+impl OneTimePad {
+    pub fn new(char_set: CharSet) -> Self {
+        Self { periodic: PeriodicCipher::new(char_set, Tableau::Vigenère), used: HashSet::new() }
+    }
+
+    /// `len` random characters of the charset
+    pub fn generate_key(&self, len: usize, rng: &mut impl Rng) -> Result<String, Error> {
+        let chars = &self.periodic.char_set().chars;
+
+        if chars.is_empty() {
+            return Err(Error::InvalidCharSet("a pad needs a non-empty charset".to_string()));
+        }
+
+        Ok((0..len).map(|_| chars[rng.gen_range(0..chars.len())]).collect())
+    }
+
+    /// encrypts with a new pad, (ciphertext, pad), a drawn pad overlapping a used one is an error
+    pub fn encrypt(&mut self, text: &str, rng: &mut impl Rng) -> Result<(String, String), Error> {
+        let key = self.generate_key(text.chars().count(), rng)?;
+
+        if self.is_used(&self.parse_key(text, &key)?) {
+            return Err(Error::InvalidKey("drew a one-time pad overlapping a used one, the text is too short for the charset".to_string()));
+        }
+
+        let encrypted = self.encrypt_with_key(text, &key)?;
+        Ok((encrypted, key))
+    }
+
+    /// encrypts with a given pad, refused if it (or the part of it the text needs) was used before
+    pub fn encrypt_with_key(&mut self, text: &str, key: &str) -> Result<String, Error> {
+        let key = self.parse_key(text, key)?;
+
+        if self.is_used(&key) {
+            return Err(Error::InvalidKey("one-time pad already used in this session".to_string()));
+        }
+
+        let encrypted = self.periodic.encrypt_with(text, &key)?;
+        self.used.insert(key);
+
+        Ok(encrypted)
+    }
+
+    pub fn decrypt(&self, text: &str, key: &str) -> Result<String, Error> {
+        self.periodic.decrypt_with(text, &self.parse_key(text, key)?)
+    }
+
+    /// pads used in this session
+    pub fn used(&self) -> usize {
+        self.used.len()
+    }
+
+    /// the pad, a prefix of it or a pad it starts with was encrypted with
+    fn is_used(&self, key: &[usize]) -> bool {
+        self.used.iter().any(|used| used.starts_with(key) || key.starts_with(used))
+    }
+
+    /// the key must be exactly as long as the text
+    fn parse_key(&self, text: &str, key: &str) -> Result<Vec<usize>, Error> {
+        let (len, found) = (text.chars().count(), key.chars().count());
+
+        if found != len || len == 0 {
+            return Err(Error::InvalidKeyLength { expected: len, found });
+        }
+
+        self.periodic.char_set().indices_of(key)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_round_trip() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut pad = OneTimePad::new(CharSet::from_alphabet_lowercase());
+
+        let (encrypted, key) = pad.encrypt("attackatdawn", &mut rng).unwrap();
+        assert_eq!(key.len(), 12);
+        assert!(key.chars().all(|c| c.is_ascii_lowercase()));
+        assert_eq!(pad.decrypt(&encrypted, &key).unwrap(), "attackatdawn");
+
+        // any charset
+        let mut pad = OneTimePad::new(CharSet::from_string("01"));
+        let (encrypted, key) = pad.encrypt("0110100001101001", &mut rng).unwrap();
+        assert_eq!(pad.decrypt(&encrypted, &key).unwrap(), "0110100001101001");
+    }
+
+    #[test]
+    fn test_key_reuse() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut pad = OneTimePad::new(CharSet::from_alphabet_lowercase());
+
+        let (encrypted, key) = pad.encrypt("attackatdawn", &mut rng).unwrap();
+        let reused = Err(Error::InvalidKey("one-time pad already used in this session".to_string()));
+
+        assert_eq!(pad.encrypt_with_key("retreatatdusk", &key), Err(Error::InvalidKeyLength { expected: 13, found: 12 }));
+        assert_eq!(pad.encrypt_with_key("retreatatdus", &key), reused);
+        assert_eq!(pad.encrypt_with_key("retreat", &key[..7]), reused);
+
+        // decrypting doesn't use up a pad, a new one is fine
+        assert_eq!(pad.decrypt(&encrypted, &key).unwrap(), "attackatdawn");
+        assert!(pad.encrypt_with_key("retreat", "qwertyu").is_ok());
+        assert_eq!(pad.used(), 2);
+    }
+
+    #[test]
+    fn test_short_pads() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut pad = OneTimePad::new(CharSet::from_string("01"));
+
+        // "0" & "1" both start a used pad, the draw is refused rather than repeated
+        pad.encrypt("0110100001101001", &mut rng).unwrap();
+        assert_eq!(
+            pad.encrypt("0", &mut rng),
+            Err(Error::InvalidKey("drew a one-time pad overlapping a used one, the text is too short for the charset".to_string()))
+        );
+        assert_eq!(pad.used(), 1);
+
+        let mut empty = OneTimePad::new(CharSet::from_string(""));
+        assert!(empty.generate_key(3, &mut rng).is_err());
+        assert!(empty.encrypt("abc", &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_key_length() {
+        let pad = OneTimePad::new(CharSet::from_alphabet_lowercase());
+
+        assert_eq!(pad.decrypt("abc", "ab"), Err(Error::InvalidKeyLength { expected: 3, found: 2 }));
+        assert_eq!(pad.decrypt("abc", "abcd"), Err(Error::InvalidKeyLength { expected: 3, found: 4 }));
+        assert!(pad.decrypt("abc", "ab1").is_err());
+    }
+
+    #[test]
+    fn test_uniform_key() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let pad = OneTimePad::new(CharSet::from_alphabet_lowercase());
+        let key = pad.generate_key(26_000, &mut rng).unwrap();
+
+        // every letter close to 1000 times
+        for c in 'a'..='z' {
+            let count = key.chars().filter(|&x| x == c).count();
+            assert!((850..1150).contains(&count), "{} {}", c, count);
+        }
+    }
+}
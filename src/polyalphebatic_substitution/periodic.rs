@@ -0,0 +1,165 @@
+/*===================================================================
+ * Author: denosauabh
+ * Description: Periodic polyalphabetic substitution, the engine behind Vigenère, Beaufort,
+                Variant Beaufort, Gronsfeld & Porta
+ * Reference: https://en.wikipedia.org/wiki/Tabula_recta
+
+ * The key is repeated along the text, every character is substituted with the row of
+   the tableau picked by its key character (indices in a charset of m characters):
+
+    Vigenère          c = p + k        p = c - k
+    Beaufort          c = k - p        p = k - c       (its own inverse)
+    Variant Beaufort  c = p - k        p = c + k
+    Porta             the two halves of the charset swapped, the second shifted
+                      back by k / 2    (its own inverse)
+
+ * Every character of a column (every L-th character for a key of length L) goes through
+   the same row, so the key length & key of all of them fall to the same analysis.
+*===================================================================*/
+
+use crate::utils::{char_set::CharSet, error::Error, mod_arithmetic::ModArithmetic};
+
+/// how a text index & a key index combine
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tableau {
+    Vigenère,
+    Beaufort,
+    VariantBeaufort,
+    Porta,
+}
+
+impl Tableau {
+    pub fn encrypt(&self, p: usize, k: usize, m: usize) -> usize {
+        let (p, k, m) = (p as i32, k as i32, m as i32);
+
+        (match self {
+            Tableau::Vigenère => ModArithmetic::add(p, k, m),
+            Tableau::Beaufort => ModArithmetic::add(k, -p, m),
+            Tableau::VariantBeaufort => ModArithmetic::add(p, -k, m),
+            Tableau::Porta => Self::porta(p, k, m),
+        }) as usize
+    }
+
+    pub fn decrypt(&self, c: usize, k: usize, m: usize) -> usize {
+        match self {
+            Tableau::Vigenère => Tableau::VariantBeaufort.encrypt(c, k, m),
+            Tableau::VariantBeaufort => Tableau::Vigenère.encrypt(c, k, m),
+            Tableau::Beaufort | Tableau::Porta => self.encrypt(c, k, m),
+        }
+    }
+
+    /// first half <-> second half, a..m <-> n..z for A..Z, the last character
+    /// of an odd charset is left as it is
+    fn porta(p: i32, k: i32, m: i32) -> i32 {
+        let half = m / 2;
+        let shift = k / 2;
+
+        if p < half {
+            half + ModArithmetic::add(p, shift, half)
+        } else if p < 2 * half {
+            ModArithmetic::add(p - half, -shift, half)
+        } else {
+            p
+        }
+    }
+}
+
+pub struct PeriodicCipher {
+    char_set: CharSet,
+    tableau: Tableau,
+}
+
+impl PeriodicCipher {
+    pub fn new(char_set: CharSet, tableau: Tableau) -> Self {
+        Self { char_set, tableau }
+    }
+
+    pub fn char_set(&self) -> &CharSet {
+        &self.char_set
+    }
+
+    pub fn tableau(&self) -> Tableau {
+        self.tableau
+    }
+
+    pub fn encrypt(&self, text: &str, key: &str) -> Result<String, Error> {
+        self.encrypt_with(text, &self.parse_key(key)?)
+    }
+
+    pub fn decrypt(&self, text: &str, key: &str) -> Result<String, Error> {
+        self.decrypt_with(text, &self.parse_key(key)?)
+    }
+
+    /// key as indices, e.g. the digits of a Gronsfeld key
+    pub fn encrypt_with(&self, text: &str, key: &[usize]) -> Result<String, Error> {
+        self.substitute(text, key, |tableau, p, k, m| tableau.encrypt(p, k, m))
+    }
+
+    pub fn decrypt_with(&self, text: &str, key: &[usize]) -> Result<String, Error> {
+        self.substitute(text, key, |tableau, c, k, m| tableau.decrypt(c, k, m))
+    }
+
+    /// indices of the key characters in the charset
+    pub fn parse_key(&self, key: &str) -> Result<Vec<usize>, Error> {
+        if key.is_empty() {
+            return Err(Error::InvalidKey("key must not be empty".to_string()));
+        }
+
+        self.char_set.indices_of(key)
+    }
+
+    /// the key repeated along the text
+    fn substitute(&self, text: &str, key: &[usize], f: fn(&Tableau, usize, usize, usize) -> usize) -> Result<String, Error> {
+        if key.is_empty() {
+            return Err(Error::InvalidKey("key must not be empty".to_string()));
+        }
+
+        let m = self.char_set.len();
+
+        self.char_set
+            .indices_of(text)?
+            .into_iter()
+            .enumerate()
+            .map(|(i, x)| self.char_set.char_at(f(&self.tableau, x, key[i % key.len()], m)))
+            .collect()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TABLEAUX: [Tableau; 4] = [Tableau::Vigenère, Tableau::Beaufort, Tableau::VariantBeaufort, Tableau::Porta];
+
+    #[test]
+    fn test_tableau() {
+        // every row of the tableau is a permutation undone by decrypt
+        for m in [26, 27, 10] {
+            for tableau in TABLEAUX {
+                for k in 0..m {
+                    let mut row = (0..m).map(|p| tableau.encrypt(p, k, m)).collect::<Vec<usize>>();
+                    assert!(row.iter().enumerate().all(|(p, &c)| tableau.decrypt(c, k, m) == p), "{:?} {} {}", tableau, k, m);
+
+                    row.sort();
+                    assert_eq!(row, (0..m).collect::<Vec<usize>>());
+                }
+            }
+        }
+
+        // A <-> N for the keys A/B, A <-> Z for Y/Z
+        assert_eq!(Tableau::Porta.encrypt(0, 1, 26), 13);
+        assert_eq!(Tableau::Porta.encrypt(0, 25, 26), 25);
+        assert_eq!(Tableau::Porta.encrypt(25, 25, 26), 0);
+    }
+
+    #[test]
+    fn test_key_cycling() {
+        let cipher = PeriodicCipher::new(CharSet::from_numbers(), Tableau::Vigenère);
+
+        assert_eq!(cipher.encrypt_with("0000000", &[1, 2, 3]).unwrap(), "1231231");
+        assert_eq!(cipher.decrypt_with("1231231", &[1, 2, 3]).unwrap(), "0000000");
+        assert!(cipher.encrypt_with("0000", &[]).is_err());
+        assert!(cipher.encrypt("0000", "").is_err());
+    }
+}
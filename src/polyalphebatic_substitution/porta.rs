@@ -0,0 +1,85 @@
+/*===================================================================
+ * Author: denosauabh
+ * Description: Implementation of the Porta Cipher, 13 reciprocal alphabets picked by pairs of key letters
+ * Reference: https://en.wikipedia.org/wiki/Porta_cipher
+
+        A B C D E F G H I J K L M
+    AB  N O P Q R S T U V W X Y Z
+    CD  O P Q R S T U V W X Y Z N
+    EF  P Q R S T U V W X Y Z N O
+    ..
+    YZ  Z N O P Q R S T U V W X Y
+
+ * The first half of the charset is swapped with the second one, so the cipher is its own inverse.
+ * The charset must have an even length.
+*===================================================================*/
+
+use crate::polyalphebatic_substitution::periodic::{PeriodicCipher, Tableau};
+use crate::utils::{char_set::CharSet, cipher::Cipher, error::Error};
+
+pub struct Porta {
+    periodic: PeriodicCipher,
+}
+
+impl Porta {
+    pub fn new(char_set: CharSet) -> Result<Self, Error> {
+        if !char_set.len().is_multiple_of(2) {
+            return Err(Error::InvalidCharSet(format!("Porta needs an even number of characters, found {}", char_set.len())));
+        }
+
+        Ok(Self { periodic: PeriodicCipher::new(char_set, Tableau::Porta) })
+    }
+
+    pub fn encrypt(&self, text: &str, key: &str) -> Result<String, Error> {
+        self.periodic.encrypt(text, key)
+    }
+
+    pub fn decrypt(&self, text: &str, key: &str) -> Result<String, Error> {
+        self.periodic.decrypt(text, key)
+    }
+}
+
+impl Cipher for Porta {
+    type Key = String;
+
+    fn encrypt(&self, text: &str, key: &String) -> Result<String, Error> {
+        self.encrypt(text, key)
+    }
+
+    fn decrypt(&self, text: &str, key: &String) -> Result<String, Error> {
+        self.decrypt(text, key)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt() {
+        let porta = Porta::new(CharSet::from_range('A', 'Z')).unwrap();
+
+        let encrypted = porta.encrypt("DEFENDTHEEASTWALLOFTHECASTLE", "FORTIFICATION").unwrap();
+        assert_eq!(encrypted, "SYNNJSCVRNRLAHUTUKUCVRYRLANY");
+
+        // A & B pick the same alphabet
+        assert_eq!(porta.encrypt("HELLO", "A").unwrap(), porta.encrypt("HELLO", "B").unwrap());
+    }
+
+    #[test]
+    fn test_decrypt() {
+        let porta = Porta::new(CharSet::from_range('A', 'Z')).unwrap();
+
+        let decrypted = porta.decrypt("SYNNJSCVRNRLAHUTUKUCVRYRLANY", "FORTIFICATION").unwrap();
+        assert_eq!(decrypted, "DEFENDTHEEASTWALLOFTHECASTLE");
+
+        // reciprocal
+        assert_eq!(porta.encrypt("SYNNJSCVRNRLAHUTUKUCVRYRLANY", "FORTIFICATION").unwrap(), decrypted);
+    }
+
+    #[test]
+    fn test_invalid_char_set() {
+        assert!(Porta::new(CharSet::from_string("ABC")).is_err());
+    }
+}
@@ -0,0 +1,106 @@
+/*===================================================================
+ * Author: denosauabh
+ * Description: Implementation of the Running Key Cipher, Vigenère with a key as long as the text
+ * Reference: https://en.wikipedia.org/wiki/Running_key_cipher
+
+ * The key is a long text, e.g. a passage of a book both sides own, so it never repeats
+   & there are no columns for Kasiski or Friedman to find:
+
+    attackatdawn
+    itwasthebest        (It was the best of times, ...)
+    impaudhxeeog
+
+ * Characters of the key text outside of the charset (spaces, punctuation) are skipped,
+   what is left must be at least as long as the text.
+ * It still falls to language statistics, both the text & the key are language.
+*===================================================================*/
+
+use crate::polyalphebatic_substitution::periodic::{PeriodicCipher, Tableau};
+use crate::utils::{analysis::Analysis, char_set::CharSet, cipher::Cipher, error::Error};
+
+pub struct RunningKey {
+    periodic: PeriodicCipher,
+}
+
+impl RunningKey {
+    /// Vigenère tableau
+    pub fn new(char_set: CharSet) -> Self {
+        Self::with_tableau(char_set, Tableau::Vigenère)
+    }
+
+    /// e.g. a running key Beaufort
+    pub fn with_tableau(char_set: CharSet, tableau: Tableau) -> Self {
+        Self { periodic: PeriodicCipher::new(char_set, tableau) }
+    }
+
+    pub fn encrypt(&self, text: &str, key_text: &str) -> Result<String, Error> {
+        self.periodic.encrypt_with(text, &self.parse_key(text, key_text)?)
+    }
+
+    pub fn decrypt(&self, text: &str, key_text: &str) -> Result<String, Error> {
+        self.periodic.decrypt_with(text, &self.parse_key(text, key_text)?)
+    }
+
+    /// the characters of the key text in the charset, as many as the text has
+    pub fn parse_key(&self, text: &str, key_text: &str) -> Result<Vec<usize>, Error> {
+        let char_set = self.periodic.char_set();
+        let len = text.chars().count();
+        let key = Analysis::filter(key_text, char_set);
+
+        if key.len() < len {
+            return Err(Error::InvalidKeyLength { expected: len, found: key.len() });
+        }
+
+        char_set.indices_of(&key[..len].iter().collect::<String>())
+    }
+}
+
+impl Cipher for RunningKey {
+    /// key text, at least as long as the text
+    type Key = String;
+
+    fn encrypt(&self, text: &str, key: &String) -> Result<String, Error> {
+        self.encrypt(text, key)
+    }
+
+    fn decrypt(&self, text: &str, key: &String) -> Result<String, Error> {
+        self.decrypt(text, key)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polyalphebatic_substitution::vigenere::VigenèreCipher;
+
+    const KEY_TEXT: &str = "it was the best of times, it was the worst of times";
+
+    #[test]
+    fn test_encrypt() {
+        let running_key = RunningKey::new(CharSet::from_alphabet_lowercase());
+
+        let encrypted = running_key.encrypt("attackatdawn", KEY_TEXT).unwrap();
+        assert_eq!(encrypted, "impaudhxeeog");
+        assert_eq!(encrypted, VigenèreCipher::new(CharSet::from_alphabet_lowercase()).encrypt("attackatdawn", "itwasthebest").unwrap());
+    }
+
+    #[test]
+    fn test_decrypt() {
+        let running_key = RunningKey::new(CharSet::from_alphabet_lowercase());
+        assert_eq!(running_key.decrypt("impaudhxeeog", KEY_TEXT).unwrap(), "attackatdawn");
+
+        let beaufort = RunningKey::with_tableau(CharSet::from_alphabet_lowercase(), Tableau::Beaufort);
+        let encrypted = beaufort.encrypt("attackatdawn", KEY_TEXT).unwrap();
+        assert_eq!(beaufort.decrypt(&encrypted, KEY_TEXT).unwrap(), "attackatdawn");
+    }
+
+    #[test]
+    fn test_key_length() {
+        let running_key = RunningKey::new(CharSet::from_alphabet_lowercase());
+
+        // 9 letters once the spaces are skipped
+        assert_eq!(running_key.encrypt("attackatdawn", "it was the b"), Err(Error::InvalidKeyLength { expected: 12, found: 9 }));
+        assert!(running_key.decrypt("impaudhxeeog", "").is_err());
+    }
+}
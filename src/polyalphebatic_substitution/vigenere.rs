@@ -1,48 +1,39 @@
 /*===================================================================
  * Author: denosauabh
- * Description: Implementation of Vigenère Cipher
+ * Description: Implementation of Vigenère Cipher, c = p + k on the `PeriodicCipher` engine
  * Reference: https://en.wikipedia.org/wiki/Vigenère_cipher
 *===================================================================*/
 
-use crate::utils::{char_set::CharSet, mod_arithmetic::ModArithmetic};
+use crate::polyalphebatic_substitution::periodic::{PeriodicCipher, Tableau};
+use crate::utils::{char_set::CharSet, cipher::Cipher, error::Error};
 
 pub struct VigenèreCipher {
-    char_set: CharSet,
+    periodic: PeriodicCipher,
 }
 
 impl VigenèreCipher {
     pub fn new(char_set: CharSet) -> Self {
-        Self { char_set }
+        Self { periodic: PeriodicCipher::new(char_set, Tableau::Vigenère) }
     }
 
-    pub fn encrypt(&self, text: &str, key: &str) -> String {
-        text.chars()
-            .enumerate()
-            .map(|(i, c)| {
-                let i: i32 = ModArithmetic::modm(i as i32, key.len() as i32);
+    pub fn encrypt(&self, text: &str, key: &str) -> Result<String, Error> {
+        self.periodic.encrypt(text, key)
+    }
 
-                let p = self.char_set.index_of(c);
-                let k = self.char_set.index_of(key.chars().nth(i as usize).unwrap());
+    pub fn decrypt(&self, text: &str, key: &str) -> Result<String, Error> {
+        self.periodic.decrypt(text, key)
+    }
+}
 
-                println!("p: {}, k: {}", p, k);
+impl Cipher for VigenèreCipher {
+    type Key = String;
 
-                let ci = ModArithmetic::modm((p + k) as i32, self.char_set.len() as i32) as usize;
-                self.char_set.char_at(ci)
-            })
-            .collect()
+    fn encrypt(&self, text: &str, key: &String) -> Result<String, Error> {
+        self.encrypt(text, key)
     }
 
-    pub fn decrypt(&self, text: &str, key: &str) -> String {
-        text.chars()
-            .enumerate()
-            .map(|(i, c)| {
-                let ki = self.char_set.index_of(key.chars().nth(i % key.len()).unwrap());
-                let ci = self.char_set.index_of(c);
-
-                let pi = (ci + self.char_set.len() - ki) % self.char_set.len();
-                self.char_set.char_at(pi)
-            })
-            .collect()
+    fn decrypt(&self, text: &str, key: &String) -> Result<String, Error> {
+        self.decrypt(text, key)
     }
 }
 
@@ -57,13 +48,13 @@ mod tests {
         let char_set = CharSet::from_alphabet_lowercase();
         let vigenere = VigenèreCipher::new(char_set);
 
-        let encrypted = vigenere.encrypt("attackingtonight", "oculorhinolaringology");
+        let encrypted = vigenere.encrypt("attackingtonight", "oculorhinolaringology").unwrap();
         assert_eq!(encrypted, "ovnlqbpvthznzouz");
 
         let char_set = CharSet::from_numbers();
         let vigenere = VigenèreCipher::new(char_set);
 
-        let encrypted = vigenere.encrypt("923467234", "82394343");
+        let encrypted = vigenere.encrypt("923467234", "82394343").unwrap();
         assert_eq!(encrypted, "746300662");
     }
 
@@ -72,15 +63,24 @@ mod tests {
         let char_set = CharSet::from_alphabet_lowercase();
         let vigenere = VigenèreCipher::new(char_set);
 
-        let decrypted = vigenere.decrypt("ovnlqbpvthznzouz", "oculorhinolaringology");
+        let decrypted = vigenere.decrypt("ovnlqbpvthznzouz", "oculorhinolaringology").unwrap();
         assert_eq!(decrypted, "attackingtonight");
 
 
         let char_set = CharSet::from_numbers();
         let vigenere = VigenèreCipher::new(char_set);
 
-        let decrypted = vigenere.decrypt("746300662", "82394343");
+        let decrypted = vigenere.decrypt("746300662", "82394343").unwrap();
         assert_eq!(decrypted, "923467234");
     }
+
+    #[test]
+    fn test_invalid_key() {
+        let char_set = CharSet::from_alphabet_lowercase();
+        let vigenere = VigenèreCipher::new(char_set);
+
+        assert!(vigenere.encrypt("attack", "").is_err());
+        assert_eq!(vigenere.encrypt("attack", "lem0n"), Err(Error::UnknownCharacter { character: '0', position: Some(3) }));
+    }
  
 }
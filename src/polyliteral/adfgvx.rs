@@ -14,85 +14,118 @@
     X	S	U	V	X	Y	Z
 
 
-    there's also a version of this cipher called ADFGX, which is a simplified version of ADFGVX
-    where the alphabet is reduced to 5 characters, and the grid is 5x5 rather than 6x6
+    there's also a version of this cipher called ADFGX (March 1918, ADFGVX followed in June), 
+    where the labels are reduced to 5 characters, and the grid is 5x5 rather than 6x6,
+    holding A..Z with I/J sharing a cell (`ADFGVX::adfgx`):
+
+        A	D	F	G	X
+    A	B	T	A	L	P
+    D	D	H	O	Z	K
+    F	Q	F	V	S	N
+    G	G	I/J	C	U	X
+    X	M	R	E	W	Y
+
+    in the field the square was keyed, it's derived from a keyword with `ADFGVX::adfgx` & `ADFGVX::with_keyword`,
+    the square is a labelled `PolybiusSquare`, any other one can be used with `ADFGVX::with_square`
 
 
  *===================================================================*/
 
 
-use crate::{transposition::columnar::Columnar, utils::char_set::CharSet};
+use crate::transposition::columnar::Columnar;
+use crate::utils::{char_set::CharSet, cipher::Cipher, error::Error, polybius::PolybiusSquare};
 
  pub struct ADFGVX {
-    char_set: CharSet,
-
-    row_char_set: CharSet,
-    col_char_set: CharSet,
+    square: PolybiusSquare,
  }
  
  impl ADFGVX {
-    /// as this is based purely on Polybius Square, we will use the default alphabet charset
+    /// the square above, `char_set` written row by row with `row_char_set` & `col_char_set` as labels
      pub fn new(
         char_set: Option<CharSet>,
 
         row_char_set: Option<CharSet>,
         col_char_set: Option<CharSet>,
-     ) -> Self {
+     ) -> Result<Self, Error> {
         let char_set = char_set.unwrap_or(CharSet::from_string("NA1C3H8TB2OME5WRPD4F6G7I9J0KLQSUVXYZ"));
 
         let row_char_set = row_char_set.unwrap_or(CharSet::from_string("ADFGVX"));
         let col_char_set = col_char_set.unwrap_or(CharSet::from_string("ADFGVX"));
 
-        if char_set.len() != row_char_set.len() * col_char_set.len() {
-            panic!("char_set length must be equal to row_char_set length * col_char_set length");
-        }
+        let square = PolybiusSquare::with_dimensions("", &char_set, &[], row_char_set.len(), col_char_set.len())?
+            .labelled(&row_char_set.chars.iter().collect::<String>(), &col_char_set.chars.iter().collect::<String>())?;
 
-        Self {
-            char_set,
-            row_char_set,
-            col_char_set
-        }
+        Ok(Self { square })
+     }
+
+    /// ADFGVX, A..Z & 0..9 keyed by the letters & digits of `keyword` (a passphrase is fine)
+     pub fn with_keyword(keyword: &str) -> Result<Self, Error> {
+        let char_set = CharSet::from_string("ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789");
+        let keyword = PolybiusSquare::keyword(keyword, &char_set);
+
+        Ok(Self { square: PolybiusSquare::new(&keyword, &char_set)?.labelled("ADFGVX", "ADFGVX")? })
+     }
+
+    /// ADFGX, A..Z with J merged into I keyed by the letters of `keyword`
+     pub fn adfgx(keyword: &str) -> Result<Self, Error> {
+        let char_set = CharSet::from_range('A', 'Z');
+        let keyword = PolybiusSquare::keyword(keyword, &char_set);
+
+        let square = PolybiusSquare::with_merge(&keyword, &char_set, Some(('J', 'I')))?;
+
+        Ok(Self { square: square.labelled("ADFGX", "ADFGX")? })
+     }
+
+    /// any labelled square
+     pub fn with_square(square: PolybiusSquare) -> Self {
+        Self { square }
      }
  
-     pub fn encrypt(&self, text: &str, key: &str) -> String {
+     pub fn encrypt(&self, text: &str, key: &str) -> Result<String, Error> {
         let mut encrypted = String::new();
 
         // polyliterial substitution using a table
-        for c in text.chars() {
-            let index = self.char_set.index_of(c);
-            let row = index / self.col_char_set.len();
-            let col = index % self.col_char_set.len();
+        for (i, c) in text.chars().enumerate() {
+            let (row, col) = self.square
+                .encode(c)
+                .map_err(|_| Error::UnknownCharacter { character: c, position: Some(i) })?;
 
-            encrypted.push(self.row_char_set.char_at(row));
-            encrypted.push(self.col_char_set.char_at(col));
+            encrypted.push(row);
+            encrypted.push(col);
         }
 
         // columnar transposition using key
         let columnar = Columnar::new();
-        encrypted = columnar.encrypt(&encrypted, key);
-
-        encrypted
+        columnar.encrypt(&encrypted, key)
      }
  
-     pub fn decrypt(&self, text: &str, key: &str) -> String {
+     pub fn decrypt(&self, text: &str, key: &str) -> Result<String, Error> {
         // reverse columnar transposition using key
         let columnar = Columnar::new();
-        let transposed_text = columnar.decrypt(text, key);
-
-        println!("transposed_text: {}", transposed_text);
+        let transposed_text = columnar.decrypt(text, key)?.chars().collect::<Vec<char>>();
 
-        let mut decrypted = String::new();
+        if !transposed_text.len().is_multiple_of(2) {
+            return Err(Error::MalformedCiphertext("ciphertext must have an even length".to_string()));
+        }
 
         // reverse polyliterial substitution using a table
-        for i in 0..transposed_text.len() / 2 {
-            let row = self.row_char_set.index_of(transposed_text.chars().nth(i * 2).unwrap());
-            let col = self.col_char_set.index_of(transposed_text.chars().nth(i * 2 + 1).unwrap());
+        transposed_text
+            .chunks(2)
+            .map(|pair| self.square.decode(pair[0], pair[1]))
+            .collect()
+    }
+ }
 
-            let index = row * self.col_char_set.len() + col;
-            decrypted.push(self.char_set.char_at(index));
-        }
+ impl Cipher for ADFGVX {
+    /// keyword of the columnar transposition step
+    type Key = String;
+
+    fn encrypt(&self, text: &str, key: &String) -> Result<String, Error> {
+        self.encrypt(text, key)
+    }
 
-        decrypted
+    fn decrypt(&self, text: &str, key: &String) -> Result<String, Error> {
+        self.decrypt(text, key)
     }
  }
  
@@ -103,17 +136,71 @@ use crate::{transposition::columnar::Columnar, utils::char_set::CharSet};
 
      #[test]
      fn test_encrypt() {
-         let adfgvx = ADFGVX::new(Option::None, Option::None, Option::None);
+         let adfgvx = ADFGVX::new(Option::None, Option::None, Option::None).unwrap();
  
-         let encrypted = adfgvx.encrypt(&"ATTACK AT 1200 AM".replace(' ', ""), "PRIVACY");
+         let encrypted = adfgvx.encrypt(&"ATTACK AT 1200 AM".replace(' ', ""), "PRIVACY").unwrap();
          assert_eq!(encrypted, "DGDD DAGD DGAF ADDF DADV DVFA ADVX".replace(' ', ""));
      }
  
      #[test]
      fn test_decrypt() {
-        let adfgvx = ADFGVX::new(Option::None, Option::None, Option::None);
+        let adfgvx = ADFGVX::new(Option::None, Option::None, Option::None).unwrap();
  
-        let decrypted: String = adfgvx.decrypt(&"DGDD DAGD DGAF ADDF DADV DVFA ADVX".replace(' ', ""), "PRIVACY");
+        let decrypted: String = adfgvx.decrypt(&"DGDD DAGD DGAF ADDF DADV DVFA ADVX".replace(' ', ""), "PRIVACY").unwrap();
         assert_eq!(decrypted, "ATTACK AT 1200 AM".replace(' ', ""));
      }
+
+     #[test]
+     fn test_round_trip() {
+        let adfgvx = ADFGVX::new(Option::None, Option::None, Option::None).unwrap();
+
+        // the transposition grid is irregular for most lengths
+        for text in ["A", "ATTACK", "ATTACKAT1200AMTOMORROW"] {
+            let encrypted = adfgvx.encrypt(text, "PRIVACY").unwrap();
+            assert_eq!(adfgvx.decrypt(&encrypted, "PRIVACY").unwrap(), text);
+        }
+     }
+
+     #[test]
+     fn test_keyed_square() {
+        let adfgvx = ADFGVX::with_keyword("Privacy 1st!").unwrap();
+
+        // P R I V A C / Y 1 S T B D / ..., CAB -> AX AV DV, transposed by KEY
+        let encrypted = adfgvx.encrypt("CAB", "KEY").unwrap();
+        assert_eq!(encrypted, "XDAVAV");
+        assert_eq!(adfgvx.decrypt(&encrypted, "KEY").unwrap(), "CAB");
+     }
+
+     #[test]
+     fn test_adfgx() {
+        let adfgx = ADFGVX::adfgx("BTALPDHOZKQFVSNGICUXMREWY").unwrap();
+
+        // AF AD AD AF GF DX AF AD DF FX GF XF, transposed by CARGO
+        let encrypted = adfgx.encrypt("ATTACKATONCE", "CARGO").unwrap();
+        assert_eq!(encrypted, "FAXDF ADDDG DGFFF AFAX AFAFX".replace(' ', ""));
+        assert_eq!(adfgx.decrypt(&encrypted, "CARGO").unwrap(), "ATTACKATONCE");
+
+        // J is written as I
+        let encrypted = adfgx.encrypt("JUST", "CARGO").unwrap();
+        assert_eq!(adfgx.decrypt(&encrypted, "CARGO").unwrap(), "IUST");
+
+        assert!(adfgx.encrypt("ATTACK AT 1", "CARGO").is_err());
+        assert!(adfgx.decrypt("FAXDV", "CARGO").is_err());
+     }
+
+     #[test]
+     fn test_keyed_round_trip() {
+        for adfgvx in [ADFGVX::with_keyword("GERMAN ARMY 1918").unwrap(), ADFGVX::adfgx("Geheimschreiber").unwrap()] {
+            for text in ["A", "ATTACK", "ATTACKATDAWNONTHEWESTERNFRONT"] {
+                let encrypted = adfgvx.encrypt(text, "KAISER").unwrap();
+                assert_eq!(adfgvx.decrypt(&encrypted, "KAISER").unwrap(), text);
+            }
+        }
+     }
+
+     #[test]
+     fn test_invalid_square() {
+        let square = ADFGVX::new(Some(CharSet::from_string("ABC")), Option::None, Option::None);
+        assert!(square.is_err());
+     }
  }
@@ -0,0 +1,167 @@
+/*===================================================================
+ * Bifid Cipher
+ * Author: denosauabh
+ * Description: Implementation of the Bifid Cipher, a fractionation of the Polybius square coordinates
+ * Reference: https://en.wikipedia.org/wiki/Bifid_cipher
+
+ * keyword "BGWKZQPNDSIOAXEFCLUMTHYVR" over A..Z with J merged into I:
+
+        1 2 3 4 5
+    1   B G W K Z
+    2   Q P N D S
+    3   I O A X E
+    4   F C L U M
+    5   T H Y V R
+
+ * The rows & columns of a block of letters are written on two lines, read off
+   row line first, and the pairs turned back into letters:
+
+    F L E E A T O N C E
+    4 4 3 3 3 5 3 2 4 3     ->  44 33 35 32 43 13 55 31 23 25  ->  U A E O L W R I N S
+    1 3 5 5 3 1 2 3 2 5
+
+ * The period is the length of a block, 0 takes the whole text as one block.
+*===================================================================*/
+
+use crate::utils::cipher::Cipher;
+use crate::utils::error::Error;
+use crate::utils::polybius::{PolybiusSquare, SquareOptions};
+
+pub struct Bifid {
+    options: SquareOptions,
+    period: usize,
+}
+
+impl Bifid {
+    pub fn new(period: usize) -> Self {
+        Self::with_options(SquareOptions::default(), period)
+    }
+
+    pub fn with_options(options: SquareOptions, period: usize) -> Self {
+        Self { options, period }
+    }
+
+    pub fn square(&self, keyword: &str) -> Result<PolybiusSquare, Error> {
+        self.options.keyed(keyword)
+    }
+
+    pub fn encrypt(&self, text: &str, keyword: &str) -> Result<String, Error> {
+        let square = self.square(keyword)?;
+        let positions = Self::positions(&square, text)?;
+
+        let mut encrypted = String::with_capacity(positions.len());
+
+        for block in positions.chunks(self.block_len(positions.len())) {
+            let line = block
+                .iter()
+                .map(|&(row, _)| row)
+                .chain(block.iter().map(|&(_, col)| col))
+                .collect::<Vec<usize>>();
+
+            for pair in line.chunks(2) {
+                encrypted.push(square.char_at(pair[0], pair[1])?);
+            }
+        }
+
+        Ok(encrypted)
+    }
+
+    pub fn decrypt(&self, text: &str, keyword: &str) -> Result<String, Error> {
+        let square = self.square(keyword)?;
+        let positions = Self::positions(&square, text)?;
+
+        let mut decrypted = String::with_capacity(positions.len());
+
+        for block in positions.chunks(self.block_len(positions.len())) {
+            let line = block
+                .iter()
+                .flat_map(|&(row, col)| [row, col])
+                .collect::<Vec<usize>>();
+            let (rows, cols) = line.split_at(block.len());
+
+            for (&row, &col) in rows.iter().zip(cols) {
+                decrypted.push(square.char_at(row, col)?);
+            }
+        }
+
+        Ok(decrypted)
+    }
+
+    fn block_len(&self, len: usize) -> usize {
+        match self.period {
+            0 => len.max(1),
+            period => period,
+        }
+    }
+
+    fn positions(square: &PolybiusSquare, text: &str) -> Result<Vec<(usize, usize)>, Error> {
+        text.chars()
+            .enumerate()
+            .map(|(i, c)| square.position(c).map_err(|_| Error::UnknownCharacter { character: c, position: Some(i) }))
+            .collect()
+    }
+}
+
+impl Cipher for Bifid {
+    /// keyword of the square
+    type Key = String;
+
+    fn encrypt(&self, text: &str, key: &String) -> Result<String, Error> {
+        self.encrypt(text, key)
+    }
+
+    fn decrypt(&self, text: &str, key: &String) -> Result<String, Error> {
+        self.decrypt(text, key)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::char_set::CharSet;
+
+    const KEYWORD: &str = "BGWKZQPNDSIOAXEFCLUMTHYVR";
+
+    #[test]
+    fn test_encrypt() {
+        let bifid = Bifid::new(0);
+        assert_eq!(bifid.encrypt("FLEEATONCE", KEYWORD).unwrap(), "UAEOLWRINS");
+
+        let bifid = Bifid::new(5);
+        assert_eq!(
+            bifid.encrypt("DEFENDTHEEASTWALLOFTHECASTLE", "PHQGMEAYLNOFDXKRCVSZWBUTI").unwrap(),
+            "FFYHMKHYCPLIASHADTRLHCCHLBLR"
+        );
+    }
+
+    #[test]
+    fn test_decrypt() {
+        let bifid = Bifid::new(0);
+        assert_eq!(bifid.decrypt("UAEOLWRINS", KEYWORD).unwrap(), "FLEEATONCE");
+
+        let bifid = Bifid::new(5);
+        assert_eq!(
+            bifid.decrypt("FFYHMKHYCPLIASHADTRLHCCHLBLR", "PHQGMEAYLNOFDXKRCVSZWBUTI").unwrap(),
+            "DEFENDTHEEASTWALLOFTHECASTLE"
+        );
+
+        assert_eq!(bifid.decrypt("UA1", KEYWORD), Err(Error::UnknownCharacter { character: '1', position: Some(2) }));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        // a shorter last block, J written as I
+        let bifid = Bifid::new(4);
+        let encrypted = bifid.encrypt("JUSTATEST", "KEYWORD").unwrap();
+        assert_eq!(bifid.decrypt(&encrypted, "KEYWORD").unwrap(), "IUSTATEST");
+
+        // 6×6 over letters & digits
+        let options = SquareOptions::new(CharSet::from_string("ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789"), None).unwrap();
+        let bifid = Bifid::with_options(options, 7);
+        let encrypted = bifid.encrypt("MEETAT1200NOON", "PRIVACY").unwrap();
+        assert_eq!(bifid.decrypt(&encrypted, "PRIVACY").unwrap(), "MEETAT1200NOON");
+
+        assert!(SquareOptions::new(CharSet::from_range('A', 'Z'), None).is_err());
+    }
+}
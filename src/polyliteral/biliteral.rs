@@ -16,67 +16,92 @@
 
     putting `IJ` is a common practice as the square only allows 25 characters, so one letter needs to fit with someone else. 
 
+    any `PolybiusSquare` can be used instead, keyed, with other labels or dimensions.
+
 
  *===================================================================*/
 
 
 use crate::utils::char_set::CharSet;
+use crate::utils::cipher::Cipher;
+use crate::utils::error::Error;
+use crate::utils::polybius::PolybiusSquare;
 
 pub struct Biliteral {
-    char_set: CharSet,
+    square: PolybiusSquare,
 }
 
 impl Biliteral {
-    /// as this is based purely on Polybius Square, we will use the default alphabet charset
+    /// the square above, A..Z with J merged into I
     pub fn new() -> Self {
-        Self { char_set: CharSet::from_string("ABCDEFGHIJKLMNOPQRSTUVWXYZ") }
+        let square = PolybiusSquare::with_merge("", &CharSet::from_range('A', 'Z'), Some(('J', 'I')))
+            .expect("25 letters fill a 5×5 square");
+
+        Self { square }
     }
 
-    pub fn encrypt(&self, text: &str) -> String {
-        text.chars()
-            .map(|c| {
-                let mut index = self.char_set.index_of(c);
+    /// any square, e.g. keyed by a passphrase or with other labels
+    pub fn with_square(square: PolybiusSquare) -> Self {
+        Self { square }
+    }
 
-                /// to accomodate IJ together
-                if index > 9 { index -= 1 };
-                
-                let row = index / 5 + 1;
-                let col = index % 5 + 1;
+    pub fn encrypt(&self, text: &str) -> Result<String, Error> {
+        let mut encrypted = String::new();
 
-                format!("{}{}", row, col)
-            })
-            .collect()
+        for (i, c) in text.chars().enumerate() {
+            let (row, col) = self.square
+                .encode(c)
+                .map_err(|_| Error::UnknownCharacter { character: c, position: Some(i) })?;
+
+            encrypted.push(row);
+            encrypted.push(col);
+        }
+
+        Ok(encrypted)
     }
 
-    pub fn decrypt(&self, text: &str) -> String {
-        text.chars()
+    pub fn decrypt(&self, text: &str) -> Result<String, Error> {
+        let chars = text.chars().collect::<Vec<char>>();
+
+        if !chars.len().is_multiple_of(2) {
+            return Err(Error::MalformedCiphertext("ciphertext must be pairs of labels".to_string()));
+        }
+
+        chars
+            .chunks(2)
             .enumerate()
-            .filter_map(|(i, c)| {
-                if i % 2 == 0 {
-                    return None;
-                };
-
-                let mut row = text.chars().nth(i - 1).unwrap().to_digit(10).unwrap() - 1;
-                let mut col = c.to_digit(10).unwrap() - 1;
-
-                if row * 5 + col > 9 {
-                    let cc: u32 = col + 1;
-
-                    if cc > 4 {
-                        row += 1;
-                        col = 0;
-                    } else {
-                        col += 1;
-                    }
-                }
-                
-                let index = row * 5 + col;
-                Some(self.char_set.char_at(index as usize))
+            .map(|(i, pair)| {
+                self.square.decode(pair[0], pair[1]).map_err(|err| match err {
+                    Error::UnknownCharacter { character, .. } => {
+                        let offset = if character == pair[0] { 0 } else { 1 };
+                        Error::UnknownCharacter { character, position: Some(i * 2 + offset) }
+                    },
+                    err => err,
+                })
             })
             .collect()
     }
 }
 
+impl Default for Biliteral {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Cipher for Biliteral {
+    /// the Polybius square is part of the constructor, there is no key
+    type Key = ();
+
+    fn encrypt(&self, text: &str, _key: &()) -> Result<String, Error> {
+        self.encrypt(text)
+    }
+
+    fn decrypt(&self, text: &str, _key: &()) -> Result<String, Error> {
+        self.decrypt(text)
+    }
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -87,7 +112,7 @@ mod tests {
     fn test_encrypt() {
         let biliteral = Biliteral::new();
 
-        let encrypted = biliteral.encrypt("HELLO");
+        let encrypted = biliteral.encrypt("HELLO").unwrap();
         assert_eq!(encrypted, "23 15 31 31 34".replace(' ', ""));
     }
 
@@ -95,7 +120,35 @@ mod tests {
     fn test_decrypt() {
         let biliteral = Biliteral::new();
 
-        let decrypted = biliteral.decrypt(&"23 15 31 31 34".replace(' ', ""));
+        let decrypted = biliteral.decrypt(&"23 15 31 31 34".replace(' ', "")).unwrap();
         assert_eq!(decrypted, "HELLO");
+
+        assert!(biliteral.decrypt("2315313").is_err());
+        assert!(biliteral.decrypt("2316").is_err());
+    }
+
+    #[test]
+    fn test_merge() {
+        let biliteral = Biliteral::new();
+
+        // J & I share a cell, K keeps its own
+        assert_eq!(biliteral.encrypt("JIK").unwrap(), "242425");
+        assert_eq!(biliteral.decrypt("242425").unwrap(), "IIK");
+    }
+
+    #[test]
+    fn test_keyed_square() {
+        let square = PolybiusSquare::with_merge("POLYBIUS", &CharSet::from_range('A', 'Z'), Some(('J', 'I')))
+            .unwrap()
+            .labelled("ABCDE", "VWXYZ")
+            .unwrap();
+        let biliteral = Biliteral::with_square(square);
+
+        // P O L Y B / I U S A C / ...
+        let encrypted = biliteral.encrypt("PLAYS").unwrap();
+        assert_eq!(encrypted, "AVAXBYAYBX");
+        assert_eq!(biliteral.decrypt(&encrypted).unwrap(), "PLAYS");
+
+        assert_eq!(biliteral.decrypt("AVA1"), Err(Error::UnknownCharacter { character: '1', position: Some(3) }));
     }
 }
\ No newline at end of file
@@ -1,2 +1,5 @@
 pub mod biliteral;
-pub mod adfgvx;
\ No newline at end of file
+pub mod adfgvx;
+pub mod bifid;
+pub mod trifid;
+pub mod nihilist;
\ No newline at end of file
@@ -0,0 +1,180 @@
+/*===================================================================
+ * Nihilist Cipher
+ * Author: denosauabh
+ * Description: Implementation of the Nihilist Cipher, a keyed Polybius square & an additive numeric key
+ * Reference: https://en.wikipedia.org/wiki/Nihilist_cipher
+
+ * keyword "ZEBRAS" over A..Z with J merged into I:
+
+        1 2 3 4 5
+    1   Z E B R A
+    2   S C D F G
+    3   H I K L M
+    4   N O P Q T
+    5   U V W X Y
+
+ * The text & the (repeated) key are turned into the numbers of their cells,
+   added & written as two digits, modulo 100:
+
+    D  Y  N  A  M  I  T         23 55 41 15 35 32 45
+    R  U  S  S  I  A  N     +   14 51 21 21 32 15 41
+                            =   37 06 62 36 67 47 86
+
+ * Historically the sums were written as they are (106), modulo 100 every number keeps
+   two digits & the cells (11..99) can still be told apart. The square can't be larger than 9×9.
+*===================================================================*/
+
+use crate::utils::cipher::Cipher;
+use crate::utils::error::Error;
+use crate::utils::mod_arithmetic::ModArithmetic;
+use crate::utils::polybius::{PolybiusSquare, SquareOptions};
+
+pub struct Nihilist {
+    options: SquareOptions,
+}
+
+impl Nihilist {
+    pub fn new() -> Self {
+        Self { options: SquareOptions::default() }
+    }
+
+    /// the square can't be larger than 9×9
+    pub fn with_options(options: SquareOptions) -> Result<Self, Error> {
+        let square = options.plain();
+
+        if square.rows() > 9 {
+            return Err(Error::InvalidCharSet(format!("{}×{} square doesn't fit two digits", square.rows(), square.cols())));
+        }
+
+        Ok(Self { options })
+    }
+
+    pub fn square(&self, keyword: &str) -> Result<PolybiusSquare, Error> {
+        self.options.keyed(keyword)
+    }
+
+    pub fn encrypt(&self, text: &str, keyword: &str, key: &str) -> Result<String, Error> {
+        let square = self.square(keyword)?;
+        let key = Self::key(&square, key)?;
+
+        let mut encrypted = String::with_capacity(text.len() * 2);
+
+        for (i, c) in text.chars().enumerate() {
+            let number = Self::number(&square, c).map_err(|_| Error::UnknownCharacter { character: c, position: Some(i) })?;
+            encrypted.push_str(&format!("{:02}", ModArithmetic::add(number, key[i % key.len()], 100)));
+        }
+
+        Ok(encrypted)
+    }
+
+    pub fn decrypt(&self, text: &str, keyword: &str, key: &str) -> Result<String, Error> {
+        let square = self.square(keyword)?;
+        let key = Self::key(&square, key)?;
+
+        let digits = text
+            .chars()
+            .enumerate()
+            .map(|(i, c)| c.to_digit(10).ok_or(Error::UnknownCharacter { character: c, position: Some(i) }))
+            .collect::<Result<Vec<u32>, Error>>()?;
+
+        if !digits.len().is_multiple_of(2) {
+            return Err(Error::MalformedCiphertext("ciphertext must be pairs of digits".to_string()));
+        }
+
+        digits
+            .chunks(2)
+            .enumerate()
+            .map(|(i, pair)| {
+                let number = ModArithmetic::add((pair[0] * 10 + pair[1]) as i32, -key[i % key.len()], 100);
+                let (row, col) = (number / 10, number % 10);
+
+                if row == 0 || col == 0 {
+                    return Err(Error::MalformedCiphertext(format!("{}{} isn't a cell of the square", pair[0], pair[1])));
+                }
+
+                square.char_at(row as usize - 1, col as usize - 1)
+                    .map_err(|_| Error::MalformedCiphertext(format!("{}{} isn't a cell of the square", pair[0], pair[1])))
+            })
+            .collect()
+    }
+
+    /// the numbers of the key letters
+    fn key(square: &PolybiusSquare, key: &str) -> Result<Vec<i32>, Error> {
+        if key.is_empty() {
+            return Err(Error::InvalidKey("key must not be empty".to_string()));
+        }
+
+        key.chars()
+            .enumerate()
+            .map(|(i, c)| Self::number(square, c).map_err(|_| Error::UnknownCharacter { character: c, position: Some(i) }))
+            .collect()
+    }
+
+    /// row & column counted from 1, e.g. 23
+    fn number(square: &PolybiusSquare, c: char) -> Result<i32, Error> {
+        let (row, col) = square.position(c)?;
+        Ok(((row + 1) * 10 + col + 1) as i32)
+    }
+}
+
+impl Default for Nihilist {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Cipher for Nihilist {
+    /// keyword of the square & the additive key
+    type Key = (String, String);
+
+    fn encrypt(&self, text: &str, key: &(String, String)) -> Result<String, Error> {
+        self.encrypt(text, &key.0, &key.1)
+    }
+
+    fn decrypt(&self, text: &str, key: &(String, String)) -> Result<String, Error> {
+        self.decrypt(text, &key.0, &key.1)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::char_set::CharSet;
+
+    const ENCRYPTED: &str = "3706623667478626045362772755576655365427";
+
+    #[test]
+    fn test_encrypt() {
+        let nihilist = Nihilist::new();
+        assert_eq!(nihilist.encrypt("DYNAMITEWINTERPALACE", "ZEBRAS", "RUSSIAN").unwrap(), ENCRYPTED);
+
+        assert_eq!(nihilist.encrypt("DY", "ZEBRAS", ""), Err(Error::InvalidKey("key must not be empty".to_string())));
+        assert_eq!(nihilist.encrypt("D1", "ZEBRAS", "RUSSIAN"), Err(Error::UnknownCharacter { character: '1', position: Some(1) }));
+    }
+
+    #[test]
+    fn test_decrypt() {
+        let nihilist = Nihilist::new();
+        assert_eq!(nihilist.decrypt(ENCRYPTED, "ZEBRAS", "RUSSIAN").unwrap(), "DYNAMITEWINTERPALACE");
+
+        assert!(nihilist.decrypt("370", "ZEBRAS", "RUSSIAN").is_err());
+        assert!(nihilist.decrypt("37a6", "ZEBRAS", "RUSSIAN").is_err());
+
+        // 14 - 14 = 00 & 20 - 14 = 06 aren't cells
+        assert!(nihilist.decrypt("14", "ZEBRAS", "RUSSIAN").is_err());
+        assert!(nihilist.decrypt("20", "ZEBRAS", "RUSSIAN").is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let options = SquareOptions::new(CharSet::from_string("ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789"), None).unwrap();
+        let nihilist = Nihilist::with_options(options).unwrap();
+        let encrypted = nihilist.encrypt("MEETAT1200", "PRIVACY", "KEY9").unwrap();
+        assert_eq!(nihilist.decrypt(&encrypted, "PRIVACY", "KEY9").unwrap(), "MEETAT1200");
+
+        // 10×10
+        let options = SquareOptions::new(CharSet::from_unicode(0x0100, 0x0164), None).unwrap();
+        assert!(Nihilist::with_options(options).is_err());
+    }
+}
@@ -0,0 +1,170 @@
+/*===================================================================
+ * Trifid Cipher
+ * Author: denosauabh
+ * Description: Implementation of the Trifid Cipher, the Bifid Cipher on a 3×3×3 cube of 27 characters
+ * Reference: https://en.wikipedia.org/wiki/Trifid_cipher
+
+ * keyword "FELIXMARIEDELASTELLE" over A..Z & +:
+
+      layer 1       layer 2       layer 3
+       1 2 3         1 2 3         1 2 3
+    1  F E L      1  S T B      1  O P Q
+    2  I X M      2  C G H      2  U V W
+    3  A R D      3  J K N      3  Y Z +
+
+ * The layers, rows & columns of a block of letters are written on three lines, read off
+   line by line, and the triples turned back into letters:
+
+    A I D E T
+    1 1 1 1 2       ->  111 123 231 111 322  ->  F M J F V
+    3 2 3 1 1
+    1 1 3 2 2
+
+ * The cube is a 9×3 `PolybiusSquare`, layer by layer. The period is the length of a block,
+   0 takes the whole text as one block.
+*===================================================================*/
+
+use crate::utils::char_set::CharSet;
+use crate::utils::cipher::Cipher;
+use crate::utils::error::Error;
+use crate::utils::polybius::PolybiusSquare;
+
+pub struct Trifid {
+    char_set: CharSet,
+    period: usize,
+}
+
+impl Trifid {
+    /// A..Z & +
+    pub fn new(period: usize) -> Self {
+        Self { char_set: CharSet::from_string("ABCDEFGHIJKLMNOPQRSTUVWXYZ+"), period }
+    }
+
+    /// the charset must have 27 characters
+    pub fn with_options(char_set: CharSet, period: usize) -> Result<Self, Error> {
+        if char_set.len() != 27 {
+            return Err(Error::InvalidCharSet(format!("a 3×3×3 cube needs 27 characters, found {}", char_set.len())));
+        }
+
+        Ok(Self { char_set, period })
+    }
+
+    /// the cube layer by layer, 3 rows each
+    pub fn cube(&self, keyword: &str) -> Result<PolybiusSquare, Error> {
+        PolybiusSquare::with_dimensions(keyword, &self.char_set, &[], 9, 3)
+    }
+
+    pub fn encrypt(&self, text: &str, keyword: &str) -> Result<String, Error> {
+        let cube = self.cube(keyword)?;
+        let positions = Self::positions(&cube, text)?;
+
+        let mut encrypted = String::with_capacity(positions.len());
+
+        for block in positions.chunks(self.block_len(positions.len())) {
+            let lines = (0..3)
+                .flat_map(|line| block.iter().map(move |position| position[line]))
+                .collect::<Vec<usize>>();
+
+            for triple in lines.chunks(3) {
+                encrypted.push(cube.char_at(triple[0] * 3 + triple[1], triple[2])?);
+            }
+        }
+
+        Ok(encrypted)
+    }
+
+    pub fn decrypt(&self, text: &str, keyword: &str) -> Result<String, Error> {
+        let cube = self.cube(keyword)?;
+        let positions = Self::positions(&cube, text)?;
+
+        let mut decrypted = String::with_capacity(positions.len());
+
+        for block in positions.chunks(self.block_len(positions.len())) {
+            let lines = block.concat();
+            let (layers, rest) = lines.split_at(block.len());
+            let (rows, cols) = rest.split_at(block.len());
+
+            for i in 0..block.len() {
+                decrypted.push(cube.char_at(layers[i] * 3 + rows[i], cols[i])?);
+            }
+        }
+
+        Ok(decrypted)
+    }
+
+    fn block_len(&self, len: usize) -> usize {
+        match self.period {
+            0 => len.max(1),
+            period => period,
+        }
+    }
+
+    /// [layer, row, column] of every character
+    fn positions(cube: &PolybiusSquare, text: &str) -> Result<Vec<[usize; 3]>, Error> {
+        text.chars()
+            .enumerate()
+            .map(|(i, c)| {
+                cube.position(c)
+                    .map(|(row, col)| [row / 3, row % 3, col])
+                    .map_err(|_| Error::UnknownCharacter { character: c, position: Some(i) })
+            })
+            .collect()
+    }
+}
+
+impl Cipher for Trifid {
+    /// keyword of the cube
+    type Key = String;
+
+    fn encrypt(&self, text: &str, key: &String) -> Result<String, Error> {
+        self.encrypt(text, key)
+    }
+
+    fn decrypt(&self, text: &str, key: &String) -> Result<String, Error> {
+        self.decrypt(text, key)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEYWORD: &str = "FELIXMARIEDELASTELLE";
+
+    #[test]
+    fn test_cube() {
+        let cube = Trifid::new(5).cube(KEYWORD).unwrap();
+        assert_eq!(cube.chars().iter().collect::<String>(), "FELIXMARDSTBCGHJKNOPQUVWYZ+");
+    }
+
+    #[test]
+    fn test_encrypt() {
+        let trifid = Trifid::new(5);
+        assert_eq!(trifid.encrypt("AIDETOILECIELTAIDERA", KEYWORD).unwrap(), "FMJFVOISSUFTFPUFEQQC");
+    }
+
+    #[test]
+    fn test_decrypt() {
+        let trifid = Trifid::new(5);
+        assert_eq!(trifid.decrypt("FMJFVOISSUFTFPUFEQQC", KEYWORD).unwrap(), "AIDETOILECIELTAIDERA");
+
+        assert_eq!(trifid.decrypt("FMj", KEYWORD), Err(Error::UnknownCharacter { character: 'j', position: Some(2) }));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        // a shorter last block & the whole text as one block
+        for period in [0, 7] {
+            let trifid = Trifid::new(period);
+            let encrypted = trifid.encrypt("MEET+ME+AT+NOON", "KEYWORD").unwrap();
+            assert_eq!(trifid.decrypt(&encrypted, "KEYWORD").unwrap(), "MEET+ME+AT+NOON");
+        }
+
+        let trifid = Trifid::with_options(CharSet::from_string("abcdefghijklmnopqrstuvwxyz "), 4).unwrap();
+        let encrypted = trifid.encrypt("attack at dawn", "cube").unwrap();
+        assert_eq!(trifid.decrypt(&encrypted, "cube").unwrap(), "attack at dawn");
+
+        assert!(Trifid::with_options(CharSet::from_range('A', 'Z'), 5).is_err());
+    }
+}
@@ -0,0 +1,69 @@
+/*===================================================================
+ * Author: denosauabh
+ * Description: Re-exports of the most used types, `use moc::prelude::*;`
+ *===================================================================*/
+
+pub use crate::simple_substitution::{additive::AdditiveCipher, affine::AffineCipher, hill::{HillCipher, HillPadding}, multiplicative::MultiplicativeCipher};
+pub use crate::polyalphebatic_substitution::{
+    alberti::AlbertiCipher,
+    beaufort::{Beaufort, VariantBeaufort},
+    gronsfeld::Gronsfeld,
+    one_time_pad::OneTimePad,
+    periodic::{PeriodicCipher, Tableau},
+    porta::Porta,
+    running_key::RunningKey,
+    vigenere::VigenèreCipher,
+};
+pub use crate::digraphic_substitution::{four_square::FourSquare, playfair::Playfair, two_square::TwoSquare};
+pub use crate::transposition::{
+    columnar::Columnar,
+    disrupted::Disrupted,
+    double_columnar::DoubleColumnar,
+    geometric::{Geometric, GeometricTranspositionMethod},
+    myszkowski::Myszkowski,
+    permutation::Permutation,
+    rail_fence::RailFence,
+    route::{Corner, Rotation, Route, RouteCipher},
+    scytale::Scytale,
+};
+pub use crate::polyliteral::{adfgvx::ADFGVX, bifid::Bifid, biliteral::Biliteral, nihilist::Nihilist, trifid::Trifid};
+pub use crate::block::{aes::AES, des::DES};
+pub use crate::stream::{autokey::{AutoKeyType, Autokey}, gromark::Gromark};
+pub use crate::hash::{sha1::Sha1, sha256::Sha256, HashFunction};
+pub use crate::mode_of_operations::{cbc::CBC, cfb::CFB, ctr::CTR, ecb::ECB, pbc::PBC, pfb::PFB};
+pub use crate::public_key::{
+    deffie_hellman_key_exchange::DiffieHellmanKeyExchange,
+    pohlig_hellman::PohligHellman,
+    rsa::{RsaPrivateKey, RsaPublicKey, RSA},
+    signature::SignatureScheme,
+    tpp_pohlig_hellman::{Alice, Bob, TPPPohigHellman},
+};
+
+pub use crate::cryptanalysis::{
+    brute_force::BruteForce,
+    candidate::Candidate,
+    transposition::{TranspositionBreaker, TranspositionKind},
+    vigenere::VigenèreBreaker,
+};
+
+pub use crate::utils::{analysis::{Analysis, Frequencies, NgramModel}, bignum::BigUint, char_set::{CharSet, Converter}, cipher::Cipher, encoding::Encoding, error::Error, integer::Integer, polybius::{PolybiusSquare, SquareOptions}};
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prelude() -> Result<(), Error> {
+        let additive = AdditiveCipher::new(CharSet::from_alphabet_lowercase());
+        assert_eq!(additive.encrypt("hello", 3)?, "khoor");
+
+        let columnar = Columnar::new();
+        let encrypted = Cipher::encrypt(&columnar, "WEAREDISCOVEREDFLEEATONCE", &"ZEBRAS".to_string())?;
+        assert_eq!(Cipher::decrypt(&columnar, &encrypted, &"ZEBRAS".to_string())?, "WEAREDISCOVEREDFLEEATONCE");
+
+        assert!(Geometric::new(GeometricTranspositionMethod::Vertical, 0).is_err());
+
+        Ok(())
+    }
+}
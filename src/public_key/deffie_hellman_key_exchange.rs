@@ -2,31 +2,34 @@
  * Simple Implementation of Deffie-Hellman Key Exchange
  * Author: denosauabh
  * Reference: https://en.wikipedia.org/wiki/Diffie–Hellman_key_exchange
+
+ * Generic over the integer type, `u64` for toy primes, `BigUint` for the RFC 3526 groups.
  *===================================================================*/
 
 
-use crate::{utils::mod_arithmetic::ModArithmetic};
+use crate::utils::integer::Integer;
 
-pub struct DiffieHellmanKeyExchange {
-    pub prime: u64,
-    pub generator: u64,
+pub struct DiffieHellmanKeyExchange<T: Integer = u64> {
+    pub prime: T,
+    pub generator: T,
 }
 
-impl DiffieHellmanKeyExchange {
-    pub fn new(prime: u64, generator: u64) -> Self {
+impl<T: Integer> DiffieHellmanKeyExchange<T> {
+    pub fn new(prime: T, generator: T) -> Self {
         DiffieHellmanKeyExchange {
             prime,
             generator,
         }
     }
 
-    pub fn setup(&self, private_key_a: u64, private_key_b: u64) -> (u64, u64, u64) {
+    #[allow(non_snake_case)]
+    pub fn setup(&self, private_key_a: T, private_key_b: T) -> (T, T, T) {
         // generate the public keys
-        let A = ModArithmetic::pow(self.generator, private_key_a, self.prime);
-        let B = ModArithmetic::pow(self.generator, private_key_b, self.prime);
+        let A = self.generator.pow_mod(&private_key_a, &self.prime);
+        let B = self.generator.pow_mod(&private_key_b, &self.prime);
 
         // generate the shared key
-        let g_ab = ModArithmetic::pow(A, private_key_b, self.prime);
+        let g_ab = A.pow_mod(&private_key_b, &self.prime);
 
         (A, B, g_ab)
     }
@@ -35,14 +38,31 @@ impl DiffieHellmanKeyExchange {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::bignum::BigUint;
 
     #[test]
+    #[allow(non_snake_case)]
     fn test_diffie_hellman_key_agreement() {
-        let diffie_hellman = DiffieHellmanKeyExchange::new(2819, 2);
+        let diffie_hellman = DiffieHellmanKeyExchange::new(2819u64, 2);
         let (A, B, g_ab) = diffie_hellman.setup(94, 305);
 
         assert_eq!(A, 2220);
         assert_eq!(B, 1367);
         assert_eq!(g_ab, 747);
     }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_diffie_hellman_2048() {
+        // 2048-bit MODP group of RFC 3526 (group 14)
+        let prime: BigUint = "0xffffffffffffffffc90fdaa22168c234c4c6628b80dc1cd129024e088a67cc74020bbea63b139b22514a08798e3404ddef9519b3cd3a431b302b0a6df25f14374fe1356d6d51c245e485b576625e7ec6f44c42e9a637ed6b0bff5cb6f406b7edee386bfb5a899fa5ae9f24117c4b1fe649286651ece45b3dc2007cb8a163bf0598da48361c55d39a69163fa8fd24cf5f83655d23dca3ad961c62f356208552bb9ed529077096966d670c354e4abc9804f1746c08ca18217c32905e462e36ce3be39e772c180e86039b2783a2ec07a28fb5c55df06f4c52c9de2bcbf6955817183995497cea956ae515d2261898fa051015728e5a8aacaa68ffffffffffffffff".parse().unwrap();
+        let diffie_hellman = DiffieHellmanKeyExchange::new(prime.clone(), BigUint::from(2u64));
+
+        let a: BigUint = "0x8f3c1d2e4b5a69788796a5b4c3d2e1f00f1e2d3c4b5a69788796a5b4c3d2e1f0".parse().unwrap();
+        let b: BigUint = "0x1234567890abcdeffedcba09876543211234567890abcdeffedcba0987654321".parse().unwrap();
+        let (A, B, g_ab) = diffie_hellman.setup(a.clone(), b);
+
+        assert!(A < prime && B < prime);
+        assert_eq!(B.pow_mod(&a, &prime), g_ab);
+    }
 }
@@ -2,3 +2,5 @@ pub mod pohlig_hellman; // Pohlig-Hellman Exponentiation Cipher
 pub mod deffie_hellman_key_exchange;
 pub mod rsa;
 pub mod tpp_pohlig_hellman;
+pub mod pkcs1; // PKCS #1 v1.5 & OAEP padding for RSA
+pub mod signature; // RSA signatures, PKCS #1 v1.5 & PSS
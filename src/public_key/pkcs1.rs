@@ -0,0 +1,299 @@
+/*===================================================================
+ * Author: denosauabh
+ * Description: Byte oriented RSA encryption, textbook & padded with PKCS #1 v1.5 or OAEP
+ * Reference: RFC 8017 (PKCS #1 v2.2), 7.1 RSAES-OAEP & 7.2 RSAES-PKCS1-v1_5
+              https://en.wikipedia.org/wiki/Optimal_asymmetric_encryption_padding
+
+ * Textbook RSA (`encrypt_raw`) is deterministic, the same message always gives the same
+   ciphertext, and malleable, `c · 2^e` decrypts to `2 · m`. Padding adds randomness & a
+   structure decryption checks:
+
+    PKCS #1 v1.5    EM = 00 || 02 || PS || 00 || M              PS: at least 8 random non-zero bytes
+    OAEP            EM = 00 || maskedSeed || maskedDB           DB = lHash || 00..00 || 01 || M
+
+ * OAEP masks the data block with MGF1(seed) and the seed with MGF1(maskedDB). Every padding
+   failure is the same `decryption error`, telling them apart is an oracle (Bleichenbacher, Manger).
+ *===================================================================*/
+
+use rand::Rng;
+
+use crate::hash::HashFunction;
+use crate::public_key::rsa::{RsaPrivateKey, RsaPublicKey};
+use crate::utils::{bignum::BigUint, error::Error, integer::Integer};
+
+/// I2OSP, `x` as exactly `len` big-endian bytes
+pub fn to_octets(x: &BigUint, len: usize) -> Result<Vec<u8>, Error> {
+    let bytes = x.to_bytes_be();
+
+    if bytes.len() > len {
+        return Err(Error::MessageTooLong { max: len, found: bytes.len() });
+    }
+
+    let mut octets = vec![0u8; len - bytes.len()];
+    octets.extend(bytes);
+    Ok(octets)
+}
+
+/// MGF1, `len` bytes of `H(seed || 0) || H(seed || 1) || ...`
+pub fn mgf1<H: HashFunction>(seed: &[u8], len: usize) -> Vec<u8> {
+    let mut mask = Vec::with_capacity(len + H::OUTPUT_LEN);
+
+    let mut counter = 0u32;
+
+    while mask.len() < len {
+        let mut block = seed.to_vec();
+        block.extend_from_slice(&counter.to_be_bytes());
+        mask.extend(H::digest(&block));
+        counter += 1;
+    }
+
+    mask.truncate(len);
+    mask
+}
+
+fn xor(data: &mut [u8], mask: &[u8]) {
+    data.iter_mut().zip(mask).for_each(|(d, m)| *d ^= m);
+}
+
+fn decryption_error() -> Error {
+    Error::MalformedCiphertext("decryption error".to_string())
+}
+
+impl RsaPublicKey<BigUint> {
+    /// length of the modulus in bytes, every ciphertext has exactly this length
+    pub fn size(&self) -> usize {
+        self.n.bits().div_ceil(8) as usize
+    }
+
+    /// textbook `m^e mod n` of the message read as a big-endian number, deterministic & malleable
+    pub fn encrypt_raw(&self, message: &[u8]) -> Result<Vec<u8>, Error> {
+        let m = BigUint::from_bytes_be(message);
+
+        if m >= self.n {
+            return Err(Error::MessageTooLong { max: self.size() - 1, found: message.len() });
+        }
+
+        to_octets(&self.encrypt(m), self.size())
+    }
+
+    /// RSAES-PKCS1-v1_5, messages of at most `size - 11` bytes
+    pub fn encrypt_pkcs1v15<R: Rng + ?Sized>(&self, message: &[u8], rng: &mut R) -> Result<Vec<u8>, Error> {
+        let k = self.size();
+
+        if message.len() + 11 > k {
+            return Err(Error::MessageTooLong { max: k.saturating_sub(11), found: message.len() });
+        }
+
+        let mut em = vec![0x00, 0x02];
+        em.extend((0..k - message.len() - 3).map(|_| rng.gen_range(1..=255u8)));
+        em.push(0x00);
+        em.extend_from_slice(message);
+
+        self.encrypt_raw(&em)
+    }
+
+    /// RSAES-OAEP with the hash `H` & the label (usually empty), messages of at most
+    /// `size - 2 · hLen - 2` bytes
+    pub fn encrypt_oaep<H: HashFunction, R: Rng + ?Sized>(&self, message: &[u8], label: &[u8], rng: &mut R) -> Result<Vec<u8>, Error> {
+        let seed = (0..H::OUTPUT_LEN).map(|_| rng.gen()).collect::<Vec<u8>>();
+        let em = oaep_encode::<H>(message, label, &seed, self.size())?;
+
+        self.encrypt_raw(&em)
+    }
+}
+
+impl RsaPrivateKey<BigUint> {
+    pub fn size(&self) -> usize {
+        self.modulus().bits().div_ceil(8) as usize
+    }
+
+    /// textbook `c^d mod n`, the encoded message of `size` bytes
+    pub fn decrypt_raw(&self, ciphertext: &[u8]) -> Result<Vec<u8>, Error> {
+        let k = self.size();
+
+        if ciphertext.len() != k {
+            return Err(Error::MalformedCiphertext(format!("ciphertext of {} bytes, the modulus has {}", ciphertext.len(), k)));
+        }
+
+        let c = BigUint::from_bytes_be(ciphertext);
+        if &c >= self.modulus() {
+            return Err(Error::MalformedCiphertext("ciphertext is not smaller than the modulus".to_string()));
+        }
+
+        to_octets(&self.decrypt(c), k)
+    }
+
+    pub fn decrypt_pkcs1v15(&self, ciphertext: &[u8]) -> Result<Vec<u8>, Error> {
+        if self.size() < 11 {
+            return Err(decryption_error());
+        }
+
+        let em = self.decrypt_raw(ciphertext)?;
+
+        if em[0] != 0x00 || em[1] != 0x02 {
+            return Err(decryption_error());
+        }
+
+        // the zero byte after at least 8 bytes of padding
+        match em[2..].iter().position(|&b| b == 0x00) {
+            Some(ps) if ps >= 8 => Ok(em[2 + ps + 1..].to_vec()),
+            _ => Err(decryption_error()),
+        }
+    }
+
+    pub fn decrypt_oaep<H: HashFunction>(&self, ciphertext: &[u8], label: &[u8]) -> Result<Vec<u8>, Error> {
+        if self.size() < 2 * H::OUTPUT_LEN + 2 {
+            return Err(decryption_error());
+        }
+
+        let em = self.decrypt_raw(ciphertext)?;
+        oaep_decode::<H>(&em, label)
+    }
+}
+
+/// EME-OAEP encoding into `k` bytes with the given seed of `hLen` bytes
+fn oaep_encode<H: HashFunction>(message: &[u8], label: &[u8], seed: &[u8], k: usize) -> Result<Vec<u8>, Error> {
+    let h_len = H::OUTPUT_LEN;
+
+    if message.len() + 2 * h_len + 2 > k {
+        return Err(Error::MessageTooLong { max: k.saturating_sub(2 * h_len + 2), found: message.len() });
+    }
+
+    // DB = lHash || PS || 01 || M
+    let mut db = H::digest(label);
+    db.resize(k - message.len() - h_len - 2, 0x00);
+    db.push(0x01);
+    db.extend_from_slice(message);
+
+    let mut masked_seed = seed.to_vec();
+    xor(&mut db, &mgf1::<H>(&masked_seed, k - h_len - 1));
+    xor(&mut masked_seed, &mgf1::<H>(&db, h_len));
+
+    let mut em = vec![0x00];
+    em.extend(masked_seed);
+    em.extend(db);
+    Ok(em)
+}
+
+/// EME-OAEP decoding, one error for every kind of failure
+fn oaep_decode<H: HashFunction>(em: &[u8], label: &[u8]) -> Result<Vec<u8>, Error> {
+    let h_len = H::OUTPUT_LEN;
+
+    let (y, rest) = em.split_first().ok_or_else(decryption_error)?;
+    let (masked_seed, masked_db) = rest.split_at(h_len);
+
+    let mut seed = masked_seed.to_vec();
+    xor(&mut seed, &mgf1::<H>(masked_db, h_len));
+
+    let mut db = masked_db.to_vec();
+    xor(&mut db, &mgf1::<H>(&seed, masked_db.len()));
+
+    let (l_hash, rest) = db.split_at(h_len);
+    let one = rest.iter().position(|&b| b != 0x00);
+
+    match one {
+        Some(i) if *y == 0x00 && l_hash == H::digest(label) && rest[i] == 0x01 => Ok(rest[i + 1..].to_vec()),
+        _ => Err(decryption_error()),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::{sha1::Sha1, sha256::Sha256};
+    use crate::public_key::rsa::{tests::key, RSA};
+    use crate::utils::encoding::Encoding;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_raw_is_unsafe() {
+        let rsa = key();
+        let (public_key, private_key) = (rsa.public_key(), rsa.private_key());
+
+        // deterministic, the same message can be recognized
+        let c = public_key.encrypt_raw(b"yes").unwrap();
+        assert_eq!(public_key.encrypt_raw(b"yes").unwrap(), c);
+
+        // malleable, c · 2^e decrypts to 2 · m without knowing m
+        let two_e = public_key.encrypt(BigUint::from(2u64));
+        let forged = to_octets(&(BigUint::from_bytes_be(&c) * two_e % public_key.n.clone()), public_key.size()).unwrap();
+        let m = BigUint::from_bytes_be(&private_key.decrypt_raw(&forged).unwrap());
+        assert_eq!(m, BigUint::from_bytes_be(b"yes") * BigUint::from(2u64));
+
+        assert!(public_key.encrypt_raw(&[0xff; 128]).is_err());
+    }
+
+    #[test]
+    fn test_pkcs1v15() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let rsa = key();
+        let (public_key, private_key) = (rsa.public_key(), rsa.private_key());
+
+        let message = "attack at dawn".as_bytes();
+        let c1 = public_key.encrypt_pkcs1v15(message, &mut rng).unwrap();
+        let c2 = public_key.encrypt_pkcs1v15(message, &mut rng).unwrap();
+
+        assert_eq!(c1.len(), 128);
+        assert_ne!(c1, c2);
+        assert_eq!(private_key.decrypt_pkcs1v15(&c1).unwrap(), message);
+        assert_eq!(private_key.decrypt_pkcs1v15(&c2).unwrap(), message);
+
+        // 117 bytes fit, 118 don't, the empty message does
+        assert!(public_key.encrypt_pkcs1v15(&[b'a'; 117], &mut rng).is_ok());
+        assert_eq!(public_key.encrypt_pkcs1v15(&[b'a'; 118], &mut rng), Err(Error::MessageTooLong { max: 117, found: 118 }));
+        assert!(private_key.decrypt_pkcs1v15(&public_key.encrypt_pkcs1v15(b"", &mut rng).unwrap()).unwrap().is_empty());
+
+        // encrypted by an independent implementation (pyca/cryptography)
+        let c = Encoding::hex_decode("1a9cb9583e32519ca64d6c1f8abde53ba7c9ceff1724eee4d91af16beaf2e03329c865b0c439dd5e7adaeb274ae036870256f0e2433cfb1e05389cb7d24d82cc950cf4e64cef472a35a278f008bb0e619cbd295904ade3f505e58ada78fb371039d36b68f41760af6714aa98394856d85cb45d84e95bf0e058cf0016fd08746f").unwrap();
+        assert_eq!(private_key.decrypt_pkcs1v15(&c).unwrap(), message);
+
+        // not a PKCS #1 v1.5 block, wrong length
+        assert_eq!(private_key.decrypt_pkcs1v15(&public_key.encrypt_raw(message).unwrap()), Err(decryption_error()));
+        assert!(private_key.decrypt_pkcs1v15(&c1[1..]).is_err());
+
+        // a 1-byte modulus (15) has no room for the padding
+        let tiny = RSA::<BigUint>::new(BigUint::from(3u64), BigUint::from(5u64)).unwrap();
+        for c in 0..15u8 {
+            assert_eq!(tiny.private_key().decrypt_pkcs1v15(&[c]), Err(decryption_error()));
+        }
+        assert!(tiny.public_key().encrypt_pkcs1v15(b"", &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_oaep() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let rsa = key();
+        let (public_key, private_key) = (rsa.public_key(), rsa.private_key());
+
+        let message = "attack at dawn".as_bytes();
+
+        let c = public_key.encrypt_oaep::<Sha256, _>(message, b"", &mut rng).unwrap();
+        assert_ne!(public_key.encrypt_oaep::<Sha256, _>(message, b"", &mut rng).unwrap(), c);
+        assert_eq!(private_key.decrypt_oaep::<Sha256>(&c, b"").unwrap(), message);
+
+        // the label is bound to the ciphertext, the hash too
+        let c = public_key.encrypt_oaep::<Sha1, _>(message, b"orders", &mut rng).unwrap();
+        assert_eq!(private_key.decrypt_oaep::<Sha1>(&c, b"orders").unwrap(), message);
+        assert_eq!(private_key.decrypt_oaep::<Sha1>(&c, b"other"), Err(decryption_error()));
+        assert_eq!(private_key.decrypt_oaep::<Sha256>(&c, b"orders"), Err(decryption_error()));
+
+        // encrypted by an independent implementation (pyca/cryptography), SHA-256 & the label "orders"
+        let c = Encoding::hex_decode("1f460805b516a6af4af8bc4bf93e5d1645ac823a018cd7f382ec3ac746f6b15d98fb8fa57f9286dea98637727e7cd84918a26645b9a86e7a571ae29c1927c9ac9f065e3f4cfa5274abab4692f94b255b54f075e0249637ddc1e04a052a3cbf6d5cc94e18354dc2113b0aa94c9541e982efeef284b8e66cbf4fb1769bad31b16e").unwrap();
+        assert_eq!(private_key.decrypt_oaep::<Sha256>(&c, b"orders").unwrap(), message);
+
+        // 128 - 2 · 32 - 2 = 62 bytes with SHA-256
+        assert!(public_key.encrypt_oaep::<Sha256, _>(&[b'a'; 62], b"", &mut rng).is_ok());
+        assert_eq!(public_key.encrypt_oaep::<Sha256, _>(&[b'a'; 63], b"", &mut rng), Err(Error::MessageTooLong { max: 62, found: 63 }));
+    }
+
+    #[test]
+    fn test_mgf1() {
+        // SHA-1 & SHA-256 masks of "foo" & "bar"
+        assert_eq!(mgf1::<Sha1>(b"foo", 3), Encoding::hex_decode("1ac907").unwrap());
+        assert_eq!(mgf1::<Sha1>(b"foo", 5), Encoding::hex_decode("1ac9075cd4").unwrap());
+        assert_eq!(mgf1::<Sha1>(b"bar", 5), Encoding::hex_decode("bc0c655e01").unwrap());
+        assert_eq!(mgf1::<Sha1>(b"bar", 50), Encoding::hex_decode("bc0c655e016bc2931d85a2e675181adcef7f581f76df2739da74faac41627be2f7f415c89e983fd0ce80ced9878641cb4876").unwrap());
+        assert_eq!(mgf1::<Sha256>(b"bar", 50), Encoding::hex_decode("382576a7841021cc28fc4c0948753fb8312090cea942ea4c4e735d10dc724b155f9f6069f289d61daca0cb814502ef04eae1").unwrap());
+    }
+}
@@ -1,78 +1,148 @@
 /*===================================================================
  * Implementation of Pohlig-Hellman Exponention Cipher
  * Author: denosauabh
+
+ * Blocks of two characters are raised to the key `e` modulo a prime `p`, decrypting raises
+   them to `d = e⁻¹ mod p - 1`. A key is only valid when `1 < e < p - 1` & `gcd(e, p - 1) = 1`.
+ * Generic over the integer type, the default modulus (the prime after the charset length
+   repeated twice) fits a `u64`, `with_modulus` takes any larger prime, e.g. a `BigUint`.
  *===================================================================*/
 
 
 use primes::{PrimeSet, Sieve};
-use crate::utils::{char_set::{CharSet, Converter}, mod_arithmetic::ModArithmetic};
+use rand::Rng;
+use crate::utils::{char_set::{CharSet, Converter}, error::Error, integer::{self, Integer}, prime};
+
+/// random exponents drawn by `random_exponent`, for a prime modulus about half of them are valid
+const KEY_ATTEMPTS: usize = 256;
 
-pub struct PohligHellman {
+pub struct PohligHellman<T: Integer = u64> {
     char_set: CharSet,
-    n: u64,
+    n: T,
 }
 
-impl PohligHellman {
-    pub fn new(char_set: CharSet) -> Result<Self, String> {
-        let n = Self::calculate_n(&char_set)?;
+impl<T: Integer> PohligHellman<T> {
+    pub fn new(char_set: CharSet) -> Result<Self, Error> {
+        let n = T::from(default_modulus(&char_set)?);
         Ok(Self { char_set, n })
     }
 
-    fn calculate_n(char_set: &CharSet) -> Result<u64, String> {
-        let n = char_set.len().to_string().repeat(2).parse::<u64>()
-            .map_err(|e| format!("Failed to parse n: {}", e))?;
-        Ok(Self::next_prime(n))
+    /// a caller chosen prime modulus `n`, larger than every block of the charset
+    pub fn with_modulus(char_set: CharSet, n: T) -> Result<Self, Error> {
+        check_modulus(&char_set, &n)?;
+        Ok(Self { char_set, n })
+    }
+
+    /// the prime modulus
+    pub fn modulus(&self) -> T {
+        self.n.clone()
+    }
+
+    /// a random valid key for the modulus
+    pub fn generate_key<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<T, Error> {
+        random_exponent(&self.n, rng)
     }
 
-    fn next_prime(n: u64) -> u64 {
-        Sieve::new().find(n).1
+    /// every ciphertext block is zero padded to the digits of the modulus
+    fn block_width(&self) -> usize {
+        self.n.to_string().len()
     }
 
     /// Encrypt the given text using the provided key.
-    pub fn encrypt(&self, text: &str, key: u64) -> Result<String, String> {
+    pub fn encrypt(&self, text: &str, key: T) -> Result<String, Error> {
+        // a key without an inverse gives a ciphertext nobody can decrypt
+        self.inverse(&key)?;
+
         let mut encrypted = String::new();
 
         let convertor = Converter::new(self.char_set.clone());
         let num_vec = convertor.convert_to_numvec(text)?;
 
         for p in num_vec.iter() {
-            let c = ModArithmetic::pow(*p as u64, key, self.n);
-            encrypted.push_str(&format!("{:0width$}", c, width = convertor.pad_length * 2));
+            let c = T::from(*p as u64).pow_mod(&key, &self.n);
+            encrypted.push_str(&format!("{:0width$}", c, width = self.block_width()));
         }
 
         Ok(encrypted)
     }
 
     // Decrypts the given text using the provided key.
-    pub fn decrypt(&self, text: &str, key: u64) -> Result<String, String> {
-        let charset_len = self.char_set.len();
-        let pad_length = charset_len.to_string().len();
-
-        let d = ModArithmetic::pow_inverse(key as u128, (self.n - 1) as u128)? as u64;
+    pub fn decrypt(&self, text: &str, key: T) -> Result<String, Error> {
+        let d = self.inverse(&key)?;
 
         let num_vec = text.chars()
             .collect::<Vec<char>>()
-            .chunks(pad_length * 2)
+            .chunks(self.block_width())
             .map(|chunk| {
-               let c = chunk.iter().collect::<String>().parse::<u64>().unwrap();
-               ModArithmetic::pow(c, d, self.n) as u32
+               let chunk = chunk.iter().collect::<String>();
+               let c = chunk.parse::<T>()
+                   .map_err(|_| Error::MalformedCiphertext(format!("{:?} is not a number", chunk)))?;
+
+               c.pow_mod(&d, &self.n)
+                   .to_u128()
+                   .and_then(|p| u32::try_from(p).ok())
+                   .ok_or_else(|| Error::MalformedCiphertext(format!("{:?} is not a block", chunk)))
             })
-            .collect::<Vec<u32>>();
+            .collect::<Result<Vec<u32>, Error>>()?;
+
+        Converter::new(self.char_set.clone()).numvec_to_string(num_vec)
+    }
+
+    /// `d` of a valid key, the same rule `generate_key` draws by, e = 1 would leave the text as it is
+    fn inverse(&self, e: &T) -> Result<T, Error> {
+        if !integer::is_valid_exponent(e, &self.n) {
+            return Err(integer::non_invertible(e, &(self.n.clone() - T::one())));
+        }
+
+        integer::pow_inverse(e, &self.n)
+    }
+}
+
+/// the prime after the charset length repeated twice, larger than every block
+pub(crate) fn default_modulus(char_set: &CharSet) -> Result<u64, Error> {
+    let n = char_set.len().to_string().repeat(2).parse::<u64>()
+        .map_err(|_| Error::InvalidCharSet(format!("charset of length {} is too large", char_set.len())))?;
+    Ok(Sieve::new().find(n).1)
+}
+
+/// `n` must be a prime larger than every block of the charset
+pub(crate) fn check_modulus<T: Integer>(char_set: &CharSet, n: &T) -> Result<(), Error> {
+    if !prime::is_probable_prime(n, prime::MILLER_RABIN_ROUNDS, &mut rand::thread_rng()) {
+        return Err(Error::InvalidKey(format!("modulus {} is not a prime", n)));
+    }
+
+    let largest = Converter::new(char_set.clone()).largest_block()?;
+
+    if *n <= T::from(largest) {
+        return Err(Error::InvalidKey(format!("modulus {} is not larger than the block {}", n, largest)));
+    }
+
+    Ok(())
+}
 
-        let decrypted = Converter::new(self.char_set.clone()).numvec_to_string(num_vec);
+/// a random valid exponent for the prime modulus `n`
+pub(crate) fn random_exponent<T: Integer, R: Rng + ?Sized>(n: &T, rng: &mut R) -> Result<T, Error> {
+    for _ in 0..KEY_ATTEMPTS {
+        let e = T::random_below(n, rng);
 
-        Ok(decrypted)
+        if integer::is_valid_exponent(&e, n) {
+            return Ok(e);
+        }
     }
+
+    Err(Error::InvalidKey(format!("no valid exponent for the modulus {} after {} attempts", n, KEY_ATTEMPTS)))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::bignum::BigUint;
+    use rand::{rngs::StdRng, SeedableRng};
 
     #[test]
-    fn test_pohlig_hellman() -> Result<(), String> {
+    fn test_pohlig_hellman() -> Result<(), Error> {
         let charset = CharSet::from_alphabet_lowercase();
-        let pohlig_hellman = PohligHellman::new(charset)?;
+        let pohlig_hellman = PohligHellman::<u64>::new(charset)?;
 
         let text = "powertothepeople";
         let key = 769;
@@ -91,4 +161,62 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_invalid_key() -> Result<(), Error> {
+        let pohlig_hellman = PohligHellman::<u64>::new(CharSet::from_alphabet_lowercase())?;
+
+        // 2632 = 2³ · 7 · 47
+        assert_eq!(pohlig_hellman.modulus(), 2633);
+        assert_eq!(pohlig_hellman.encrypt("power", 94), Err(Error::NonInvertibleKey { value: 94, modulus: 2632 }));
+        assert!(pohlig_hellman.decrypt("1087", 7).is_err());
+
+        // e = 1 is invertible, but leaves every block as it is
+        assert_eq!(pohlig_hellman.encrypt("power", 1), Err(Error::NonInvertibleKey { value: 1, modulus: 2632 }));
+        assert!(pohlig_hellman.decrypt("1087", 1).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_generate_key() -> Result<(), Error> {
+        let mut rng = StdRng::seed_from_u64(7);
+        let pohlig_hellman = PohligHellman::<u64>::new(CharSet::from_alphabet_lowercase())?;
+
+        for _ in 0..20 {
+            let key = pohlig_hellman.generate_key(&mut rng)?;
+            assert!(integer::is_valid_exponent(&key, &2633));
+
+            for text in ["attackatdawn", "flap", "ab", "abe"] {
+                let encrypted = pohlig_hellman.encrypt(text, key)?;
+                assert_eq!(pohlig_hellman.decrypt(&encrypted, key)?, text);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_large_modulus() -> Result<(), Error> {
+        let mut rng = StdRng::seed_from_u64(7);
+
+        // 1024-bit MODP prime of RFC 2409 (Oakley group 2)
+        let p: BigUint = "0xffffffffffffffffc90fdaa22168c234c4c6628b80dc1cd129024e088a67cc74020bbea63b139b22514a08798e3404ddef9519b3cd3a431b302b0a6df25f14374fe1356d6d51c245e485b576625e7ec6f44c42e9a637ed6b0bff5cb6f406b7edee386bfb5a899fa5ae9f24117c4b1fe649286651ece65381ffffffffffffffff".parse()?;
+        let pohlig_hellman = PohligHellman::with_modulus(CharSet::from_alphabet_lowercase(), p.clone())?;
+
+        let key = pohlig_hellman.generate_key(&mut rng)?;
+        let encrypted = pohlig_hellman.encrypt("powertothepeople", key.clone())?;
+
+        // 8 blocks of the 309 digits of the modulus
+        assert_eq!(encrypted.len(), 8 * 309);
+        assert_eq!(pohlig_hellman.decrypt(&encrypted, key)?, "powertothepeople");
+
+        assert!(PohligHellman::with_modulus(CharSet::from_alphabet_lowercase(), 2521u64).is_err());
+
+        // large enough, but 2627 = 37 · 71 & p² isn't a prime either
+        assert_eq!(PohligHellman::with_modulus(CharSet::from_alphabet_lowercase(), 2627u64).err(), Some(Error::InvalidKey("modulus 2627 is not a prime".to_string())));
+        assert!(PohligHellman::with_modulus(CharSet::from_alphabet_lowercase(), &p * &p).is_err());
+
+        Ok(())
+    }
 }
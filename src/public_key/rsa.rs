@@ -2,48 +2,206 @@
  * Simple Implementation of RSA system
  * Author: denosauabh
  * Reference: https://en.wikipedia.org/wiki/RSA_(cryptosystem)
+              RFC 8017 (PKCS #1 v2.2), 3.1 & 3.2
+
+ * Generic over the integer type, `u128` for toy primes, `BigUint` for real sized ones.
+ * The public key (n, e) & private key (n, d & the CRT parameters) are separate types,
+   `RSA` holds both. Decryption uses the Chinese Remainder Theorem:
+
+    m₁ = c^dP mod p,    dP = d mod p - 1
+    m₂ = c^dQ mod q,    dQ = d mod q - 1
+    h  = qInv · (m₁ - m₂) mod p,    qInv = q⁻¹ mod p
+    m  = m₂ + h · q
  *===================================================================*/
 
 
+use rand::Rng;
+
+use crate::utils::{error::Error, integer::{non_invertible, Integer}, prime};
+
+/// the common public exponent, F4 = 2¹⁶ + 1
+pub const DEFAULT_EXPONENT: u64 = 65537;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RsaPublicKey<T: Integer = u128> {
+    pub n: T,
+    pub e: T,
+}
 
-use crate::utils::mod_arithmetic::ModArithmetic;
+#[derive(Clone, PartialEq, Eq)]
+pub struct RsaPrivateKey<T: Integer = u128> {
+    n: T,
+    d: T,
+    p: T,
+    q: T,
+    dp: T,
+    dq: T,
+    q_inv: T,
+}
 
-pub struct RSA {
-    pub n: u128,
-    pub e: u128,
-    d: u128,
+pub struct RSA<T: Integer = u128> {
+    public_key: RsaPublicKey<T>,
+    private_key: RsaPrivateKey<T>,
+}
+
+impl<T: Integer> RsaPublicKey<T> {
+    pub fn encrypt(&self, message: T) -> T {
+        message.pow_mod(&self.e, &self.n)
+    }
 }
 
-impl RSA {
-    pub fn new(p: u128, q: u128) -> Self {
-        let n = p * q;
-        let phi = (p - 1) * (q - 1);
-        let e = Self::generate_e(phi);
-        let d = ModArithmetic::mod_inverse_i128(e as i128, phi as i128).expect("Failed to calculate d") as u128;
+impl<T: Integer> RsaPrivateKey<T> {
+    /// the private key of the primes `p` & `q` and the public exponent `e`
+    pub fn from_primes(p: T, q: T, e: &T) -> Result<Self, Error> {
+        if p < T::from(3) || q < T::from(3) {
+            return Err(Error::InvalidKey("p & q must be odd primes".to_string()));
+        }
+        if p == q {
+            return Err(Error::InvalidKey("p & q must be distinct".to_string()));
+        }
 
-        RSA { n, e, d }
+        let n = p.checked_mul(&q).ok_or(Error::InvalidKey("p * q doesn't fit in the integer type".to_string()))?;
+        let (p_1, q_1) = (p.clone() - T::one(), q.clone() - T::one());
+        let phi = p_1.clone() * q_1.clone();
+
+        if *e < T::from(3) || e.gcd(&phi) != T::one() {
+            return Err(non_invertible(e, &phi));
+        }
+
+        let d = e.inverse_mod(&phi).ok_or_else(|| non_invertible(e, &phi))?;
+        let q_inv = q.inverse_mod(&p).ok_or_else(|| non_invertible(&q, &p))?;
+
+        Ok(Self { dp: d.clone() % p_1, dq: d.clone() % q_1, n, d, p, q, q_inv })
+    }
+
+    pub fn modulus(&self) -> &T {
+        &self.n
     }
 
-    pub fn encrypt(&self, message: u128) -> u128 {
-        ModArithmetic::pow_u128(message, self.e, self.n)
+    pub fn exponent(&self) -> &T {
+        &self.d
     }
 
-    pub fn decrypt(&self, ciphertext: u128) -> u128 {
-        ModArithmetic::pow_u128(ciphertext, self.d, self.n)
+    pub fn primes(&self) -> (&T, &T) {
+        (&self.p, &self.q)
     }
 
-    fn generate_e(phi: u128) -> u128 {
-        // For simplicity, we'll use 65537 as our public exponent
-        // This is a common choice in many RSA implementations
-        let e = 65537;
-        assert!(ModArithmetic::euclidean_gcd_u128(e, phi) == 1, "65537 is not coprime with phi");
-        e
+    /// (dP, dQ, qInv)
+    pub fn crt_parameters(&self) -> (&T, &T, &T) {
+        (&self.dp, &self.dq, &self.q_inv)
+    }
+
+    /// `c^d mod n` through the CRT, two exponentiations of half the size
+    pub fn decrypt(&self, ciphertext: T) -> T {
+        let c = ciphertext % self.n.clone();
+
+        let m1 = c.pow_mod(&self.dp, &self.p);
+        let m2 = c.pow_mod(&self.dq, &self.q);
+
+        // m₁ - m₂ mod p without going negative
+        let diff = (m1 + self.p.clone() - m2.clone() % self.p.clone()) % self.p.clone();
+        let h = self.q_inv.mul_mod(&diff, &self.p);
+
+        m2 + h * self.q.clone()
+    }
+
+    /// `c^d mod n` without the CRT
+    pub fn decrypt_without_crt(&self, ciphertext: T) -> T {
+        ciphertext.pow_mod(&self.d, &self.n)
+    }
+}
+
+/// the private exponent stays out of debug output
+impl<T: Integer> std::fmt::Debug for RsaPrivateKey<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RsaPrivateKey").field("n", &self.n).finish_non_exhaustive()
+    }
+}
+
+impl<T: Integer> RSA<T> {
+    /// keys of the primes `p` & `q` with `e = 65537`
+    pub fn new(p: T, q: T) -> Result<Self, Error> {
+        Self::with_exponent(p, q, T::from(DEFAULT_EXPONENT))
+    }
+
+    /// keys of the primes `p` & `q` with the public exponent `e`, `gcd(e, φ(n)) = 1`
+    pub fn with_exponent(p: T, q: T, e: T) -> Result<Self, Error> {
+        let private_key = RsaPrivateKey::from_primes(p, q, &e)?;
+        let public_key = RsaPublicKey { n: private_key.n.clone(), e };
+
+        Ok(RSA { public_key, private_key })
+    }
+
+    /// keys with a modulus of exactly `bits` bits from two random probable primes, `e = 65537`
+    pub fn generate<R: Rng + ?Sized>(bits: u64, rng: &mut R) -> Result<Self, Error> {
+        if bits < 16 || !bits.is_multiple_of(2) {
+            return Err(Error::Unsupported(format!("modulus of {} bits, must be even & at least 16", bits)));
+        }
+        if let Some(max) = T::MAX_BITS.filter(|&max| bits > max) {
+            return Err(Error::Unsupported(format!("modulus of {} bits, the integer type holds {}", bits, max)));
+        }
+
+        let e = T::from(DEFAULT_EXPONENT);
+
+        // a prime with gcd(e, p - 1) = 1, so e is invertible modulo φ(n) = (p - 1)(q - 1)
+        let mut prime = || loop {
+            let p = prime::random_prime::<T, R>(bits / 2, rng)?;
+
+            if e.gcd(&(p.clone() - T::one())) == T::one() {
+                return Ok::<T, Error>(p);
+            }
+        };
+
+        let p = prime()?;
+        let q = loop {
+            let q = prime()?;
+
+            if q != p {
+                break q;
+            }
+        };
+
+        // the larger prime first, the CRT recombination only needs qInv
+        let (p, q) = if p > q { (p, q) } else { (q, p) };
+
+        Self::with_exponent(p, q, e)
+    }
+
+    pub fn public_key(&self) -> &RsaPublicKey<T> {
+        &self.public_key
+    }
+
+    pub fn private_key(&self) -> &RsaPrivateKey<T> {
+        &self.private_key
+    }
+
+    /// the keys apart, the public one can be handed out on its own
+    pub fn into_keys(self) -> (RsaPublicKey<T>, RsaPrivateKey<T>) {
+        (self.public_key, self.private_key)
+    }
+
+    pub fn encrypt(&self, message: T) -> T {
+        self.public_key.encrypt(message)
+    }
+
+    pub fn decrypt(&self, ciphertext: T) -> T {
+        self.private_key.decrypt(ciphertext)
     }
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
+    use crate::utils::bignum::BigUint;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    /// two 512-bit primes, a 1024-bit modulus, the key of the PKCS #1 test vectors
+    pub(crate) fn key() -> RSA<BigUint> {
+        let p: BigUint = "0xd17f655bf27c8b16d35462c905cc04a26f37e2a67fa9c0ce0dced472394a0df743fe7f929e378efdb368eddff453cf007af6d948e0ade757371f8a711e278f6b".parse().unwrap();
+        let q: BigUint = "0xc6d92b6fee7414d1358ce1546fb62987530b90bd15e0f14963a5e2635adb69347ec0c01b2ab1763fd8ac1a592fb22757463a982425bb97a3a437c5bf86d03f2f".parse().unwrap();
+
+        RSA::new(p, q).unwrap()
+    }
 
     #[test]
     fn test_rsa() {
@@ -51,12 +209,85 @@ mod tests {
         let p: u128 = 61;
         let q: u128 = 53;
 
-        let rsa = RSA::new(p, q);
+        let rsa = RSA::new(p, q).unwrap();
 
         let message: u128 = 2022;
         let encrypted = rsa.encrypt(message);
         let decrypted = rsa.decrypt(encrypted);
 
         assert_eq!(message, decrypted);
+
+        // phi = 65537 * 2, which isn't coprime with e = 65537
+        assert!(RSA::new(65538u128, 3).is_err());
+
+        // n doesn't fit in a u128
+        assert!(RSA::new(u128::MAX - 158, 3).is_err());
+
+        // p = q, n would be a square
+        assert!(RSA::new(61u128, 61).is_err());
+    }
+
+    #[test]
+    fn test_crt_parameters() {
+        // the textbook example, n = 3233, e = 17, d = 2753
+        let rsa = RSA::with_exponent(61u128, 53, 17).unwrap();
+        let private_key = rsa.private_key();
+
+        assert_eq!(*private_key.exponent(), 2753);
+        assert_eq!(private_key.crt_parameters(), (&53, &49, &38));
+        assert_eq!(rsa.encrypt(65), 2790);
+
+        for c in 0..3233 {
+            assert_eq!(private_key.decrypt(c), private_key.decrypt_without_crt(c));
+        }
+
+        assert!(RSA::with_exponent(61u128, 53, 3).is_err());
+        assert!(RSA::with_exponent(61u128, 53, 1).is_err());
+    }
+
+    #[test]
+    fn test_rsa_1024() {
+        let rsa = key();
+        let (p, q) = rsa.private_key().primes();
+        assert_eq!(rsa.public_key().n, p * q);
+        assert_eq!(rsa.public_key().n.bits(), 1024);
+
+        let message: BigUint = "0xdeadbeefcafebabe0123456789abcdef".parse().unwrap();
+        let encrypted = rsa.encrypt(message.clone());
+
+        assert_ne!(encrypted, message);
+        assert_eq!(rsa.decrypt(encrypted.clone()), message);
+        assert_eq!(rsa.private_key().decrypt_without_crt(encrypted), message);
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut rng = StdRng::seed_from_u64(7);
+
+        let rsa = RSA::<u128>::generate(64, &mut rng).unwrap();
+        assert_eq!(rsa.public_key().n.bits(), 64);
+        assert_eq!(rsa.decrypt(rsa.encrypt(0xdeadbeef)), 0xdeadbeef);
+
+        let (public_key, private_key) = RSA::<BigUint>::generate(1024, &mut rng).unwrap().into_keys();
+        let (p, q) = private_key.primes();
+
+        assert_eq!(public_key.n.bits(), 1024);
+        assert_eq!(&(p * q), private_key.modulus());
+        assert_eq!(public_key.e, BigUint::from(65537u64));
+
+        // e · d = 1 mod φ(n)
+        let phi = (p - &BigUint::one()) * (q - &BigUint::one());
+        assert_eq!(public_key.e.mul_mod(private_key.exponent(), &phi), BigUint::one());
+
+        let message = BigUint::from(0x48656c6c6f2c2052534121u128);
+        assert_eq!(private_key.decrypt(public_key.encrypt(message.clone())), message);
+
+        assert!(RSA::<u128>::generate(15, &mut rng).is_err());
+        assert!(RSA::<u128>::generate(8, &mut rng).is_err());
+
+        // the modulus or even its primes don't fit
+        assert_eq!(RSA::<u128>::generate(130, &mut rng).err(), Some(Error::Unsupported("modulus of 130 bits, the integer type holds 128".to_string())));
+        assert!(RSA::<u128>::generate(258, &mut rng).is_err());
+        assert!(RSA::<u64>::generate(128, &mut rng).is_err());
     }
 }
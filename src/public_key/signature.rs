@@ -0,0 +1,270 @@
+/*===================================================================
+ * Author: denosauabh
+ * Description: RSA digital signatures, PKCS #1 v1.5 & PSS
+ * Reference: RFC 8017 (PKCS #1 v2.2), 8.1 RSASSA-PSS, 8.2 RSASSA-PKCS1-v1_5 & 9 (EMSA encodings)
+              https://en.wikipedia.org/wiki/Probabilistic_signature_scheme
+
+ * Signing is the private key operation on the encoded hash of the message, `s = EM^d mod n`,
+   verifying is the public one, `EM = s^e mod n`, and checking the encoding:
+
+    PKCS #1 v1.5    EM = 00 || 01 || FF..FF || 00 || DigestInfo(H(M))     deterministic
+    PSS             EM = maskedDB || H' || BC                            random salt
+                    H' = H(00 x 8 || H(M) || salt),  DB = 00..00 || 01 || salt
+
+ * Without the encoding (signing H(M) or M directly) signatures multiply, `s₁ · s₂` signs
+   `m₁ · m₂`, the padding is what makes them unforgeable.
+ *===================================================================*/
+
+use rand::Rng;
+
+use crate::hash::HashFunction;
+use crate::public_key::pkcs1::{mgf1, to_octets};
+use crate::public_key::rsa::{RsaPrivateKey, RsaPublicKey, RSA};
+use crate::utils::{bignum::BigUint, error::Error, integer::Integer};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SignatureScheme {
+    /// RSASSA-PKCS1-v1_5, the same message always gets the same signature
+    Pkcs1v15,
+
+    /// RSASSA-PSS with a random salt of `salt_len` bytes, usually the length of the hash
+    Pss { salt_len: usize },
+}
+
+impl RsaPrivateKey<BigUint> {
+    /// signature of `size` bytes over the message hashed with `H`, the rng is only used by PSS
+    pub fn sign<H: HashFunction, R: Rng + ?Sized>(&self, message: &[u8], scheme: SignatureScheme, rng: &mut R) -> Result<Vec<u8>, Error> {
+        let mod_bits = self.modulus().bits() as usize;
+
+        let em = match scheme {
+            SignatureScheme::Pkcs1v15 => pkcs1v15_encode::<H>(message, self.size())?,
+            SignatureScheme::Pss { salt_len } => {
+                let salt = (0..salt_len).map(|_| rng.gen()).collect::<Vec<u8>>();
+                pss_encode::<H>(message, &salt, mod_bits - 1)?
+            },
+        };
+
+        self.sign_encoded(&em)
+    }
+
+    /// `EM^d mod n`
+    fn sign_encoded(&self, em: &[u8]) -> Result<Vec<u8>, Error> {
+        to_octets(&self.decrypt(BigUint::from_bytes_be(em)), self.size())
+    }
+}
+
+impl RsaPublicKey<BigUint> {
+    /// `Ok` only if `signature` was made by the private key over the message hashed with `H`
+    pub fn verify<H: HashFunction>(&self, message: &[u8], signature: &[u8], scheme: SignatureScheme) -> Result<(), Error> {
+        let k = self.size();
+        let s = BigUint::from_bytes_be(signature);
+
+        if signature.len() != k || s >= self.n {
+            return Err(Error::InvalidSignature);
+        }
+
+        let m = self.encrypt(s);
+        let mod_bits = self.n.bits() as usize;
+
+        let valid = match scheme {
+            SignatureScheme::Pkcs1v15 => {
+                let em = to_octets(&m, k)?;
+                pkcs1v15_encode::<H>(message, k).is_ok_and(|expected| expected == em)
+            },
+            SignatureScheme::Pss { salt_len } => {
+                // the encoded message has mod_bits - 1 bits, a byte less when that's a multiple of 8
+                let em_len = (mod_bits - 1).div_ceil(8);
+                to_octets(&m, em_len).is_ok_and(|em| pss_verify::<H>(message, &em, salt_len, mod_bits - 1))
+            },
+        };
+
+        valid.then_some(()).ok_or(Error::InvalidSignature)
+    }
+}
+
+impl RSA<BigUint> {
+    pub fn sign<H: HashFunction, R: Rng + ?Sized>(&self, message: &[u8], scheme: SignatureScheme, rng: &mut R) -> Result<Vec<u8>, Error> {
+        self.private_key().sign::<H, R>(message, scheme, rng)
+    }
+
+    pub fn verify<H: HashFunction>(&self, message: &[u8], signature: &[u8], scheme: SignatureScheme) -> Result<(), Error> {
+        self.public_key().verify::<H>(message, signature, scheme)
+    }
+}
+
+/// EMSA-PKCS1-v1_5 into `em_len` bytes
+fn pkcs1v15_encode<H: HashFunction>(message: &[u8], em_len: usize) -> Result<Vec<u8>, Error> {
+    let mut t = H::DIGEST_INFO_PREFIX.to_vec();
+    t.extend(H::digest(message));
+
+    // at least 8 bytes of FF
+    if em_len < t.len() + 11 {
+        return Err(Error::MessageTooLong { max: em_len.saturating_sub(11), found: t.len() });
+    }
+
+    let mut em = vec![0x00, 0x01];
+    em.resize(em_len - t.len() - 1, 0xff);
+    em.push(0x00);
+    em.extend(t);
+    Ok(em)
+}
+
+/// EMSA-PSS encoding of `em_bits` bits with the given salt
+fn pss_encode<H: HashFunction>(message: &[u8], salt: &[u8], em_bits: usize) -> Result<Vec<u8>, Error> {
+    let (h_len, em_len) = (H::OUTPUT_LEN, em_bits.div_ceil(8));
+
+    if em_len < h_len + salt.len() + 2 {
+        return Err(Error::MessageTooLong { max: em_len.saturating_sub(h_len + 2), found: salt.len() });
+    }
+
+    let h = pss_hash::<H>(message, salt);
+
+    // DB = PS || 01 || salt
+    let mut db = vec![0x00; em_len - salt.len() - h_len - 2];
+    db.push(0x01);
+    db.extend_from_slice(salt);
+
+    db.iter_mut().zip(mgf1::<H>(&h, em_len - h_len - 1)).for_each(|(d, m)| *d ^= m);
+    db[0] &= 0xff >> (8 * em_len - em_bits);
+
+    let mut em = db;
+    em.extend(h);
+    em.push(0xbc);
+    Ok(em)
+}
+
+/// EMSA-PSS verification, the salt is recovered from the encoded message
+fn pss_verify<H: HashFunction>(message: &[u8], em: &[u8], salt_len: usize, em_bits: usize) -> bool {
+    let (h_len, em_len) = (H::OUTPUT_LEN, em_bits.div_ceil(8));
+    let top_mask = 0xff >> (8 * em_len - em_bits);
+
+    if em.len() != em_len || em_len < h_len + salt_len + 2 || em[em_len - 1] != 0xbc {
+        return false;
+    }
+
+    let (masked_db, h) = em[..em_len - 1].split_at(em_len - h_len - 1);
+
+    // the bits above em_bits must be zero
+    if masked_db[0] & !top_mask != 0 {
+        return false;
+    }
+
+    let mut db = masked_db.to_vec();
+    db.iter_mut().zip(mgf1::<H>(h, masked_db.len())).for_each(|(d, m)| *d ^= m);
+    db[0] &= top_mask;
+
+    // PS zeros, 01, then the salt
+    let ps_len = em_len - h_len - salt_len - 2;
+    if db[..ps_len].iter().any(|&b| b != 0x00) || db[ps_len] != 0x01 {
+        return false;
+    }
+
+    pss_hash::<H>(message, &db[ps_len + 1..]) == h
+}
+
+/// `H(00 x 8 || H(M) || salt)`
+fn pss_hash<H: HashFunction>(message: &[u8], salt: &[u8]) -> Vec<u8> {
+    let mut m = vec![0x00; 8];
+    m.extend(H::digest(message));
+    m.extend_from_slice(salt);
+
+    H::digest(&m)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::{sha1::Sha1, sha256::Sha256};
+    use crate::public_key::rsa::tests::key;
+    use crate::utils::{encoding::Encoding, prime};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    const MESSAGE: &[u8] = b"I owe Bob 100 dollars";
+
+    #[test]
+    fn test_pkcs1v15() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let rsa = key();
+
+        // signed by an independent implementation (pyca/cryptography), deterministic so byte for byte the same
+        let expected = Encoding::hex_decode("3d63e36f8200c8e5bad1378ce0c6202d1f9e04fa42a5b938322aaecaa052bd91169f53fb15240ed8c4111723fc698229b167872a3e792288d17c0fa4fdf0d9ad340df7e02c634abd70456a5eda508845f3f0eafef3a0157cf8889c336988455afad3cd497f677a8e8a30c6bfdf38f70ce50c9555d420fa4635f95c829eba5416").unwrap();
+        let signature = rsa.sign::<Sha256, _>(MESSAGE, SignatureScheme::Pkcs1v15, &mut rng).unwrap();
+        assert_eq!(signature, expected);
+        assert_eq!(rsa.verify::<Sha256>(MESSAGE, &signature, SignatureScheme::Pkcs1v15), Ok(()));
+
+        let expected = Encoding::hex_decode("9cb7bbefc9984d1990ef684da481d760e7c839b1c98b84cf208975cc850e61a45369cf0f0c561c74059ffc2d3c79b36dfa50004342de052fbd8a8fa9aeb4a22edb4d9b30cb02ea0649b84c3edd6f658ad712094e180b96c3872e74f322fabe11bedbe632a91c290dc1f377e252ca0ba2c34c62aa3d6a95793f206c3c8b7eb079").unwrap();
+        assert_eq!(rsa.sign::<Sha1, _>(MESSAGE, SignatureScheme::Pkcs1v15, &mut rng).unwrap(), expected);
+
+        // another message, another hash, a flipped bit
+        assert_eq!(rsa.verify::<Sha256>(b"I owe Bob 900 dollars", &signature, SignatureScheme::Pkcs1v15), Err(Error::InvalidSignature));
+        assert!(rsa.verify::<Sha1>(MESSAGE, &signature, SignatureScheme::Pkcs1v15).is_err());
+
+        let mut tampered = signature.clone();
+        tampered[64] ^= 1;
+        assert!(rsa.verify::<Sha256>(MESSAGE, &tampered, SignatureScheme::Pkcs1v15).is_err());
+        assert!(rsa.verify::<Sha256>(MESSAGE, &signature[1..], SignatureScheme::Pkcs1v15).is_err());
+    }
+
+    #[test]
+    fn test_pss() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let rsa = key();
+        let scheme = SignatureScheme::Pss { salt_len: 32 };
+
+        // with the salt 00 01 .. 1f, checked against an independent implementation (pyca/cryptography)
+        let salt = (0..32).collect::<Vec<u8>>();
+        let em = pss_encode::<Sha256>(MESSAGE, &salt, 1023).unwrap();
+        let expected = Encoding::hex_decode("3d34c9a4fea240dcd99e24cabbb3399b2dfc2e446cc6773067981623bbc52209530f025bea3599839dd2c0a70bc60e0ca0c24bcd0fb90dfe201531e4a9dbc6da7d753b1a9c2398dd03fb39a0bcd612fdb5252eb98350e35b8558c40ecffae903b1d55e6596ee05eb98df04d4006e41a8357fcd68dfeed3a671166cefe6f15537").unwrap();
+        assert_eq!(rsa.private_key().sign_encoded(&em).unwrap(), expected);
+        assert_eq!(rsa.verify::<Sha256>(MESSAGE, &expected, scheme), Ok(()));
+
+        // a random salt every time, both verify
+        let s1 = rsa.sign::<Sha256, _>(MESSAGE, scheme, &mut rng).unwrap();
+        let s2 = rsa.sign::<Sha256, _>(MESSAGE, scheme, &mut rng).unwrap();
+        assert_ne!(s1, s2);
+        assert!(rsa.verify::<Sha256>(MESSAGE, &s1, scheme).is_ok());
+        assert!(rsa.verify::<Sha256>(MESSAGE, &s2, scheme).is_ok());
+
+        // signed by pyca/cryptography with SHA-1 & a 20 byte salt
+        let signature = Encoding::hex_decode("3f3682fe058839ff55e06ab9bcf1c295eec95746a9a1a2d6e37175931f50930b082058cfa767dfcf9d8e745c608a6be4055b3ed45117ae5c8dc4bb91c352acdca769de5be542f110f8d8f2565f949e86880e95dc1e6c4f1b7839d630a22b11b8d93390cea488e8af146130e30a5ed337127e79a84e775fad13299eb8e12d1dc4").unwrap();
+        assert_eq!(rsa.verify::<Sha1>(MESSAGE, &signature, SignatureScheme::Pss { salt_len: 20 }), Ok(()));
+        assert!(rsa.verify::<Sha1>(MESSAGE, &signature, SignatureScheme::Pss { salt_len: 16 }).is_err());
+
+        assert!(rsa.verify::<Sha256>(b"I owe Bob 900 dollars", &s1, scheme).is_err());
+        assert!(rsa.verify::<Sha256>(MESSAGE, &s1, SignatureScheme::Pkcs1v15).is_err());
+
+        // a salt that leaves no room in the 128 byte block
+        assert!(rsa.sign::<Sha256, _>(MESSAGE, SignatureScheme::Pss { salt_len: 95 }, &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_unpadded_signatures_multiply() {
+        // the reason for the encoding, raw signatures of m₁ & m₂ give one of m₁ · m₂ for free
+        let rsa = key();
+        let (public_key, private_key) = (rsa.public_key(), rsa.private_key());
+
+        let (m1, m2) = (BigUint::from(1000u64), BigUint::from(3u64));
+        let (s1, s2) = (private_key.decrypt(m1.clone()), private_key.decrypt(m2.clone()));
+
+        let forged = s1.mul_mod(&s2, &public_key.n);
+        assert_eq!(public_key.encrypt(forged), m1 * m2);
+    }
+
+    #[test]
+    fn test_odd_modulus_length() {
+        // a 1025-bit modulus, the PSS block has 1024 bits, a byte less than the signature
+        let mut rng = StdRng::seed_from_u64(7);
+        let p = prime::random_prime::<BigUint, _>(513, &mut rng).unwrap();
+        let q = prime::random_prime::<BigUint, _>(512, &mut rng).unwrap();
+
+        let rsa = RSA::new(p, q).unwrap();
+        assert_eq!(rsa.public_key().n.bits(), 1025);
+
+        for scheme in [SignatureScheme::Pkcs1v15, SignatureScheme::Pss { salt_len: 32 }] {
+            let signature = rsa.sign::<Sha256, _>(b"attack at dawn", scheme, &mut rng).unwrap();
+            assert_eq!(signature.len(), 129);
+            assert!(rsa.verify::<Sha256>(b"attack at dawn", &signature, scheme).is_ok());
+        }
+    }
+}
@@ -1,106 +1,201 @@
-use primes::{PrimeSet, Sieve};
+/*===================================================================
+ * Author: denosauabh
+ * Description: Shamir's Three-Pass Protocol on Pohlig-Hellman exponentiation
+ * Reference: https://en.wikipedia.org/wiki/Three-pass_protocol
 
-use crate::utils::{char_set::{CharSet, Converter}, mod_arithmetic::ModArithmetic};
+ * Alice & Bob agree on a prime modulus p only, each one keeps a secret exponent
+   (a, b with gcd(a, p - 1) = gcd(b, p - 1) = 1) that never leaves their side:
 
+    1. Alice -> Bob     m^a             Alice locks the message
+    2. Bob -> Alice     m^(a·b)         Bob adds his lock
+    3. Alice -> Bob     m^b             Alice removes hers, a⁻¹ mod p - 1
+    4. Bob              m               Bob removes his, b⁻¹ mod p - 1
 
-struct TPPPohigHellman {
+ * The message is split into blocks of two characters (see `Converter`), every block
+   must be smaller than the modulus.
+ * Generic over the integer type like `PohligHellman`, the modulus checks are shared with it.
+ *===================================================================*/
+
+use rand::Rng;
+
+use crate::public_key::pohlig_hellman;
+use crate::utils::{char_set::{CharSet, Converter}, error::Error, integer::{self, non_invertible, Integer}};
+
+
+/// the public parameters both sides agree on
+#[derive(Clone, Debug)]
+pub struct TPPPohigHellman<T: Integer = u64> {
     char_set: CharSet,
+    m: T, // prime modulus
+}
 
-    a: u64, // private key of Alice
-    b: u64, // private key of Bob
+/// the sending side, holds Alice's secret exponent only
+pub struct Alice<T: Integer = u64> {
+    params: TPPPohigHellman<T>,
+    a: T,
+    a_inv: T,
+}
 
-    m: u64, // modulus
+/// the receiving side, holds Bob's secret exponent only
+pub struct Bob<T: Integer = u64> {
+    params: TPPPohigHellman<T>,
+    b: T,
+    b_inv: T,
 }
 
 
-impl TPPPohigHellman {
-    pub fn new(char_set: CharSet, a: u64, b: u64, m: u64) -> Result<Self, String> {
-        // let m = Self::calculate_n(&char_set)?;
-        Ok(Self { char_set, a, b, m })
+impl<T: Integer> TPPPohigHellman<T> {
+    /// `m` must be a prime larger than every block of the charset
+    pub fn new(char_set: CharSet, m: T) -> Result<Self, Error> {
+        pohlig_hellman::check_modulus(&char_set, &m)?;
+        Ok(Self { char_set, m })
     }
 
-    fn calculate_n(char_set: &CharSet) -> Result<u64, String> {
-        let n = char_set.len().to_string().repeat(2).parse::<u64>()
-            .map_err(|e| format!("Failed to parse n: {}", e))?;
-        Ok(Self::next_prime(n))
+    /// same as `new`, with the modulus derived from the charset (the prime after its length repeated twice)
+    pub fn from_char_set(char_set: CharSet) -> Result<Self, Error> {
+        let m = T::from(pohlig_hellman::default_modulus(&char_set)?);
+        Self::new(char_set, m)
     }
 
-    fn next_prime(n: u64) -> u64 {
-        Sieve::new().find(n).1
+    pub fn modulus(&self) -> T {
+        self.m.clone()
     }
 
-    /// send message from Alice to Bob
-    pub fn send_message(&self, message: &str) -> Result<String, String> {
-        
-        println!("MODULUS: {:?}", self.m);
-        
-        // convert the message to a vector of numbers
-        let convertor = Converter::new(self.char_set.clone());
-        let num_vec = convertor.convert_to_numvec(message)?;
-
-        println!("Message: {:?}", num_vec);
-
-        // 1. Alice encrypts the message with her private key
-        let mut alice_encrypted_message = Vec::new();
-        for n in num_vec.iter() {
-            let c = ModArithmetic::pow(*n as u64, self.a, self.m);
-            alice_encrypted_message.push(c);
-        };
-        println!("Alice encrypted message: {:?}", alice_encrypted_message);
-
-        // 2. Bob encrypts the message with his private key
-        let mut bob_encrypted_message = Vec::new();
-        for c in alice_encrypted_message.iter() {
-            let c = ModArithmetic::pow(*c, self.b, self.m);
-            bob_encrypted_message.push(c);
-        };
-
-        println!("Bob encrypted message: {:?}", bob_encrypted_message);
+    /// a random valid secret exponent for the modulus
+    pub fn generate_exponent<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<T, Error> {
+        pohlig_hellman::random_exponent(&self.m, rng)
+    }
 
-        // 3. Alice decrypts the message with her private key
-        let mut alice_decrypted_message = Vec::new();
-        let a_inv = ModArithmetic::pow_inverse(self.a as u128, self.m as u128).unwrap() as u64;
-        println!("A inverse: {:?}", a_inv);
+    /// Alice with her secret exponent `a`
+    pub fn alice(&self, a: T) -> Result<Alice<T>, Error> {
+        let a_inv = self.inverse(&a)?;
+        Ok(Alice { params: self.clone(), a, a_inv })
+    }
 
-        for c in bob_encrypted_message.iter() {
-            let c = ModArithmetic::pow(*c, a_inv, self.m);
-            alice_decrypted_message.push(c);
-        };
+    /// Bob with his secret exponent `b`
+    pub fn bob(&self, b: T) -> Result<Bob<T>, Error> {
+        let b_inv = self.inverse(&b)?;
+        Ok(Bob { params: self.clone(), b, b_inv })
+    }
 
-        println!("Alice decrypted message: {:?}", alice_decrypted_message);
+    fn inverse(&self, e: &T) -> Result<T, Error> {
+        if !integer::is_valid_exponent(e, &self.m) {
+            return Err(non_invertible(e, &(self.m.clone() - T::one())));
+        }
 
+        integer::pow_inverse(e, &self.m)
+    }
 
-        // 4. Bob decrypts the message with his private key
-        let mut bob_decrypted_message: Vec<u32> = Vec::new();
-        let b_inv = ModArithmetic::pow_inverse(self.b as u128, self.m as u128).unwrap() as u64;
-        println!("B inverse: {:?}", b_inv);
+    fn pow(&self, blocks: &[T], e: &T) -> Vec<T> {
+        blocks.iter().map(|x| x.pow_mod(e, &self.m)).collect()
+    }
+}
 
-        for c in alice_decrypted_message.iter() {
-            let c = ModArithmetic::pow(*c, b_inv, self.m);
-            bob_decrypted_message.push(c as u32);
-        };
+impl<T: Integer> Alice<T> {
+    /// 1. the message locked with `a`
+    pub fn send(&self, message: &str) -> Result<Vec<T>, Error> {
+        let blocks = Converter::new(self.params.char_set.clone())
+            .convert_to_numvec(message)?
+            .into_iter()
+            .map(|x| T::from(x as u64))
+            .collect::<Vec<T>>();
 
-        println!("Bob decrypted message: {:?}", bob_decrypted_message);
+        Ok(self.params.pow(&blocks, &self.a))
+    }
 
-        // so Bob, finally gets the plaintext
-        let decrypted_message = convertor.numvec_to_string(bob_decrypted_message);
+    /// 3. Alice's lock removed from Bob's reply, still locked by Bob
+    pub fn unlock(&self, reply: &[T]) -> Vec<T> {
+        self.params.pow(reply, &self.a_inv)
+    }
+}
 
-        Ok(decrypted_message)
+impl<T: Integer> Bob<T> {
+    /// 2. Alice's message locked with `b` as well
+    pub fn lock(&self, message: &[T]) -> Vec<T> {
+        self.params.pow(message, &self.b)
     }
 
+    /// 4. Bob's lock removed, the message
+    pub fn receive(&self, message: &[T]) -> Result<String, Error> {
+        let blocks = self.params
+            .pow(message, &self.b_inv)
+            .into_iter()
+            .map(|x| x.to_u128()
+                .and_then(|p| u32::try_from(p).ok())
+                .ok_or_else(|| Error::MalformedCiphertext(format!("{} is not a block", x))))
+            .collect::<Result<Vec<u32>, Error>>()?;
+
+        Converter::new(self.params.char_set.clone()).numvec_to_string(blocks)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::bignum::BigUint;
+    use rand::{rngs::StdRng, SeedableRng};
 
     #[test]
     fn test_send_message() {
+        let tpp = TPPPohigHellman::new(CharSet::from_alphabet_lowercase(), 2819u64).unwrap();
+        let alice = tpp.alice(113).unwrap();
+        let bob = tpp.bob(87).unwrap();
+
+        let message = "tellmethreetimes";
+
+        let first = alice.send(message).unwrap();
+        let second = bob.lock(&first);
+        let third = alice.unlock(&second);
+
+        // every pass is locked by somebody
+        assert_ne!(first, vec![1904, 1111, 1204, 1907, 1704, 419, 812, 418]);
+        assert_ne!(third, first);
+
+        assert_eq!(bob.receive(&third).unwrap(), message);
+    }
+
+    #[test]
+    fn test_generated_exponents() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let tpp = TPPPohigHellman::<u64>::from_char_set(CharSet::from_alphabet_lowercase()).unwrap();
+        assert_eq!(tpp.modulus(), 2633);
+
+        for message in ["attackatdawn", "abe", "odd", "flap", "ab"] {
+            let alice = tpp.alice(tpp.generate_exponent(&mut rng).unwrap()).unwrap();
+            let bob = tpp.bob(tpp.generate_exponent(&mut rng).unwrap()).unwrap();
+
+            let third = alice.unlock(&bob.lock(&alice.send(message).unwrap()));
+            assert_eq!(bob.receive(&third).unwrap(), message);
+        }
+    }
+
+    #[test]
+    fn test_large_modulus() {
+        let mut rng = StdRng::seed_from_u64(7);
+
+        // 1024-bit MODP prime of RFC 2409 (Oakley group 2)
+        let p: BigUint = "0xffffffffffffffffc90fdaa22168c234c4c6628b80dc1cd129024e088a67cc74020bbea63b139b22514a08798e3404ddef9519b3cd3a431b302b0a6df25f14374fe1356d6d51c245e485b576625e7ec6f44c42e9a637ed6b0bff5cb6f406b7edee386bfb5a899fa5ae9f24117c4b1fe649286651ece65381ffffffffffffffff".parse().unwrap();
+        let tpp = TPPPohigHellman::new(CharSet::from_alphabet_lowercase(), p).unwrap();
+
+        let alice = tpp.alice(tpp.generate_exponent(&mut rng).unwrap()).unwrap();
+        let bob = tpp.bob(tpp.generate_exponent(&mut rng).unwrap()).unwrap();
+
+        let third = alice.unlock(&bob.lock(&alice.send("tellmethreetimes").unwrap()));
+        assert_eq!(bob.receive(&third).unwrap(), "tellmethreetimes");
+    }
+
+    #[test]
+    fn test_invalid() {
         let char_set = CharSet::from_alphabet_lowercase();
-        let tpp_pohlig_hellman = TPPPohigHellman::new(char_set, 113, 87, 2819).expect("Failed to create TPPPohigHellman");
 
-        // let message = "tellmethreetimes";
-        let message = "ufmmnfuisffujnft";
-        assert_eq!(tpp_pohlig_hellman.send_message(message).unwrap(), message);
+        // not a prime, too small for the block 2625
+        assert!(TPPPohigHellman::new(char_set.clone(), 2820u64).is_err());
+        assert!(TPPPohigHellman::new(char_set.clone(), 2521u64).is_err());
+
+        // 2818 = 2 · 1409
+        let tpp = TPPPohigHellman::new(char_set, 2819u64).unwrap();
+        assert_eq!(tpp.alice(1409).err(), Some(Error::NonInvertibleKey { value: 1409, modulus: 2818 }));
+        assert!(tpp.bob(2).is_err());
+        assert!(tpp.bob(1).is_err());
     }
 }
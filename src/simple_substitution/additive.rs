@@ -4,6 +4,8 @@
 *===================================================================*/
 
 use crate::utils::char_set::CharSet;
+use crate::utils::cipher::Cipher;
+use crate::utils::error::Error;
 use crate::utils::mod_arithmetic::ModArithmetic;
 
 
@@ -16,23 +18,36 @@ impl AdditiveCipher {
         Self { char_set }
     }
 
-    pub fn encrypt(&self, text: &str, key: i32) -> String {
-        text.chars()
-            .map(|c| self.shift_char(c, key))
+    pub fn encrypt(&self, text: &str, key: i32) -> Result<String, Error> {
+        self.char_set
+            .indices_of(text)?
+            .into_iter()
+            .map(|index| self.shift_index(index, key))
             .collect()
     }
 
-    pub fn decrypt(&self, text: &str, key: i32) -> String {
+    pub fn decrypt(&self, text: &str, key: i32) -> Result<String, Error> {
         self.encrypt(text, -key)
     }
 
-    fn shift_char(&self, c: char, key: i32) -> char {
-        let index = self.char_set.index_of(c);
+    fn shift_index(&self, index: usize, key: i32) -> Result<char, Error> {
         let new_index = ModArithmetic::add_usize(index, key, self.char_set.len());
         self.char_set.char_at(new_index)
     }
 }
 
+impl Cipher for AdditiveCipher {
+    type Key = i32;
+
+    fn encrypt(&self, text: &str, key: &i32) -> Result<String, Error> {
+        self.encrypt(text, *key)
+    }
+
+    fn decrypt(&self, text: &str, key: &i32) -> Result<String, Error> {
+        self.decrypt(text, *key)
+    }
+}
+
 
 
 #[cfg(test)]
@@ -43,39 +58,46 @@ mod tests {
     fn test_ascii_lowercase() {
         let char_set = CharSet::from_reduced_ascii();
         let cipher = AdditiveCipher::new(char_set);
-        assert_eq!(cipher.encrypt("hello", 3), "khoor");
-        assert_eq!(cipher.decrypt("khoor", 3), "hello");
+        assert_eq!(cipher.encrypt("hello", 3).unwrap(), "khoor");
+        assert_eq!(cipher.decrypt("khoor", 3).unwrap(), "hello");
     }
 
     #[test]
     fn test_ascii_uppercase() {
         let char_set = CharSet::from_range('A', 'Z');
         let cipher = AdditiveCipher::new(char_set);
-        assert_eq!(cipher.encrypt("HELLO", 3), "KHOOR");
-        assert_eq!(cipher.decrypt("KHOOR", 3), "HELLO");
+        assert_eq!(cipher.encrypt("HELLO", 3).unwrap(), "KHOOR");
+        assert_eq!(cipher.decrypt("KHOOR", 3).unwrap(), "HELLO");
     }
 
     #[test]
     fn test_mixed_case_and_punctuation() {
         let char_set = CharSet::from_ascii(); 
         let cipher = AdditiveCipher::new(char_set);
-        assert_eq!(cipher.encrypt("Hello, World!", 3), "Khoor/#Zruog$");
-        assert_eq!(cipher.decrypt("Khoor/#Zruog$", 3), "Hello, World!");
+        assert_eq!(cipher.encrypt("Hello, World!", 3).unwrap(), "Khoor/#Zruog$");
+        assert_eq!(cipher.decrypt("Khoor/#Zruog$", 3).unwrap(), "Hello, World!");
     }
 
     #[test]
     fn test_unicode() {
         let char_set = CharSet::from_range('α', 'ω'); 
         let cipher = AdditiveCipher::new(char_set);
-        assert_eq!(cipher.encrypt("αβγδε", 2), "γδεζη");
-        assert_eq!(cipher.decrypt("γδεζη", 2), "αβγδε");
+        assert_eq!(cipher.encrypt("αβγδε", 2).unwrap(), "γδεζη");
+        assert_eq!(cipher.decrypt("γδεζη", 2).unwrap(), "αβγδε");
     }
 
     #[test]
     fn test_custom_char_set() {
         let char_set = CharSet::from_string("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789");
         let cipher = AdditiveCipher::new(char_set);
-        assert_eq!(cipher.encrypt("Hello123", 5), "Mjqqt678");
-        assert_eq!(cipher.decrypt("Mjqqt678", 5), "Hello123");
+        assert_eq!(cipher.encrypt("Hello123", 5).unwrap(), "Mjqqt678");
+        assert_eq!(cipher.decrypt("Mjqqt678", 5).unwrap(), "Hello123");
+    }
+
+    #[test]
+    fn test_unknown_character() {
+        let char_set = CharSet::from_range('A', 'Z');
+        let cipher = AdditiveCipher::new(char_set);
+        assert_eq!(cipher.encrypt("HELLo", 3), Err(Error::UnknownCharacter { character: 'o', position: Some(4) }));
     }
 }
\ No newline at end of file
@@ -4,6 +4,8 @@
 *===================================================================*/
 
 use crate::utils::char_set::CharSet;
+use crate::utils::cipher::Cipher;
+use crate::utils::error::Error;
 use crate::utils::mod_arithmetic::ModArithmetic;
 
 pub struct AffineCipher {
@@ -15,10 +17,11 @@ impl AffineCipher {
         Self { char_set }
     }
 
-    pub fn encrypt(&self, text: &str, k: i32, m: i32) -> String {
-        text.chars()
-            .map(|c| {
-                let index = self.char_set.index_of(c);
+    pub fn encrypt(&self, text: &str, k: i32, m: i32) -> Result<String, Error> {
+        self.char_set
+            .indices_of(text)?
+            .into_iter()
+            .map(|index| {
                 let p = ModArithmetic::mult_usize(index, k, self.char_set.len());
 
                 let new_index = ModArithmetic::add_usize(
@@ -32,13 +35,13 @@ impl AffineCipher {
             .collect()
     }
 
-    pub fn decrypt(&self, text: &str, k: i32, m: i32) -> String {
-        let k_inv = ModArithmetic::mod_inverse(k, self.char_set.len() as i32)
-            .expect("Invalid key: k is not coprime with m");
+    pub fn decrypt(&self, text: &str, k: i32, m: i32) -> Result<String, Error> {
+        let k_inv = ModArithmetic::mod_inverse(k, self.char_set.len() as i32)?;
 
-        text.chars()
-            .map(|c| {
-                let index = self.char_set.index_of(c);
+        self.char_set
+            .indices_of(text)?
+            .into_iter()
+            .map(|index| {
                 let new_index = ModArithmetic::mult_usize(
                     ModArithmetic::add_usize(index, -m, self.char_set.len()),
                     k_inv,
@@ -50,6 +53,19 @@ impl AffineCipher {
     }
 }
 
+impl Cipher for AffineCipher {
+    /// (k, m) in `C = kP + m`
+    type Key = (i32, i32);
+
+    fn encrypt(&self, text: &str, key: &(i32, i32)) -> Result<String, Error> {
+        self.encrypt(text, key.0, key.1)
+    }
+
+    fn decrypt(&self, text: &str, key: &(i32, i32)) -> Result<String, Error> {
+        self.decrypt(text, key.0, key.1)
+    }
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -63,13 +79,26 @@ mod tests {
         let cipher = AffineCipher::new(char_set);
 
 
-        assert_eq!(cipher.encrypt("123", 7, 3), "074");
+        assert_eq!(cipher.encrypt("123", 7, 3).unwrap(), "074");
     }
 
     #[test]
     fn test_decrypt() {
         let char_set = CharSet::from_numbers();
         let cipher = AffineCipher::new(char_set);
-        assert_eq!(cipher.decrypt("074", 7, 3), "123");
+        assert_eq!(cipher.decrypt("074", 7, 3).unwrap(), "123");
+    }
+
+    #[test]
+    fn test_cipher_trait() {
+        let char_set = CharSet::from_numbers();
+        let cipher = AffineCipher::new(char_set);
+
+        assert_eq!(Cipher::encrypt(&cipher, "123", &(7, 3)), Ok("074".to_string()));
+        assert_eq!(Cipher::decrypt(&cipher, "074", &(7, 3)), Ok("123".to_string()));
+
+        // 5 has no inverse mod 10 & `a` is not a number
+        assert_eq!(Cipher::decrypt(&cipher, "074", &(5, 3)), Err(Error::NonInvertibleKey { value: 5, modulus: 10 }));
+        assert_eq!(Cipher::encrypt(&cipher, "12a", &(7, 3)), Err(Error::UnknownCharacter { character: 'a', position: Some(2) }));
     }
 }
\ No newline at end of file
@@ -1,114 +1,192 @@
 /*===================================================================
  * Author: denosauabh
  * Description: Implementation of Hill Cipher by Lester S. Hill
- * Reference: https://en.wikipedia.org/wiki/Hill_cipher     
-                
+ * Reference: https://en.wikipedia.org/wiki/Hill_cipher
+
  * Formula:
- 
+
  2-letter block example:
  `C1 = k1*P1 + k2*P2 (mod 26)`
  `C2 = k3*P1 + k4*P2 (mod 26)`
 
+ n-letter blocks, with K an n×n key matrix:
+ `C = K * P (mod m)`
+ `P = K^-1 * C (mod m)` where `K^-1 = det(K)^-1 * adj(K)`
+
 *===================================================================*/
 
 use crate::utils::char_set::CharSet;
-use crate::utils::consts::NULL;
-use crate::utils::mod_arithmetic::ModArithmetic;
+use crate::utils::cipher::Cipher;
+use crate::utils::error::Error;
+use crate::utils::matrix::ModMatrix;
+
+/// sets of known blocks `recover_key` tries for an independent one before giving up
+const BLOCK_SETS: usize = 10_000;
+
+/// how the last block is filled when the text isn't a multiple of the block size
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HillPadding {
+    /// no padding, the text length must be a multiple of the block size
+    None,
+
+    /// fill the last block with a character of the charset (classically 'x'),
+    /// the padding is part of the decrypted text
+    Char(char),
+}
 
 pub struct HillCipher {
     char_set: CharSet,
-    block_size: u8,
+    padding: HillPadding,
 }
 
 impl HillCipher {
     pub fn new(char_set: CharSet) -> Self {
         Self {
-            char_set, 
-            block_size: 2
+            char_set,
+            padding: HillPadding::None,
          }
     }
 
-    pub fn encrypt(&self, text: &str, key: [i32; 4]) -> String {
-        let blocks = self.create_blocks(text);
-        let m = self.char_set.len() as i32;
+    pub fn with_padding(char_set: CharSet, padding: HillPadding) -> Result<Self, Error> {
+        if let HillPadding::Char(c) = padding {
+            char_set.index_of(c)?;
+        }
 
-        blocks
-            .iter()
-            .map(|block| {
-                let bi: Vec<i32> = block.iter().map(|c| self.char_set.index_of(*c) as i32).collect::<Vec<i32>>();
-
-                let c1 = ModArithmetic::add(
-                    bi[0] * key[0],
-                    bi[1] * key[1],
-                    m
-                );
-                let c2 = ModArithmetic::add(
-                    bi[0] * key[2],
-                    bi[1] * key[3],
-                    m
-                );
-
-                format!("{}{}", self.char_set.char_at(c1 as usize), self.char_set.char_at(c2 as usize))
-            })
-            .collect()
+        Ok(Self { char_set, padding })
+    }
+
+    /// `key` is the n×n key matrix (rows), the block size is n
+    pub fn encrypt(&self, text: &str, key: &[Vec<i32>]) -> Result<String, Error> {
+        let block_size = ModMatrix::dimension(key)?;
+        let blocks = self.create_blocks(text, block_size, true)?;
+
+        self.transform(&blocks, key)
+    }
+
+    pub fn decrypt(&self, text: &str, key: &[Vec<i32>]) -> Result<String, Error> {
+        let block_size = ModMatrix::dimension(key)?;
+        let blocks = self.create_blocks(text, block_size, false)?;
+
+        let key_inv = ModMatrix::inverse(key, self.char_set.len() as i32)?;
+
+        self.transform(&blocks, &key_inv)
+    }
+
+    /// checks that the key is square & invertible modulo the charset length, i.e. can be decrypted
+    pub fn check_key(&self, key: &[Vec<i32>]) -> Result<(), Error> {
+        ModMatrix::inverse(key, self.char_set.len() as i32).map(|_| ())
     }
 
-    pub fn decrypt(&self, text: &str, key: [i32; 4]) -> String {
-        let blocks = self.create_blocks(text);
+    /// known-plaintext attack, solves `C = K * P (mod m)` for the key of the given block size.
+    /// Needs `block_size` independent plaintext blocks (their matrix invertible), the sets of blocks
+    /// are tried in order (the first `BLOCK_SETS` of them), every block verifies the recovered key.
+    pub fn recover_key(&self, plaintext: &str, ciphertext: &str, block_size: usize) -> Result<Vec<Vec<i32>>, Error> {
+        if block_size == 0 {
+            return Err(Error::InvalidKey("block size must be at least 1".to_string()));
+        }
+
         let m = self.char_set.len() as i32;
+        let p_blocks = self.create_blocks(plaintext, block_size, false)?;
+        let c_blocks = self.create_blocks(ciphertext, block_size, false)?;
+
+        if p_blocks.len() != c_blocks.len() {
+            return Err(Error::MalformedCiphertext("plaintext & ciphertext have different lengths".to_string()));
+        }
+        if p_blocks.len() < block_size {
+            return Err(Error::NotEnoughBlocks { needed: block_size, found: p_blocks.len() });
+        }
+
+        let (set, p_inv) = Self::independent_blocks(&p_blocks, block_size, m)?;
+
+        // blocks are the columns of P & C
+        let c = ModMatrix::transpose(&set.iter().map(|&i| c_blocks[i].clone()).collect::<Vec<Vec<i32>>>());
+        let key = ModMatrix::mul(&c, &p_inv, m);
 
-        // inverse keys
-        let k1 = key[0];
-        let k2 = key[1];
-        let k3 = key[2];
-        let k4 = key[3];
+        if p_blocks.iter().zip(&c_blocks).any(|(p, c)| &ModMatrix::mul_vec(&key, p, m) != c) {
+            return Err(Error::MalformedCiphertext("plaintext & ciphertext are not related by a single key".to_string()));
+        }
 
-        let key_inv = ModArithmetic::mod_inverse(ModArithmetic::modm((k1*k4) - (k2*k3), m), m).expect("inverse doesn't exist");
+        Ok(key)
+    }
+
+    /// the first set of `block_size` blocks (indices in increasing order) whose matrix is invertible,
+    /// with that inverse. `DependentBlocks` carries the determinant of the first set
+    fn independent_blocks(blocks: &[Vec<i32>], block_size: usize, m: i32) -> Result<(Vec<usize>, Vec<Vec<i32>>), Error> {
+        let mut set: Vec<usize> = (0..block_size).collect();
+        let mut first_err = None;
 
-        let m1: i32 = key_inv * k4; 
-        let m2: i32 = key_inv * -k2; 
-        let m3: i32 = key_inv * -k3;  
-        let m4: i32 = key_inv * k1; 
+        for _ in 0..BLOCK_SETS {
+            let p = ModMatrix::transpose(&set.iter().map(|&i| blocks[i].clone()).collect::<Vec<Vec<i32>>>());
+
+            match ModMatrix::inverse(&p, m) {
+                Ok(p_inv) => return Ok((set, p_inv)),
+                Err(Error::NonInvertibleKey { value, modulus }) => {
+                    first_err.get_or_insert(Error::DependentBlocks { determinant: value, modulus });
+                },
+                Err(err) => return Err(err),
+            }
+
+            // next set, the last index that can still move moves up & the ones after it follow
+            let Some(i) = (0..block_size).rev().find(|&i| set[i] < blocks.len() - block_size + i) else { break };
+            set[i] += 1;
+            for j in i + 1..block_size {
+                set[j] = set[j - 1] + 1;
+            }
+        }
+
+        Err(first_err.expect("at least one set of blocks"))
+    }
+
+    fn transform(&self, blocks: &[Vec<i32>], matrix: &[Vec<i32>]) -> Result<String, Error> {
+        let m = self.char_set.len() as i32;
 
         blocks
             .iter()
-            .map(|block| {
-                let bi: Vec<i32> = block.iter().map(|c| self.char_set.index_of(*c) as i32).collect::<Vec<i32>>();
-
-                let p1 = ModArithmetic::add(
-                    bi[0] * m1,
-                    bi[1] * m2,
-                    m
-                );
-                let p2 = ModArithmetic::add(
-                    bi[0] * m3,
-                    bi[1] * m4,
-                    m
-                );
-
-                format!("{}{}", self.char_set.char_at(p1 as usize), self.char_set.char_at(p2 as usize))
-            })
+            .flat_map(|block| ModMatrix::mul_vec(matrix, block, m))
+            .map(|i| self.char_set.char_at(i as usize))
             .collect()
     }
 
 
-    pub fn create_blocks(&self, text: &str) -> Vec<Vec<char>> {
-        let binding = text.chars().collect::<Vec<char>>();
-        let blocks = binding.chunks(self.block_size as usize);
+    /// splits the text into blocks of charset indices,
+    /// only plaintext (`pad`) is padded according to the padding policy
+    pub fn create_blocks(&self, text: &str, block_size: usize, pad: bool) -> Result<Vec<Vec<i32>>, Error> {
+        let mut indices = self.char_set
+            .indices_of(text)?
+            .into_iter()
+            .map(|i| i as i32)
+            .collect::<Vec<i32>>();
 
-        // if the last block is not of size `block_size`, pad it with `PAD_CHAR`
-        blocks.map(|block| {
-            let mut block = block.to_vec();
-            while block.len() < self.block_size as usize {
-                block.push(self.PAD_CHAR());
+        let rem = indices.len() % block_size;
+
+        if rem != 0 {
+            match (pad, self.padding) {
+                (true, HillPadding::Char(c)) => {
+                    let index = self.char_set.index_of(c)? as i32;
+                    indices.extend(std::iter::repeat_n(index, block_size - rem));
+                },
+                (true, HillPadding::None) => {
+                    return Err(Error::InvalidTextLength { multiple_of: block_size, found: indices.len() });
+                },
+                (false, _) => {
+                    return Err(Error::MalformedCiphertext(format!("length {} is not a multiple of the block size {}", indices.len(), block_size)));
+                },
             }
-            block
-        }).collect()
+        }
+
+        Ok(indices.chunks(block_size).map(|block| block.to_vec()).collect())
+    }
+}
+
+impl Cipher for HillCipher {
+    type Key = Vec<Vec<i32>>;
+
+    fn encrypt(&self, text: &str, key: &Vec<Vec<i32>>) -> Result<String, Error> {
+        self.encrypt(text, key)
     }
 
-    pub fn PAD_CHAR(&self) -> char {
-        // self.char_set.char_at(0).unwrap_or(' ')
-        NULL
+    fn decrypt(&self, text: &str, key: &Vec<Vec<i32>>) -> Result<String, Error> {
+        self.decrypt(text, key)
     }
 }
 
@@ -122,13 +200,13 @@ mod tests {
         let char_set = CharSet::from_numbers();
         let hill_cipher = HillCipher::new(char_set);
 
-        assert_eq!(hill_cipher.encrypt("1234", [1, 2, 3, 4]), "5115");
+        assert_eq!(hill_cipher.encrypt("1234", &[vec![1, 2], vec![3, 4]]).unwrap(), "5115");
+
 
-        
         let char_set_sm_alph = CharSet::from_alphabet_lowercase();
         let hill_cipher_sm_alph = HillCipher::new(char_set_sm_alph);
 
-        assert_eq!(hill_cipher_sm_alph.encrypt("jack", [3, 5, 6, 1]), "bcew");
+        assert_eq!(hill_cipher_sm_alph.encrypt("jack", &[vec![3, 5], vec![6, 1]]).unwrap(), "bcew");
 
     }
 
@@ -140,6 +218,102 @@ mod tests {
 
         // assert_eq!(hill_cipher_sm_alph.encrypt("bcew", [25, 5, 6, 23]), "jack");
         // assert_eq!(hill_cipher_sm_alph.encrypt("bcew", [1, 21, 20, 3]), "jack");
-        assert_eq!(hill_cipher_sm_alph.decrypt("bcew", [3, 5, 6, 1]), "jack");
+        assert_eq!(hill_cipher_sm_alph.decrypt("bcew", &[vec![3, 5], vec![6, 1]]).unwrap(), "jack");
+    }
+
+    #[test]
+    fn test_3x3() {
+        // https://en.wikipedia.org/wiki/Hill_cipher#Example, key "GYBNQKURP"
+        let hill_cipher = HillCipher::new(CharSet::from_alphabet_lowercase());
+        let key = vec![vec![6, 24, 1], vec![13, 16, 10], vec![20, 17, 15]];
+
+        assert_eq!(hill_cipher.encrypt("act", &key).unwrap(), "poh");
+        assert_eq!(hill_cipher.encrypt("cat", &key).unwrap(), "fin");
+        assert_eq!(hill_cipher.decrypt("pohfin", &key).unwrap(), "actcat");
+    }
+
+    #[test]
+    fn test_4x4() {
+        let hill_cipher = HillCipher::new(CharSet::from_alphabet_lowercase());
+        let key = vec![vec![3, 10, 20, 20], vec![9, 17, 5, 12], vec![1, 2, 23, 1], vec![7, 15, 4, 18]];
+
+        let encrypted = hill_cipher.encrypt("shortexampletext", &key).unwrap();
+        assert_eq!(encrypted, "qjhvlqkzsynjbkdd");
+        assert_eq!(hill_cipher.decrypt(&encrypted, &key).unwrap(), "shortexampletext");
+    }
+
+    #[test]
+    fn test_padding() {
+        let key = vec![vec![6, 24, 1], vec![13, 16, 10], vec![20, 17, 15]];
+
+        let hill_cipher = HillCipher::new(CharSet::from_alphabet_lowercase());
+        assert_eq!(hill_cipher.encrypt("acta", &key), Err(Error::InvalidTextLength { multiple_of: 3, found: 4 }));
+
+        let hill_cipher = HillCipher::with_padding(CharSet::from_alphabet_lowercase(), HillPadding::Char('x')).unwrap();
+        let encrypted = hill_cipher.encrypt("acta", &key).unwrap();
+        assert_eq!(encrypted.len(), 6);
+        assert_eq!(hill_cipher.decrypt(&encrypted, &key).unwrap(), "actaxx");
+
+        assert!(hill_cipher.decrypt("pohfi", &key).is_err());
+        assert!(HillCipher::with_padding(CharSet::from_alphabet_lowercase(), HillPadding::Char('X')).is_err());
+    }
+
+    #[test]
+    fn test_recover_key() {
+        let hill_cipher = HillCipher::new(CharSet::from_alphabet_lowercase());
+
+        let key = vec![vec![3, 5], vec![6, 1]];
+        assert_eq!(hill_cipher.recover_key("help", "pued", 2), Ok(key));
+
+        // "jack" won't do, its blocks have determinant 9*10 - 2*0 = 12 (mod 26)
+        assert_eq!(hill_cipher.recover_key("jack", "bcew", 2), Err(Error::DependentBlocks { determinant: 12, modulus: 26 }));
+
+        let key = vec![vec![6, 24, 1], vec![13, 16, 10], vec![20, 17, 15]];
+        let plaintext = "retreatnowxx";
+        let ciphertext = hill_cipher.encrypt(plaintext, &key).unwrap();
+        assert_eq!(hill_cipher.recover_key(plaintext, &ciphertext, 3), Ok(key));
+
+        let key = vec![vec![3, 10, 20, 20], vec![9, 17, 5, 12], vec![1, 2, 23, 1], vec![7, 15, 4, 18]];
+        assert_eq!(hill_cipher.recover_key("shortexampletext", "qjhvlqkzsynjbkdd", 4), Ok(key));
+    }
+
+    #[test]
+    fn test_recover_key_errors() {
+        let hill_cipher = HillCipher::new(CharSet::from_alphabet_lowercase());
+        let key = vec![vec![3, 5], vec![6, 1]];
+
+        // "bb" (1, 1) & "dd" (3, 3) are multiples of each other
+        let ciphertext = hill_cipher.encrypt("bbdd", &key).unwrap();
+        assert_eq!(hill_cipher.recover_key("bbdd", &ciphertext, 2), Err(Error::DependentBlocks { determinant: 0, modulus: 26 }));
+
+        // not enough blocks
+        assert_eq!(hill_cipher.recover_key("ja", "bc", 2), Err(Error::NotEnoughBlocks { needed: 2, found: 1 }));
+
+        // "bb" & "dd" are dependent, "bb" & "ep" aren't, so the third block stands in for the second
+        let ciphertext = hill_cipher.encrypt("bbddep", &key).unwrap();
+        assert_eq!(hill_cipher.recover_key("bbddep", &ciphertext, 2), Ok(key.clone()));
+
+        // the last pair doesn't match the key of the first two
+        assert!(matches!(hill_cipher.recover_key("helphelp", "puedpuea", 2), Err(Error::MalformedCiphertext(_))));
+    }
+
+    #[test]
+    fn test_non_invertible_key() {
+        let char_set_sm_alph = CharSet::from_alphabet_lowercase();
+        let hill_cipher_sm_alph = HillCipher::new(char_set_sm_alph);
+
+        // determinant is 2*4 - 2*3 = 2, which shares a factor with 26
+        assert_eq!(
+            hill_cipher_sm_alph.decrypt("bcew", &[vec![2, 2], vec![3, 4]]),
+            Err(Error::NonInvertibleKey { value: 2, modulus: 26 })
+        );
+        assert!(hill_cipher_sm_alph.check_key(&[vec![2, 2], vec![3, 4]]).is_err());
+        assert!(hill_cipher_sm_alph.check_key(&[vec![3, 5], vec![6, 1]]).is_ok());
+
+        // not a square matrix
+        assert_eq!(
+            hill_cipher_sm_alph.encrypt("jack", &[vec![3, 5], vec![6]]),
+            Err(Error::InvalidKeyLength { expected: 2, found: 1 })
+        );
     }
 }
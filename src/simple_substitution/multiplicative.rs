@@ -5,10 +5,11 @@
 
 
 use crate::utils::char_set::CharSet;
-use crate::utils::consts::NULL;
+use crate::utils::cipher::Cipher;
+use crate::utils::error::Error;
 use crate::utils::mod_arithmetic::ModArithmetic;
 
-struct MultiplicativeCipher {
+pub struct MultiplicativeCipher {
     char_set: CharSet,
 }
 
@@ -17,30 +18,35 @@ impl MultiplicativeCipher {
         Self { char_set }
     }
 
-    pub fn encrypt(&self, text: &str, key: i32) -> String {
-        text.chars()
-            .map(|c| self.shift_char(c, key))
+    pub fn encrypt(&self, text: &str, key: i32) -> Result<String, Error> {
+        self.char_set
+            .indices_of(text)?
+            .into_iter()
+            .map(|index| self.shift_index(index, key))
             .collect()
     }
 
-    pub fn decrypt(&self, text: &str, key: i32) -> String {
-        match ModArithmetic::mod_inverse(key, self.char_set.len() as i32) {
-            Ok(inv_key) => self.encrypt(text, inv_key),
-            Err(err) => {
-               panic!("{}", err)
-            },
-        }
+    pub fn decrypt(&self, text: &str, key: i32) -> Result<String, Error> {
+        let inv_key = ModArithmetic::mod_inverse(key, self.char_set.len() as i32)?;
+        self.encrypt(text, inv_key)
     }
 
-    fn shift_char(&self, c: char, key: i32) -> char {
-        let index = self.char_set.index_of(c);
+    /// index 0 stays the first character of the charset, so `0 · k` decrypts back to it
+    fn shift_index(&self, index: usize, key: i32) -> Result<char, Error> {
         let new_index = ModArithmetic::mult_usize(index, key, self.char_set.len());
+        self.char_set.char_at(new_index)
+    }
+}
 
-        if new_index == 0 {
-            return NULL;
-        }
+impl Cipher for MultiplicativeCipher {
+    type Key = i32;
 
-        self.char_set.char_at(new_index)
+    fn encrypt(&self, text: &str, key: &i32) -> Result<String, Error> {
+        self.encrypt(text, *key)
+    }
+
+    fn decrypt(&self, text: &str, key: &i32) -> Result<String, Error> {
+        self.decrypt(text, *key)
     }
 }
 
@@ -53,21 +59,27 @@ mod tests {
     fn test_encrypt() {
         let char_set = CharSet::from_numbers();
         let cipher = MultiplicativeCipher::new(char_set);
-        assert_eq!(cipher.encrypt("123", 3), "369");
+        assert_eq!(cipher.encrypt("123", 3).unwrap(), "369");
 
         let r_ascii_char_set = CharSet::from_reduced_ascii();
         let rascii_cipher = MultiplicativeCipher::new(r_ascii_char_set);
 
-        assert_eq!(rascii_cipher.encrypt("HeLlO..", 0), "\u{FFFD}".repeat(7));
-        assert_eq!(rascii_cipher.encrypt("HeLlO..", 1), "HeLlO..");
-        assert_eq!(rascii_cipher.encrypt("!@HeLllO!..", 8), "(bCmcFF{(11");
+        assert_eq!(rascii_cipher.encrypt("HeLlO..", 0).unwrap(), " ".repeat(7));
+        assert_eq!(rascii_cipher.encrypt("HeLlO..", 1).unwrap(), "HeLlO..");
+        assert_eq!(rascii_cipher.encrypt("!@HeLllO!..", 8).unwrap(), "(bCmcFF{(11");
     }
 
     #[test]
     fn test_decrypt() {
         let char_set = CharSet::from_numbers();
         let cipher = MultiplicativeCipher::new(char_set);
-        assert_eq!(cipher.decrypt("369", 3), "123");
+        assert_eq!(cipher.decrypt("369", 3).unwrap(), "123");
+        assert_eq!(cipher.decrypt("369", 5), Err(Error::NonInvertibleKey { value: 5, modulus: 10 }));
+
+        // index 0 maps to itself
+        let cipher = MultiplicativeCipher::new(CharSet::from_alphabet_lowercase());
+        assert_eq!(cipher.encrypt("attack", 7).unwrap(), "addaos");
+        assert_eq!(cipher.decrypt("addaos", 7).unwrap(), "attack");
     }
 
 }
\ No newline at end of file
@@ -4,9 +4,7 @@
  *===================================================================*/
 
 
-use std::usize;
-
-use crate::utils::{char_set::CharSet, mod_arithmetic::ModArithmetic};
+use crate::utils::{char_set::CharSet, cipher::Cipher, error::Error, mod_arithmetic::ModArithmetic};
 
 
 pub enum AutoKeyType {
@@ -26,83 +24,95 @@ impl Autokey {
     }
 
     /// can be any other cipher or excryption method
-    pub fn transformation(&self, c: char, inverse: bool) -> char {
-        let c_index = self.char_set.index_of(c) as u32;
-        let m = self.char_set.len() as u32;
-
-        let new_char_index: u32;
+    pub fn transformation(&self, c: char, inverse: bool) -> Result<char, Error> {
+        let c_index = self.char_set.index_of(c)? as i32;
+        let m = self.char_set.len() as i32;
 
-        if inverse {
-            let mult_inv = ModArithmetic::mod_inverse(25, m as i32).expect("inverse doesn't exist") as u32;
-            new_char_index = ModArithmetic::modm_u32(mult_inv*ModArithmetic::modm_u32((if c_index == 0 { 26 } else { c_index }) - 1, m), m);
+        let new_char_index = if inverse {
+            let mult_inv = ModArithmetic::mod_inverse(25, m)?;
+            ModArithmetic::mult(mult_inv, c_index - 1, m)
         } else {
-            new_char_index = ModArithmetic::modm_u32(25*c_index + 1, m);
-        }
+            ModArithmetic::add(25 * c_index, 1, m)
+        };
 
         self.char_set.char_at(new_char_index as usize)
     }
 
     /// iv - initialization vector
-    pub fn encrypt(&self, text: &str, iv: &str) -> String {
-        let chars = text.chars().collect::<Vec<char>>();
-        let iv_chars = iv.chars().collect::<Vec<char>>();
-
-        let mut encrypted: Vec<char> = vec![];
-
-        for (i, c) in text.chars().enumerate() {
-            let shifted_c: char = if i < iv.len() { iv_chars[i] } else { 
-                match self.mode {
-                    AutoKeyType::Plaintext => chars[i - iv.len()],
-                    AutoKeyType::Ciphertext => encrypted[i - iv.len()],
-                }
+    pub fn encrypt(&self, text: &str, iv: &str) -> Result<String, Error> {
+        let chars = self.char_set.indices_of(text)?;
+        let iv_chars = self.parse_iv(iv)?;
+
+        let mut encrypted: Vec<usize> = vec![];
+
+        for (i, &c) in chars.iter().enumerate() {
+            let shifted_c: char = if i < iv_chars.len() { iv_chars[i] } else { 
+                let index = match self.mode {
+                    AutoKeyType::Plaintext => chars[i - iv_chars.len()],
+                    AutoKeyType::Ciphertext => encrypted[i - iv_chars.len()],
+                };
+                self.char_set.char_at(index)?
             };
-            let keystream_char = self.transformation(shifted_c, false);
-
-            let encrypted_char = self.char_set.char_at(
-                ModArithmetic::add_usize(
-                    self.char_set.index_of(c),
-                    self.char_set.index_of(keystream_char) as i32,
-                    self.char_set.len() 
-                )
-            );
-
-            // println!("{} -> {} -> {} -> {}", shifted_c, keystream_char, c, encrypted_char);
+            let keystream_char = self.transformation(shifted_c, false)?;
 
-            encrypted.push(encrypted_char);
+            encrypted.push(ModArithmetic::add_usize(
+                c,
+                self.char_set.index_of(keystream_char)? as i32,
+                self.char_set.len() 
+            ));
         }
 
-        encrypted.iter().collect()
+        encrypted.into_iter().map(|i| self.char_set.char_at(i)).collect()
     }
 
-    pub fn decrypt(&self, text: &str, iv: &str) -> String {
-        let iv_chars = iv.chars().collect::<Vec<char>>();
+    pub fn decrypt(&self, text: &str, iv: &str) -> Result<String, Error> {
+        let chars = self.char_set.indices_of(text)?;
+        let iv_chars = self.parse_iv(iv)?;
 
-        let mut decrypted: Vec<char> = vec![];
+        let mut decrypted: Vec<usize> = vec![];
 
-        for (i, c) in text.chars().enumerate() {
-            let shifted_c = if i < iv.len() { iv_chars[i] } else { 
-                match self.mode {
-                    AutoKeyType::Ciphertext => text.chars().nth(i - iv.len()).unwrap(),
-                    AutoKeyType::Plaintext => decrypted[i - iv.len()],
-                }
+        for (i, &c) in chars.iter().enumerate() {
+            let shifted_c = if i < iv_chars.len() { iv_chars[i] } else { 
+                let index = match self.mode {
+                    AutoKeyType::Ciphertext => chars[i - iv_chars.len()],
+                    AutoKeyType::Plaintext => decrypted[i - iv_chars.len()],
+                };
+                self.char_set.char_at(index)?
             };
-            let inverted_transformation = self.transformation(shifted_c, true);
 
-            let plaintext = self.char_set.char_at(
-                ModArithmetic::add_usize(
-                    self.char_set.index_of(c),
-                    -(self.char_set.index_of(inverted_transformation) as i32),
+            // same keystream as the encryption, which then gets subtracted
+            let keystream_char = self.transformation(shifted_c, false)?;
 
-                    self.char_set.len()
-                )
-            );
+            decrypted.push(ModArithmetic::add_usize(
+                c,
+                -(self.char_set.index_of(keystream_char)? as i32),
+                self.char_set.len()
+            ));
+        }
 
-            // println!("{} -> {} -> {} -> {}", shifted_c, inverted_transformation, c, plaintext);
+        decrypted.into_iter().map(|i| self.char_set.char_at(i)).collect()
+    }
 
-            decrypted.push(plaintext);
+    fn parse_iv(&self, iv: &str) -> Result<Vec<char>, Error> {
+        if iv.is_empty() {
+            return Err(Error::InvalidKey("initialization vector must not be empty".to_string()));
         }
+        self.char_set.check(iv)?;
+
+        Ok(iv.chars().collect())
+    }
+}
+
+impl Cipher for Autokey {
+    /// initialization vector
+    type Key = String;
 
-        decrypted.iter().collect()
+    fn encrypt(&self, text: &str, key: &String) -> Result<String, Error> {
+        self.encrypt(text, key)
+    }
+
+    fn decrypt(&self, text: &str, key: &String) -> Result<String, Error> {
+        self.decrypt(text, key)
     }
 }
 
@@ -118,14 +128,14 @@ mod tests {
 
         let text = "aworthlesscracking";
         let expected = "gpnsyuvmmizajbujmx";
-        assert_eq!(autokey.encrypt(text, "vic"), expected);
+        assert_eq!(autokey.encrypt(text, "vic").unwrap(), expected);
 
         // ciphertext autokey
         let charset = CharSet::from_alphabet_lowercase();
         let autokey = Autokey::new(AutoKeyType::Ciphertext, charset);
         let text = "wasteallyouroil";
         let expected = "ctrsmkuapvvduoj";
-        assert_eq!(autokey.encrypt(text, "vic"), expected);
+        assert_eq!(autokey.encrypt(text, "vic").unwrap(), expected);
     }
 
 
@@ -138,7 +148,7 @@ mod tests {
 
         let text = "gxtedpfupblqkdjzgu";
         let expected = "aworthlesscracking";
-        assert_eq!(autokey.decrypt(text, "v"), expected);
+        assert_eq!(autokey.decrypt(text, "v").unwrap(), expected);
 
         // ciphertext autokey
         let charset = CharSet::from_alphabet_lowercase();
@@ -146,7 +156,17 @@ mod tests {
 
         let text = "ctrsmkuapvvduoj";
         let expected = "wasteallyouroil";
-        assert_eq!(autokey.decrypt(text, "vic"), expected);
+        assert_eq!(autokey.decrypt(text, "vic").unwrap(), expected);
 
    }
+
+    #[test]
+    fn test_autokey_round_trip_other_charset() {
+        // 25 has no inverse modulo 95, so decryption must not depend on it
+        let charset = CharSet::from_reduced_ascii();
+        let autokey = Autokey::new(AutoKeyType::Plaintext, charset);
+
+        let encrypted = autokey.encrypt("Meet me at 9!", "key").unwrap();
+        assert_eq!(autokey.decrypt(&encrypted, "key").unwrap(), "Meet me at 9!");
+    }
 }
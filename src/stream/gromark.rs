@@ -5,74 +5,69 @@
  *===================================================================*/
 
 
-use std::usize;
-
-use crate::utils::{char_set::CharSet, mod_arithmetic::ModArithmetic};
+use crate::utils::{char_set::CharSet, cipher::Cipher, error::Error, mod_arithmetic::ModArithmetic};
 
 pub struct Gromark {
     char_set: CharSet,
-    last_five_keys: Vec<usize>,
 }
 
 impl Gromark {
     pub fn new(char_set: CharSet) -> Self {
-        Self { 
-            char_set,
-            last_five_keys: Vec::new(),
-        }
-    }
-
-    pub fn lagged_fibonacci_generator(&mut self, default_iv_vec: Vec<usize>) -> usize {
-        if self.last_five_keys.len() < 5 {
-            let key = default_iv_vec[self.last_five_keys.len()];
-            self.last_five_keys.push(key);
-            key
-        } else {
-            let new_key = (self.last_five_keys[0] + self.last_five_keys[1]) % self.char_set.len();
-            self.last_five_keys.remove(0);
-            self.last_five_keys.push(new_key);
-            new_key
-        }
+        Self { char_set }
     }
 
-    pub fn encrypt(&mut self, text: &str, default_iv_vec: Vec<usize>) -> String {
-        self.char_set.panic_if_invalid(text);
-        self.last_five_keys.clear();
+    /// first five keys are the iv itself, every next key is the sum of the keys 5 & 4 places before it
+    pub fn lagged_fibonacci_generator(&self, default_iv_vec: &[usize], len: usize) -> Vec<usize> {
+        let mut keys: Vec<usize> = Vec::with_capacity(len);
 
-        let mut encrypted = String::new();
+        for i in 0..len {
+            if i < 5 {
+                keys.push(default_iv_vec[i]);
+            } else {
+                keys.push((keys[i - 5] + keys[i - 4]) % self.char_set.len());
+            }
+        }
 
-        for (i, c) in text.chars().enumerate() {
-            let key = self.lagged_fibonacci_generator(default_iv_vec.clone());
+        keys
+    }
 
-            println!("key: {}", key);
+    pub fn encrypt(&self, text: &str, default_iv_vec: Vec<usize>) -> Result<String, Error> {
+        self.shift(text, &default_iv_vec, false)
+    }
 
-            let char_index = self.char_set.index_of(c);
-            let encrypted_index = ModArithmetic::add_usize(char_index, key as i32, self.char_set.len());
-            let encrypted_char = self.char_set.char_at(encrypted_index);
+    pub fn decrypt(&self, text: &str, default_iv_vec: Vec<usize>) -> Result<String, Error> {
+        self.shift(text, &default_iv_vec, true)
+    }
 
-            encrypted.push(encrypted_char);
+    fn shift(&self, text: &str, default_iv_vec: &[usize], inverse: bool) -> Result<String, Error> {
+        if default_iv_vec.len() != 5 {
+            return Err(Error::InvalidKeyLength { expected: 5, found: default_iv_vec.len() });
         }
 
-        encrypted
+        let indices = self.char_set.indices_of(text)?;
+        let keys = self.lagged_fibonacci_generator(default_iv_vec, indices.len());
+
+        indices
+            .into_iter()
+            .zip(keys)
+            .map(|(char_index, key)| {
+                let key = if inverse { -(key as i32) } else { key as i32 };
+                self.char_set.char_at(ModArithmetic::add_usize(char_index, key, self.char_set.len()))
+            })
+            .collect()
     }
+}
 
-    pub fn decrypt(&mut self, text: &str, default_iv_vec: Vec<usize>) -> String {
-        self.char_set.panic_if_invalid(text);
-        self.last_five_keys.clear();
-
-        let mut decrypted = String::new();
-
-        for (i, c) in text.chars().enumerate() {
-            let key = self.lagged_fibonacci_generator(default_iv_vec.clone());
-
-            let char_index = self.char_set.index_of(c);
-            let decrypted_index = ModArithmetic::add_usize(char_index, -(key as i32), self.char_set.len());
-            let decrypted_char = self.char_set.char_at(decrypted_index);
+impl Cipher for Gromark {
+    /// five digit primer of the lagged fibonacci generator
+    type Key = Vec<usize>;
 
-            decrypted.push(decrypted_char);
-        }
+    fn encrypt(&self, text: &str, key: &Vec<usize>) -> Result<String, Error> {
+        self.encrypt(text, key.clone())
+    }
 
-        decrypted
+    fn decrypt(&self, text: &str, key: &Vec<usize>) -> Result<String, Error> {
+        self.decrypt(text, key.clone())
     }
 }
 
@@ -83,10 +78,12 @@ mod tests {
     #[test]
     fn test_gromark() {
         let charset = CharSet::from_alphabet_lowercase();
-        let mut gromark = Gromark::new(charset);
+        let gromark = Gromark::new(charset);
 
         let text = "multiplylikerabbits";
         let expected = "nwlvrsnawupiexskzdg";
-        assert_eq!(gromark.encrypt(text, vec![1,2,0,2,9]), expected);
+        assert_eq!(gromark.encrypt(text, vec![1,2,0,2,9]).unwrap(), expected);
+        assert_eq!(gromark.decrypt(expected, vec![1,2,0,2,9]).unwrap(), text);
+        assert_eq!(gromark.encrypt(text, vec![1,2,0]), Err(Error::InvalidKeyLength { expected: 5, found: 3 }));
     }
 }
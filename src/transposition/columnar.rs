@@ -5,7 +5,9 @@
  * Reference: https://en.wikipedia.org/wiki/Transposition_cipher#Columnar_transposition
 *===================================================================*/
 
+use crate::utils::cipher::Cipher;
 use crate::utils::consts::NULL;
+use crate::utils::error::Error;
 
 pub struct Columnar {}
 
@@ -14,20 +16,21 @@ impl Columnar {
         Self {}
     }
 
-    pub fn encrypt(&self, text: &str, key: &str) -> String {
+    pub fn encrypt(&self, text: &str, key: &str) -> Result<String, Error> {
         self.permute_encrypt(text, self.parse_key(key))
     }
 
-    pub fn decrypt(&self, text: &str, key: &str) -> String {
+    pub fn decrypt(&self, text: &str, key: &str) -> Result<String, Error> {
         self.permute_decrypt(text, self.parse_key(key))
     }
 
-    pub fn permute_encrypt(&self, text: &str, key: Vec<usize>) -> String {
+    pub fn permute_encrypt(&self, text: &str, key: Vec<usize>) -> Result<String, Error> {
+        self.check_key(&key)?;
         let text: Vec<char> = text.chars().collect();
 
         let mut encryped_vec: Vec<Vec<char>> = vec![vec![]; key.len()];
 
-        let rows = (text.len() / key.len()) + if text.len() % key.len() == 0 { 0 } else { 1 };
+        let rows = text.len().div_ceil(key.len());
 
         for r in 0..rows {
             for c in 0..key.len() {
@@ -42,46 +45,31 @@ impl Columnar {
 
         let encrypted = encryped_vec.iter().flat_map(|v| v.iter()).collect::<String>();
 
-        encrypted.to_string()
+        Ok(encrypted)
     }
 
-    /// TODO: not working for some cases
-    pub fn permute_decrypt(&self, text: &str, key: Vec<usize>) -> String {
+    /// the grid may be irregular, the first `len % cols` columns are one character longer
+    pub fn permute_decrypt(&self, text: &str, key: Vec<usize>) -> Result<String, Error> {
+        self.check_key(&key)?;
         let text: Vec<char> = text.chars().collect();
 
         let cols = key.len();
         let rows = text.len() / cols;
+        let rem = text.len() % cols;
 
-        let mut decryped_vec: Vec<Vec<char>> = vec![vec![]; key.len()];
         let mut decrypted: Vec<char> = vec![NULL; text.len()];
-        
-        let mut inverse_key = self.inverse_key(key);
-        inverse_key = inverse_key.iter().map(|&x| x - 1).collect();
-
-        for i in 0..text.len() {
-            let ri = i % rows;
-            let ci = i / rows;
-
-            if ci < inverse_key.len() { 
-                let k = inverse_key[ci];
-                decryped_vec[k].push(text[i]);
-            } else { 
-                decryped_vec[i - (rows * cols)].push(text[i]);
-            };
-        }
+        let mut chars = text.into_iter();
 
-        for i in 0..decryped_vec.len() {
-            for j in 0..decryped_vec[i].len() {
-                let pos = (j * cols) + i;
-                if pos < decrypted.len() {
-                    decrypted[pos] = decryped_vec[i][j];
-                }
+        // columns are read off in key order
+        for c in self.inverse_key(key).into_iter().map(|k| k - 1) {
+            let column_len = rows + usize::from(c < rem);
+
+            for (r, ch) in chars.by_ref().take(column_len).enumerate() {
+                decrypted[r * cols + c] = ch;
             }
         }
 
-        println!("decryped_vec: {:?}", decryped_vec);
-    
-        decrypted.into_iter().filter(|&c| c != NULL).collect()
+        Ok(decrypted.into_iter().collect())
     }
 
     pub fn parse_key(&self, s: &str) -> Vec<usize> {
@@ -98,6 +86,22 @@ impl Columnar {
         result
     }
 
+    /// key must be a permutation of `1..=key.len()`
+    pub fn check_key(&self, key: &[usize]) -> Result<(), Error> {
+        if key.is_empty() {
+            return Err(Error::InvalidKey("key must not be empty".to_string()));
+        }
+
+        let mut sorted = key.to_vec();
+        sorted.sort();
+
+        if sorted.iter().enumerate().any(|(i, &k)| k != i + 1) {
+            return Err(Error::InvalidKey(format!("{:?} is not a permutation of 1..={}", key, key.len())));
+        }
+
+        Ok(())
+    }
+
     pub fn inverse_key(&self, key: Vec<usize>) -> Vec<usize> {
         let mut result = vec![0; key.len()];
         for (i, &pos) in key.iter().enumerate() {
@@ -110,6 +114,25 @@ impl Columnar {
     
 }
 
+impl Default for Columnar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Cipher for Columnar {
+    /// keyword, its alphabetical order gives the column order
+    type Key = String;
+
+    fn encrypt(&self, text: &str, key: &String) -> Result<String, Error> {
+        self.encrypt(text, key)
+    }
+
+    fn decrypt(&self, text: &str, key: &String) -> Result<String, Error> {
+        self.decrypt(text, key)
+    }
+}
+
 
 
 #[cfg(test)]
@@ -126,11 +149,11 @@ mod tests {
     fn test_encrypt() {
         let columnar = Columnar::new();
         assert_eq!(columnar.encrypt(
-            &"WE ARE DISCOVERED FLEE AT ONCE QKJEU".replace(' ', ""), "ZEBRAS"), 
+            &"WE ARE DISCOVERED FLEE AT ONCE QKJEU".replace(' ', ""), "ZEBRAS").unwrap(), 
             "EVLNE ACDTK ESEAQ ROFOJ DEECU WIREE".replace(' ', "")
         );
         assert_eq!(columnar.encrypt(
-            &"WE ARE DISCOVERED FLEE AT ONCE".replace(' ', ""), "ZEBRAS"), 
+            &"WE ARE DISCOVERED FLEE AT ONCE".replace(' ', ""), "ZEBRAS").unwrap(), 
             "EVLNA CDTES EAROF ODEEC WIREE".replace(' ', "")
         );
     }
@@ -139,14 +162,32 @@ mod tests {
     fn test_decrypt() {
         let columnar = Columnar::new();
         assert_eq!(columnar.decrypt(
-            &"EVLNA CDTES EAROF ODEEC WIREE".replace(' ', ""), "ZEBRAS"),
+            &"EVLNA CDTES EAROF ODEEC WIREE".replace(' ', ""), "ZEBRAS").unwrap(),
             "WE ARE DISCOVERED FLEE AT ONCE".replace(' ', "")
         );
 
         assert_eq!(
-            columnar.decrypt("DGDDDAGDDGAFADDFDADVDVFAADVX", "PRIVACY"),
+            columnar.decrypt("DGDDDAGDDGAFADDFDADVDVFAADVX", "PRIVACY").unwrap(),
             "ADDDDDADAGVGADDDAFDGVFVFADDX"
         );
 
+        // irregular grid, no padding
+        assert_eq!(columnar.decrypt("ELHLO", "BA").unwrap(), "HELLO");
+
+        assert!(columnar.decrypt("EVLNACDTES", "").is_err());
+        assert!(columnar.permute_decrypt("EVLNACDTES", vec![1, 1, 3]).is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let columnar = Columnar::new();
+        let text = "WEAREDISCOVEREDFLEEATONCE";
+
+        for key in ["ZEBRAS", "KEY", "CRYPTOGRAPHY", "A"] {
+            for len in 0..=text.len() {
+                let encrypted = columnar.encrypt(&text[..len], key).unwrap();
+                assert_eq!(columnar.decrypt(&encrypted, key).unwrap(), &text[..len], "{} {}", key, len);
+            }
+        }
     }
 }
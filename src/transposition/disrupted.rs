@@ -0,0 +1,174 @@
+/*===================================================================
+ * Disrupted Transposition Cipher
+ * Author: denosauabh
+ * Description: Implementation of the Disrupted (triangular) Columnar Transposition
+ * Reference: https://en.wikipedia.org/wiki/Transposition_cipher#Disrupted_transposition
+
+ * Triangular areas of the grid are blanked out. The first one starts in the top row
+   at the column numbered 1 by the key & runs to the end of the row, every next row it starts
+   one column further right. When it has shrunk to nothing a row is left undisrupted &
+   the next triangle starts at the column numbered 2, then 3, ...
+
+ * The text fills the cells outside the triangles row by row, then the triangles row by row.
+ * The columns are read off in key order, as in the columnar transposition.
+
+    Z E B R A S      6 3 2 4 1 5
+    -----------
+    W E A R # #      W E A R E A
+    E D I S C #      E D I S C T
+    O V E R E D  ->  O V E R E D     ->  ECEC AIEO EDVL RSRN ATDE WEOFE
+    F L # # # #      F L O N C E
+    E                E
+
+ * The grid is irregular, no padding is added.
+*===================================================================*/
+
+use crate::transposition::columnar::Columnar;
+use crate::utils::cipher::Cipher;
+use crate::utils::consts::NULL;
+use crate::utils::error::Error;
+
+pub struct Disrupted {
+    columnar: Columnar,
+}
+
+impl Disrupted {
+    pub fn new() -> Self {
+        Self { columnar: Columnar::new() }
+    }
+
+    pub fn encrypt(&self, text: &str, key: &str) -> Result<String, Error> {
+        let text = text.chars().collect::<Vec<char>>();
+        let (write, read) = self.positions(text.len(), &self.columnar.parse_key(key))?;
+
+        let mut grid = vec![NULL; text.len()];
+        for (&position, &c) in write.iter().zip(text.iter()) {
+            grid[position] = c;
+        }
+
+        Ok(read.iter().map(|&i| grid[i]).collect())
+    }
+
+    pub fn decrypt(&self, text: &str, key: &str) -> Result<String, Error> {
+        let text = text.chars().collect::<Vec<char>>();
+        let (write, read) = self.positions(text.len(), &self.columnar.parse_key(key))?;
+
+        let mut grid = vec![NULL; text.len()];
+        for (&position, &c) in read.iter().zip(text.iter()) {
+            grid[position] = c;
+        }
+
+        Ok(write.iter().map(|&i| grid[i]).collect())
+    }
+
+    /// which cells of the `len` character grid are blanked out by the triangles
+    pub fn disruption(&self, len: usize, key: &[usize]) -> Result<Vec<bool>, Error> {
+        self.columnar.check_key(key)?;
+
+        let cols = key.len();
+        let rows = len.div_ceil(cols);
+        let order = self.columnar.inverse_key(key.to_vec());
+
+        let mut blank = vec![false; len];
+        let (mut row, mut triangle) = (0, 0);
+
+        while row < rows {
+            let start = order[triangle % cols] - 1;
+
+            // the triangle shrinks by one column a row, the row where it is empty is left as is
+            for width in (0..=cols - start).rev() {
+                for c in cols - width..cols {
+                    if let Some(cell) = blank.get_mut(row * cols + c) {
+                        *cell = true;
+                    }
+                }
+
+                row += 1;
+            }
+
+            triangle += 1;
+        }
+
+        Ok(blank)
+    }
+
+    /// positions of the grid in the order the text is written in & in the order it is read off
+    fn positions(&self, len: usize, key: &[usize]) -> Result<(Vec<usize>, Vec<usize>), Error> {
+        let blank = self.disruption(len, key)?;
+
+        let write = (0..len)
+            .filter(|&i| !blank[i])
+            .chain((0..len).filter(|&i| blank[i]))
+            .collect();
+
+        let cols = key.len();
+        let read = self
+            .columnar
+            .inverse_key(key.to_vec())
+            .into_iter()
+            .flat_map(|k| (k - 1..len).step_by(cols))
+            .collect();
+
+        Ok((write, read))
+    }
+}
+
+impl Default for Disrupted {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Cipher for Disrupted {
+    /// keyword, its alphabetical order gives the column order & where the triangles start
+    type Key = String;
+
+    fn encrypt(&self, text: &str, key: &String) -> Result<String, Error> {
+        self.encrypt(text, key)
+    }
+
+    fn decrypt(&self, text: &str, key: &String) -> Result<String, Error> {
+        self.decrypt(text, key)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disruption() {
+        let disrupted = Disrupted::new();
+        let blank = disrupted.disruption(25, &[6, 3, 2, 4, 1, 5]).unwrap();
+
+        let rows = blank
+            .chunks(6)
+            .map(|row| row.iter().map(|&b| if b { '#' } else { '.' }).collect::<String>())
+            .collect::<Vec<String>>();
+
+        assert_eq!(rows, vec!["....##", ".....#", "......", "..####", "."]);
+    }
+
+    #[test]
+    fn test_encrypt() {
+        let disrupted = Disrupted::new();
+        assert_eq!(disrupted.encrypt("WEAREDISCOVEREDFLEEATONCE", "ZEBRAS").unwrap(), "ECECAIEOEDVLRSRNATDEWEOFE");
+        assert_eq!(disrupted.decrypt("ECECAIEOEDVLRSRNATDEWEOFE", "ZEBRAS").unwrap(), "WEAREDISCOVEREDFLEEATONCE");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let disrupted = Disrupted::new();
+        let text = "WE ARE DISCOVERED, FLEE AT ONCE TO THE EAST WALL";
+
+        for key in ["ZEBRAS", "KEY", "A", "CRYPTOGRAPHY"] {
+            for len in 0..=text.len() {
+                let encrypted = disrupted.encrypt(&text[..len], key).unwrap();
+                assert_eq!(disrupted.decrypt(&encrypted, key).unwrap(), &text[..len], "{} {}", key, len);
+            }
+        }
+
+        assert!(disrupted.encrypt(text, "").is_err());
+    }
+}
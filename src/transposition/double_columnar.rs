@@ -0,0 +1,89 @@
+/*===================================================================
+ * Double Columnar Transposition Cipher
+ * Author: denosauabh
+ * Description: Implementation of the Double Columnar Transposition,
+                a columnar transposition of the columnar transposition
+ * Reference: https://en.wikipedia.org/wiki/Transposition_cipher#Double_transposition
+
+ * Used by both sides in WW1 & WW2 (the German "Doppelwürfel", the Allied field cipher),
+ * the second key breaks up the columns of the first one which a single transposition leaves intact.
+ * Both grids are irregular, no padding is added.
+*===================================================================*/
+
+use crate::transposition::columnar::Columnar;
+use crate::utils::cipher::Cipher;
+use crate::utils::error::Error;
+
+pub struct DoubleColumnar {
+    columnar: Columnar,
+}
+
+impl DoubleColumnar {
+    pub fn new() -> Self {
+        Self { columnar: Columnar::new() }
+    }
+
+    /// transposes with `first`, then the result with `second`
+    pub fn encrypt(&self, text: &str, first: &str, second: &str) -> Result<String, Error> {
+        let once = self.columnar.encrypt(text, first)?;
+        self.columnar.encrypt(&once, second)
+    }
+
+    pub fn decrypt(&self, text: &str, first: &str, second: &str) -> Result<String, Error> {
+        let once = self.columnar.decrypt(text, second)?;
+        self.columnar.decrypt(&once, first)
+    }
+}
+
+impl Default for DoubleColumnar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Cipher for DoubleColumnar {
+    /// the first & second keyword
+    type Key = (String, String);
+
+    fn encrypt(&self, text: &str, key: &(String, String)) -> Result<String, Error> {
+        self.encrypt(text, &key.0, &key.1)
+    }
+
+    fn decrypt(&self, text: &str, key: &(String, String)) -> Result<String, Error> {
+        self.decrypt(text, &key.0, &key.1)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt() {
+        let double = DoubleColumnar::new();
+
+        // WEAREDISCOVEREDFLEEATONCE by ZEBRAS -> EVLNACDTESEAROFODEECWIREE
+        //
+        // S T R I P E
+        // E V L N A C
+        // D T E S E A
+        // R O F O D E
+        // E C W I R E
+        // E
+        assert_eq!(double.encrypt("WEAREDISCOVEREDFLEEATONCE", "ZEBRAS", "STRIPE").unwrap(), "CAEENSOIAEDRLEFWEDREEVTOC");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let double = DoubleColumnar::new();
+        let text = "WEAREDISCOVEREDFLEEATONCE";
+
+        for len in 0..=text.len() {
+            let encrypted = double.encrypt(&text[..len], "ZEBRAS", "STRIPE").unwrap();
+            assert_eq!(double.decrypt(&encrypted, "ZEBRAS", "STRIPE").unwrap(), &text[..len]);
+        }
+
+        assert!(double.encrypt(text, "ZEBRAS", "").is_err());
+    }
+}
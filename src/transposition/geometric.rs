@@ -13,11 +13,17 @@
 
         were first listed by Cononel Parker Hitt in his book "US Army Mannual of Cryptography" in WW1
 
+ * The text is written into a grid of `row_len` columns along the route & read off row by row,
+ * decryption writes row by row & reads along the route. The last row is padded with `NULL`.
+
  *===================================================================*/
 
-use crate::utils::consts::NULL;
+use crate::transposition::route::{Corner, Rotation, Route, RouteCipher};
+use crate::utils::cipher::Cipher;
+use crate::utils::error::Error;
 
-enum GeometricTranspositionMethod {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeometricTranspositionMethod {
     Horizontal,
     AlternateHorizontal,
 
@@ -31,78 +37,56 @@ enum GeometricTranspositionMethod {
     SpiralCounterClockwise,
 }
 
-struct Vec2 {
-    x: usize,
-    y: usize,
+impl GeometricTranspositionMethod {
+    /// the write-in route of the method, the text is always read off by rows
+    pub fn route(&self) -> Route {
+        match self {
+            Self::Horizontal => Route::Rows,
+            Self::AlternateHorizontal => Route::AlternateRows,
+
+            Self::Vertical => Route::Columns,
+            Self::AlternateVertical => Route::AlternateColumns,
+
+            Self::Diagonal => Route::Diagonals,
+            Self::AlternateDiagonal => Route::AlternateDiagonals,
+
+            Self::SpiralClockwise => Route::Spiral { corner: Corner::TopLeft, rotation: Rotation::Clockwise },
+            Self::SpiralCounterClockwise => Route::Spiral { corner: Corner::TopLeft, rotation: Rotation::CounterClockwise },
+        }
+    }
 }
 
+/// preset of the `RouteCipher`, written in along the route of the method & read off by rows
 pub struct Geometric {
-    method: GeometricTranspositionMethod,
-    row_len: u32,
+    route: RouteCipher,
 }
 
 impl Geometric {
-    pub fn new (method: GeometricTranspositionMethod, row_len: u32) -> Self {
-        Self { method, row_len }
+    pub fn new (method: GeometricTranspositionMethod, row_len: u32) -> Result<Self, Error> {
+        Ok(Self { route: RouteCipher::new(row_len, method.route(), Route::Rows)? })
     }
 
-    pub fn encrypt(&self, s: &str) -> String {
-        let text = self.parse_plain_text(s).chars().collect::<Vec<char>>();
-
-        let x = self.row_len as usize;
-        let y = text.len() / x;
-        let grid = Vec2 { x, y };
-
-        let mut encrypted: Vec<char> = Vec::new();
-
-        for r in 0..grid.y {
-            for c in 0..grid.x {
-
-                let index = match self.method {
-                    GeometricTranspositionMethod::Horizontal => r * grid.x + c,
-                    GeometricTranspositionMethod::AlternateHorizontal => r * grid.x + if r % 2 == 0 { c } else { grid.x - c - 1 },
-
-                    GeometricTranspositionMethod::Vertical => c * grid.y + r,
-                    GeometricTranspositionMethod::AlternateVertical => c * grid.y + if c % 2 == 0 { r } else { grid.y - r - 1 },
-
-                    GeometricTranspositionMethod::Diagonal => {
-                        todo!()
-                    },
-                    GeometricTranspositionMethod::AlternateDiagonal => {
-                        todo!()
-                    },
-
-                    GeometricTranspositionMethod::SpiralClockwise => {
-                        todo!()
-                    },
-                    GeometricTranspositionMethod::SpiralCounterClockwise => {
-                        todo!()
-                    }
-                };
-
-                encrypted.push(if index < text.len() { text[index] } else { NULL });
-            }
-        }
-
-        encrypted.iter().collect()
+    /// writes the text into the grid along the route, reads it off row by row
+    pub fn encrypt(&self, s: &str) -> Result<String, Error> {
+        self.route.encrypt(s)
     }
 
-    fn calculate_value(&self, row: usize, col: usize) -> usize {
-        let sum = row + col;
-        (sum * (sum + 1)) / (2 + row)
+    /// writes the text into the grid row by row, reads it off along the route
+    pub fn decrypt(&self, s: &str) -> Result<String, Error> {
+        self.route.decrypt(s)
     }
+}
 
-    fn parse_plain_text(&self, s: &str) -> String {
-        let rem = s.len() % self.row_len as usize;
-        let mut s = s.to_string();
+impl Cipher for Geometric {
+    /// the route & row length are part of the constructor
+    type Key = ();
 
-        // Add NULL characters to make the text length a multiple of row_len
-        if rem != 0 {
-            let nulls = self.row_len as usize - rem;
-            s.push_str(&NULL.to_string().repeat(nulls));
-        }
+    fn encrypt(&self, text: &str, _key: &()) -> Result<String, Error> {
+        self.encrypt(text)
+    }
 
-        s
+    fn decrypt(&self, text: &str, _key: &()) -> Result<String, Error> {
+        self.decrypt(text)
     }
 }
 
@@ -110,66 +94,123 @@ impl Geometric {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::consts::NULL;
 
     const TEXT: &str = "ABCDEFGHIJKLMNOPQRSTUVWX";   
 
     #[test]
     fn test_horizontal() {
-        let geometric = Geometric::new(GeometricTranspositionMethod::Horizontal, 6);
-        let encrypted = geometric.encrypt(TEXT);
+        let geometric = Geometric::new(GeometricTranspositionMethod::Horizontal, 6).unwrap();
+        let encrypted = geometric.encrypt(TEXT).unwrap();
         assert_eq!(encrypted, "ABCDEFGHIJKLMNOPQRSTUVWX");
     }
 
     #[test]
     fn test_alternate_horizontal() {
-        let geometric = Geometric::new(GeometricTranspositionMethod::AlternateHorizontal, 6);
-        let encrypted = geometric.encrypt(TEXT);
+        let geometric = Geometric::new(GeometricTranspositionMethod::AlternateHorizontal, 6).unwrap();
+        let encrypted = geometric.encrypt(TEXT).unwrap();
         assert_eq!(encrypted, "ABCDEFLKJIHGMNOPQRXWVUTS");
     }
 
     #[test]
     fn test_vertical() {
-        let geometric = Geometric::new(GeometricTranspositionMethod::Vertical, 6);
-        let encrypted = geometric.encrypt(TEXT);
+        let geometric = Geometric::new(GeometricTranspositionMethod::Vertical, 6).unwrap();
+        let encrypted = geometric.encrypt(TEXT).unwrap();
         assert_eq!(encrypted, "AEIMQUBFJNRVCGKOSWDHLPTX");
     }
 
     #[test]
     fn test_alternate_vertical() {
-        let geometric = Geometric::new(GeometricTranspositionMethod::AlternateVertical, 6);
-        let encrypted = geometric.encrypt(TEXT);
+        let geometric = Geometric::new(GeometricTranspositionMethod::AlternateVertical, 6).unwrap();
+        let encrypted = geometric.encrypt(TEXT).unwrap();
         assert_eq!(encrypted, "AHIPQXBGJORWCFKNSVDELMTU");
     }
 
-    /*
-
     #[test]
     fn test_diagonal() {
-        let geometric = Geometric::new(GeometricTranspositionMethod::Diagonal, 6);
-        let encrypted = geometric.encrypt(TEXT);
+        let geometric = Geometric::new(GeometricTranspositionMethod::Diagonal, 6).unwrap();
+        let encrypted = geometric.encrypt(TEXT).unwrap();
         assert_eq!(encrypted, "ABDGKOCEHLPSFIMQTVJNRUWX");
     }
 
     #[test]
     fn test_alternate_diagonal() {
-        let geometric = Geometric::new(GeometricTranspositionMethod::AlternateDiagonal, 6);
-        let encrypted = geometric.encrypt(TEXT);
+        let geometric = Geometric::new(GeometricTranspositionMethod::AlternateDiagonal, 6).unwrap();
+        let encrypted = geometric.encrypt(TEXT).unwrap();
         assert_eq!(encrypted, "ABFGNOCEHMPUDILQTVJKRSWX");
     }
 
     #[test]
     fn test_spiral_clockwise() {
-        let geometric = Geometric::new(GeometricTranspositionMethod::SpiralClockwise, 6);
-        let encrypted = geometric.encrypt(TEXT);
+        let geometric = Geometric::new(GeometricTranspositionMethod::SpiralClockwise, 6).unwrap();
+        let encrypted = geometric.encrypt(TEXT).unwrap();
         assert_eq!(encrypted, "ABCDEFPQRSTGOXWVUHNMLKJI");
     }
 
     #[test]
     fn test_spiral_counter_clockwise() {
-        let geometric = Geometric::new(GeometricTranspositionMethod::SpiralCounterClockwise, 6);
-        let encrypted = geometric.encrypt(TEXT);
-        assert_eq!(encrypted, "APONMLBQXWVKCRTUJDEFGHI");
+        let geometric = Geometric::new(GeometricTranspositionMethod::SpiralCounterClockwise, 6).unwrap();
+        let encrypted = geometric.encrypt(TEXT).unwrap();
+        assert_eq!(encrypted, "APONMLBQXWVKCRSTUJDEFGHI");
     }
 
-     */
+    #[test]
+    fn test_decrypt() {
+        let routes = [
+            (GeometricTranspositionMethod::Horizontal, "ABCDEFGHIJKLMNOPQRSTUVWX"),
+            (GeometricTranspositionMethod::AlternateHorizontal, "ABCDEFLKJIHGMNOPQRXWVUTS"),
+            (GeometricTranspositionMethod::Vertical, "AEIMQUBFJNRVCGKOSWDHLPTX"),
+            (GeometricTranspositionMethod::AlternateVertical, "AHIPQXBGJORWCFKNSVDELMTU"),
+            (GeometricTranspositionMethod::Diagonal, "ABDGKOCEHLPSFIMQTVJNRUWX"),
+            (GeometricTranspositionMethod::AlternateDiagonal, "ABFGNOCEHMPUDILQTVJKRSWX"),
+            (GeometricTranspositionMethod::SpiralClockwise, "ABCDEFPQRSTGOXWVUHNMLKJI"),
+            (GeometricTranspositionMethod::SpiralCounterClockwise, "APONMLBQXWVKCRSTUJDEFGHI"),
+        ];
+
+        for (method, encrypted) in routes {
+            let geometric = Geometric::new(method, 6).unwrap();
+            assert_eq!(geometric.decrypt(encrypted).unwrap(), TEXT, "{:?}", method);
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let methods = [
+            GeometricTranspositionMethod::Horizontal,
+            GeometricTranspositionMethod::AlternateHorizontal,
+            GeometricTranspositionMethod::Vertical,
+            GeometricTranspositionMethod::AlternateVertical,
+            GeometricTranspositionMethod::Diagonal,
+            GeometricTranspositionMethod::AlternateDiagonal,
+            GeometricTranspositionMethod::SpiralClockwise,
+            GeometricTranspositionMethod::SpiralCounterClockwise,
+        ];
+        let text = "WE ARE DISCOVERED, FLEE AT ONCE";
+
+        for method in methods {
+            // tall, wide & single row/column grids, most of them padded
+            for row_len in 1..=12 {
+                let geometric = Geometric::new(method, row_len).unwrap();
+
+                for len in 0..=text.len() {
+                    let encrypted = geometric.encrypt(&text[..len]).unwrap();
+                    assert_eq!(geometric.decrypt(&encrypted).unwrap(), &text[..len], "{:?} {}", method, row_len);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_padded() {
+        let geometric = Geometric::new(GeometricTranspositionMethod::SpiralClockwise, 3).unwrap();
+
+        // A B C
+        // H � D
+        // G F E
+        let encrypted = geometric.encrypt("ABCDEFGH").unwrap();
+        assert_eq!(encrypted, format!("ABCH{}DGFE", NULL));
+        assert_eq!(geometric.decrypt(&encrypted).unwrap(), "ABCDEFGH");
+
+        assert!(geometric.decrypt("ABCD").is_err());
+    }
 }
@@ -2,4 +2,8 @@ pub mod scytale;
 pub mod rail_fence;
 pub mod geometric;
 pub mod permutation;
-pub mod columnar;
\ No newline at end of file
+pub mod columnar;
+pub mod route;
+pub mod double_columnar;
+pub mod myszkowski;
+pub mod disrupted;
\ No newline at end of file
@@ -0,0 +1,135 @@
+/*===================================================================
+ * Myszkowski Transposition Cipher
+ * Author: denosauabh
+ * Description: Implementation of the Myszkowski Transposition, a columnar transposition
+                for keywords with repeated letters
+ * Reference: https://en.wikipedia.org/wiki/Transposition_cipher#Myszkowski_transposition
+
+ * Columns of the same letter get the same number & are read off together, row by row,
+   left to right:
+
+    T O M A T O      4 3 2 1 4 3
+    -----------
+    W E A R E D
+    I S C O V E     ->  ROFO ACDT EDSEEEAC WEIVRLENE
+    R E D F L E
+    E A T O N C
+    E
+
+ * The grid is irregular, no padding is added.
+*===================================================================*/
+
+use crate::utils::cipher::Cipher;
+use crate::utils::consts::NULL;
+use crate::utils::error::Error;
+
+pub struct Myszkowski {}
+
+impl Myszkowski {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn encrypt(&self, text: &str, key: &str) -> Result<String, Error> {
+        let text = text.chars().collect::<Vec<char>>();
+        let positions = self.positions(text.len(), &self.parse_key(key)?);
+
+        Ok(positions.iter().map(|&i| text[i]).collect())
+    }
+
+    pub fn decrypt(&self, text: &str, key: &str) -> Result<String, Error> {
+        let text = text.chars().collect::<Vec<char>>();
+        let positions = self.positions(text.len(), &self.parse_key(key)?);
+
+        let mut decrypted = vec![NULL; text.len()];
+        for (&position, &c) in positions.iter().zip(text.iter()) {
+            decrypted[position] = c;
+        }
+
+        Ok(decrypted.into_iter().collect())
+    }
+
+    /// dense rank of every letter of the keyword, "TOMATO" -> [4, 3, 2, 1, 4, 3]
+    pub fn parse_key(&self, s: &str) -> Result<Vec<usize>, Error> {
+        if s.is_empty() {
+            return Err(Error::InvalidKey("key must not be empty".to_string()));
+        }
+
+        let mut letters = s.chars().collect::<Vec<char>>();
+        letters.sort();
+        letters.dedup();
+
+        Ok(s.chars().map(|c| letters.binary_search(&c).unwrap_or_default() + 1).collect())
+    }
+
+    /// positions of the text in the order they are read off
+    fn positions(&self, len: usize, key: &[usize]) -> Vec<usize> {
+        let cols = key.len();
+        let rows = len.div_ceil(cols);
+        let ranks = key.iter().max().copied().unwrap_or(0);
+
+        (1..=ranks)
+            .flat_map(|rank| {
+                (0..rows).flat_map(move |r| {
+                    (0..cols)
+                        .filter(move |&c| key[c] == rank)
+                        .map(move |c| r * cols + c)
+                })
+            })
+            .filter(|&i| i < len)
+            .collect()
+    }
+}
+
+impl Default for Myszkowski {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Cipher for Myszkowski {
+    /// keyword, letters may repeat
+    type Key = String;
+
+    fn encrypt(&self, text: &str, key: &String) -> Result<String, Error> {
+        self.encrypt(text, key)
+    }
+
+    fn decrypt(&self, text: &str, key: &String) -> Result<String, Error> {
+        self.decrypt(text, key)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_key() {
+        let myszkowski = Myszkowski::new();
+        assert_eq!(myszkowski.parse_key("TOMATO").unwrap(), vec![4, 3, 2, 1, 4, 3]);
+        assert_eq!(myszkowski.parse_key("ZEBRAS").unwrap(), vec![6, 3, 2, 4, 1, 5]);
+        assert!(myszkowski.parse_key("").is_err());
+    }
+
+    #[test]
+    fn test_encrypt() {
+        let myszkowski = Myszkowski::new();
+        assert_eq!(myszkowski.encrypt("WEAREDISCOVEREDFLEEATONCE", "TOMATO").unwrap(), "ROFOACDTEDSEEEACWEIVRLENE");
+        assert_eq!(myszkowski.decrypt("ROFOACDTEDSEEEACWEIVRLENE", "TOMATO").unwrap(), "WEAREDISCOVEREDFLEEATONCE");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let myszkowski = Myszkowski::new();
+        let text = "WEAREDISCOVEREDFLEEATONCE";
+
+        for key in ["TOMATO", "BANANA", "AAA", "ZEBRAS"] {
+            for len in 0..=text.len() {
+                let encrypted = myszkowski.encrypt(&text[..len], key).unwrap();
+                assert_eq!(myszkowski.decrypt(&encrypted, key).unwrap(), &text[..len], "{} {}", key, len);
+            }
+        }
+    }
+}
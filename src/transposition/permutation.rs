@@ -4,7 +4,10 @@
  * Description: Implementation of simple Permutation cipher
 *===================================================================*/
 
+use crate::transposition::columnar::Columnar;
+use crate::utils::cipher::Cipher;
 use crate::utils::consts::NULL;
+use crate::utils::error::Error;
 
 pub struct Permutation {}
 
@@ -13,21 +16,23 @@ impl Permutation {
         Self {}
     }
 
-    pub fn encrypt(&self, text: &str, key: &str) -> String {
+    pub fn encrypt(&self, text: &str, key: &str) -> Result<String, Error> {
         self.permute(text, self.inverse_key(self.parse_key(key)))
     }
 
-    pub fn decrypt(&self, text: &str, key: &str) -> String {
+    pub fn decrypt(&self, text: &str, key: &str) -> Result<String, Error> {
         self.permute(text, self.parse_key(key))
     }
 
-    pub fn permute(&self, text: &str, key: Vec<usize>) -> String {
+    pub fn permute(&self, text: &str, key: Vec<usize>) -> Result<String, Error> {
         let text: Vec<char> = text.chars().collect();
 
-        if key.len() > text.len() {
-            panic!("Key length must be less than or equal to text length");
+        if key.is_empty() || key.len() > text.len() {
+            return Err(Error::InvalidKey("key length must be between 1 and the text length".to_string()));
         };
 
+        Columnar::new().check_key(&key)?;
+
         let mut encrypted = Vec::new();
 
         for i in 0..text.len() {
@@ -42,7 +47,7 @@ impl Permutation {
             }
         };
 
-        encrypted.iter().collect()
+        Ok(encrypted.iter().collect())
     }
 
     pub fn parse_key(&self, s: &str) -> Vec<usize> {
@@ -71,6 +76,25 @@ impl Permutation {
     
 }
 
+impl Default for Permutation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Cipher for Permutation {
+    /// keyword, its alphabetical order gives the permutation
+    type Key = String;
+
+    fn encrypt(&self, text: &str, key: &String) -> Result<String, Error> {
+        self.encrypt(text, key)
+    }
+
+    fn decrypt(&self, text: &str, key: &String) -> Result<String, Error> {
+        self.decrypt(text, key)
+    }
+}
+
 
 
 #[cfg(test)]
@@ -93,16 +117,25 @@ mod tests {
     #[test]
     fn test_encrypt() {
         let permutation = Permutation::new();
-        assert_eq!(permutation.encrypt("theb", "TALE"), "hbet");
-        assert_eq!(permutation.encrypt("abcdefgh", "MEOW"), "bacdfegh");
-        assert_eq!(permutation.encrypt("abcde", "MEOW"), format!("bacd{}", NULL));
+        assert_eq!(permutation.encrypt("theb", "TALE").unwrap(), "hbet");
+        assert_eq!(permutation.encrypt("abcdefgh", "MEOW").unwrap(), "bacdfegh");
+        assert_eq!(permutation.encrypt("abcde", "MEOW").unwrap(), format!("bacd{}", NULL));
     }
 
     #[test]
     fn test_decrypt() {
         let permutation = Permutation::new();
-        assert_eq!(permutation.decrypt("hbet", "TALE"), "theb");
-        assert_eq!(permutation.decrypt("bacdfegh", "MEOW"), "abcdefgh");
-        assert_eq!(permutation.decrypt(&format!("bacd{}", NULL), "MEOW"), format!("abcd{}", NULL)); // a character gets lost
+        assert_eq!(permutation.decrypt("hbet", "TALE").unwrap(), "theb");
+        assert_eq!(permutation.decrypt("bacdfegh", "MEOW").unwrap(), "abcdefgh");
+        assert_eq!(permutation.decrypt(&format!("bacd{}", NULL), "MEOW").unwrap(), format!("abcd{}", NULL)); // a character gets lost
+    }
+
+    #[test]
+    fn test_invalid_key() {
+        let permutation = Permutation::new();
+        assert_eq!(permutation.permute("abcd", vec![2, 1, 2, 4]), Err(Error::InvalidKey("[2, 1, 2, 4] is not a permutation of 1..=4".to_string())));
+        assert!(permutation.permute("abcd", vec![0, 1]).is_err());
+        assert!(permutation.permute("abcd", vec![1, 3]).is_err());
+        assert!(permutation.permute("ab", vec![1, 2, 3]).is_err());
     }
 }
\ No newline at end of file
@@ -1,127 +1,110 @@
 /*===================================================================
- * Rail Fence Cipher 
+ * Rail Fence Cipher
  * Author: denosauabh
  * Description: Implementation of types Rail Fence Cipher (zigzag cipher)
  * Reference: https://en.wikipedia.org/wiki/Rail_fence_cipher
+
+ * The text is written in a zigzag over the rails & read off rail by rail:
+
+    W . . . E . . . C . . . R . . . U . . . O . . .
+    . E . R . D . S . O . E . E . R . N . T . N . E
+    . . A . . . I . . . V . . . D . . . A . . . C .
+
+    WECRUO ERDSOEERNTNE AIVDAC
+
+ * One zigzag (down & back up) is a period of `2 * (rails - 1)` characters.
+ * With an offset the zigzag starts `offset` positions into the period,
+ * e.g. offset 2 on 3 rails starts on the bottom rail going up.
 *===================================================================*/
 
-use crate::utils::{consts::NULL};
+use crate::utils::{cipher::Cipher, consts::NULL, error::Error};
 
 pub struct RailFence {
     row_len: u32,
+    offset: u32,
+    padded: bool,
 }
 
 impl RailFence {
-    pub fn new(row_len: u32) -> Self {
-        Self { row_len }
+    pub fn new(row_len: u32) -> Result<Self, Error> {
+        Self::with_offset(row_len, 0)
     }
 
-    pub fn encrypt(&self, s: &str) -> String {
-        /*
-         
-         - . . . . . - 
-         . - . . . - .
-         . . - . - . . 
-         . . . - . . . 
-
-         take third line :-
-
-         . . - . - . .
-
-         two hypens are 'charCount`
-
-         first two dots are `beginPad`
-         middle dot is `middlePad`
-         end two dots are `endPad`
-
-         */
-
-        let text = s.chars().collect::<Vec<char>>();
-        
-        let period = 2 * (self.row_len - 1);
-        let _p_usize = period as usize;
-
-        // let grid = vec![self.row_len, (text.len() as u32) / self.row_len];
-        let grid = [self.row_len, text.len() as u32];
-
-        let totalPeriods = text.len() / period as usize;
-
-        println!("totalPeriods: {}", totalPeriods);
-
+    /// `offset` is the starting position in the zigzag period, `0..2 * (row_len - 1)`
+    pub fn with_offset(row_len: u32, offset: u32) -> Result<Self, Error> {
+        if row_len < 2 {
+            return Err(Error::InvalidKey("rail fence needs at least 2 rails".to_string()));
+        }
 
-        let mut encrypted: Vec<char> = Vec::new();
-        
-        for r in 0..grid[0] {
-            let charCount = if r == 0 || r == self.row_len - 1 { 1 } else { 2 };
+        let period = 2 * (row_len - 1);
 
-            let beginPad = r;
-            let _beginPad_usize = beginPad as usize;
+        if offset >= period {
+            return Err(Error::InvalidKey(format!("offset must be less than the period {}", period)));
+        }
 
-            let endPad = if r == 0 { 0 } else { beginPad - 1 };
+        Ok(Self { row_len, offset, padded: false })
+    }
 
-            let middlePad = period - beginPad - endPad - charCount;
-            let _middlePad_usize = middlePad as usize;
+    /// pads the text with `NULL` so the last zigzag is complete, the padding is removed on decryption
+    pub fn padded(mut self) -> Self {
+        self.padded = true;
+        self
+    }
 
-            println!("beginPad: {}, middlePad: {}, endPad: {}, charCount: {}, period: {}", beginPad, middlePad, endPad, charCount, period);
+    pub fn encrypt(&self, s: &str) -> Result<String, Error> {
+        let text = self.parse_plain_text(s).chars().collect::<Vec<char>>();
 
-            for c in 0..grid[1] {
-                // period index
-                let p = c  / period;
+        // stable, so each rail keeps the order of the text
+        let mut positions = (0..text.len()).collect::<Vec<usize>>();
+        positions.sort_by_key(|&i| self.rail(i));
 
-                println!("p: {}, c: {}, p+c: {}, (c % period): {}", p, c, p+c, c % period);
+        Ok(positions.iter().map(|&i| text[i]).collect())
+    }
 
-                match charCount {
-                    1 => {
-                        if (r == 0 && (c % period) != 0) || (c % period) != beginPad {
-                            println!("skipping 1");
-                            continue;
-                        }
-                    },
-                    2 => {
-                        if (c % period) != beginPad && (c % period) != beginPad + middlePad + 1 {
-                            println!("skipping 2");
-                            continue;
-                        }
-                    },
-                    _ => {}
-                }
+    pub fn decrypt(&self, text: &str) -> Result<String, Error> {
+        let text = text.chars().collect::<Vec<char>>();
 
-                let l = p * period;
+        if self.padded && !text.len().is_multiple_of(self.period()) {
+            return Err(Error::MalformedCiphertext(format!("padded length must be a multiple of the period {}", self.period())));
+        }
 
-                match charCount {
-                    1 => {
-                        println!("LLL: {}", l);
-                        encrypted.push(text[(l + beginPad) as usize]);
-                    },
-                    2 => {
+        // the rails are filled in the same order the encryption read them off
+        let mut positions = (0..text.len()).collect::<Vec<usize>>();
+        positions.sort_by_key(|&i| self.rail(i));
 
-                        // so, it only gets added once
-                        if (c % period) == beginPad {
-                            encrypted.push(text[(l + beginPad) as usize]);
-                            encrypted.push(text[(l + beginPad + middlePad + 1) as usize]);
-                        }
-                    },
-                    _ => {}
-                }
-            };
+        let mut decrypted = vec![NULL; text.len()];
+        for (&position, &c) in positions.iter().zip(text.iter()) {
+            decrypted[position] = c;
+        }
 
-        };
+        Ok(decrypted
+            .into_iter()
+            .filter(|&c| !self.padded || c != NULL)
+            .collect())
+    }
 
-        encrypted.iter().collect()
+    fn period(&self) -> usize {
+        2 * (self.row_len as usize - 1)
     }
 
+    /// rail of the i-th character of the text
+    fn rail(&self, i: usize) -> usize {
+        let position = (i + self.offset as usize) % self.period();
 
-    pub fn decrypt(&self, _text: &str) -> String {
-        todo!()
+        if position < self.row_len as usize {
+            position
+        } else {
+            self.period() - position
+        }
     }
 
     fn parse_plain_text(&self, s: &str) -> String {
-        let rem = s.len() % self.row_len as usize;
         let mut s = s.to_string();
+        let rem = s.chars().count() % self.period();
 
-        // Add NULL characters to make the text length a multiple of row_len
-        if rem != 0 {
-            let nulls = self.row_len as usize - rem;
+        // Add NULL characters to complete the last zigzag
+        if self.padded && rem != 0 {
+            let nulls = self.period() - rem;
             s.push_str(&NULL.to_string().repeat(nulls));
         }
 
@@ -129,6 +112,19 @@ impl RailFence {
     }
 }
 
+impl Cipher for RailFence {
+    /// the number of rails is part of the constructor
+    type Key = ();
+
+    fn encrypt(&self, text: &str, _key: &()) -> Result<String, Error> {
+        self.encrypt(text)
+    }
+
+    fn decrypt(&self, text: &str, _key: &()) -> Result<String, Error> {
+        self.decrypt(text)
+    }
+}
+
 
 
 #[cfg(test)]
@@ -137,19 +133,65 @@ mod tests {
 
     #[test]
     fn test_encrypt() {
-        let rail_fence = RailFence::new(3);
-        let encrypted = rail_fence.encrypt(&"WE ARE DISCOVERED RUN AT ONCE".replace(' ', ""));
+        let rail_fence = RailFence::new(3).unwrap();
+        let encrypted = rail_fence.encrypt(&"WE ARE DISCOVERED RUN AT ONCE".replace(' ', "")).unwrap();
         assert_eq!(encrypted, format!("WECRUOERDSOEERNTNEAIVDAC"));
 
-        let rail_fence = RailFence::new(3);
-        assert_eq!(rail_fence.encrypt("HELLO"), format!("HOELL"));
+        let rail_fence = RailFence::new(3).unwrap();
+        assert_eq!(rail_fence.encrypt("HELLO").unwrap(), format!("HOELL"));
+        assert_eq!(rail_fence.encrypt("HEL").unwrap(), format!("HEL"));
+
+    }
+
+    #[test]
+    fn test_decrypt() {
+        let rail_fence = RailFence::new(3).unwrap();
+        let decrypted = rail_fence.decrypt("WECRUOERDSOEERNTNEAIVDAC").unwrap();
+        assert_eq!(decrypted, "WE ARE DISCOVERED RUN AT ONCE".replace(' ', ""));
+
+        assert_eq!(rail_fence.decrypt("HOELL").unwrap(), "HELLO");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let text = "DEFEND THE EAST WALL OF THE CASTLE!";
+
+        for rails in 2..10 {
+            let rail_fence = RailFence::new(rails).unwrap();
+
+            // every length, not only whole zigzags
+            for len in 0..text.len() {
+                let encrypted = rail_fence.encrypt(&text[..len]).unwrap();
+                assert_eq!(rail_fence.decrypt(&encrypted).unwrap(), &text[..len]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_offset() {
+        // starts on the bottom rail, going up
+        let rail_fence = RailFence::with_offset(3, 2).unwrap();
+        assert_eq!(rail_fence.encrypt("HELLOWORLD").unwrap(), "LOELWRDHOL");
+        assert_eq!(rail_fence.decrypt("LOELWRDHOL").unwrap(), "HELLOWORLD");
+
+        for offset in 0..6 {
+            let rail_fence = RailFence::with_offset(4, offset).unwrap();
+            let encrypted = rail_fence.encrypt("WEAREDISCOVEREDRUNATONCE").unwrap();
+            assert_eq!(rail_fence.decrypt(&encrypted).unwrap(), "WEAREDISCOVEREDRUNATONCE");
+        }
 
+        assert!(RailFence::with_offset(3, 4).is_err());
+        assert!(RailFence::new(1).is_err());
     }
 
-    // #[test]
-    // fn test_decrypt() {
-    //     let rail_fence = RailFence::new(3);
-    //     let decrypted = rail_fence.decrypt("WECRUOERDSOEERNTNEAIVDAC");
-    //     assert_eq!(decrypted, "WE ARE DISCOVERED RUN AT ONCE".replace(" ", ""));
-    // }
-}
\ No newline at end of file
+    #[test]
+    fn test_padded() {
+        let rail_fence = RailFence::new(3).unwrap().padded();
+
+        let encrypted = rail_fence.encrypt("HELLO").unwrap();
+        assert_eq!(encrypted, format!("HOEL{}{}L{}", NULL, NULL, NULL));
+        assert_eq!(rail_fence.decrypt(&encrypted).unwrap(), "HELLO");
+
+        assert!(rail_fence.decrypt("HOELL").is_err());
+    }
+}
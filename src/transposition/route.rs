@@ -0,0 +1,351 @@
+/*===================================================================
+ * Author: denosauabh
+ * Description: Implementation of the Route Cipher, a grid transposition where
+                the text is written in along one route & read off along another
+ * Reference: https://en.wikipedia.org/wiki/Transposition_cipher#Route_cipher
+
+ * e.g. written in by rows, read off in a clockwise spiral from the top right corner,
+   on a grid of 4 columns:
+
+    W E A R
+    E D I S     ->  R S E . D E R C E W E A I V O D
+    C O V E
+    R E D .
+
+ * Hitt's geometric transpositions (`transposition::geometric`) are the routes
+ * below written in & read off by rows.
+
+ * The grid has a fixed number of columns, as many rows as the text needs,
+ * the last row is padded with `NULL`.
+*===================================================================*/
+
+use crate::utils::cipher::Cipher;
+use crate::utils::consts::NULL;
+use crate::utils::error::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rotation {
+    Clockwise,
+    CounterClockwise,
+}
+
+/// path over every cell of the grid, (row, column) from the top left
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Route {
+    /// left to right, top to bottom
+    Rows,
+    /// boustrophedon, every other row right to left
+    AlternateRows,
+
+    /// top to bottom, left to right
+    Columns,
+    /// boustrophedon, every other column bottom to top
+    AlternateColumns,
+
+    /// anti-diagonals from the top left corner, each from the top right to the bottom left
+    Diagonals,
+    /// anti-diagonals from the top left corner, alternating direction
+    AlternateDiagonals,
+
+    /// inward spiral starting in a corner
+    Spiral { corner: Corner, rotation: Rotation },
+
+    /// user supplied cells, must visit every cell of the grid exactly once
+    Custom(Vec<(usize, usize)>),
+}
+
+impl Route {
+    /// cells (row, column) of a `rows × cols` grid in the order of the route
+    pub fn cells(&self, rows: usize, cols: usize) -> Result<Vec<(usize, usize)>, Error> {
+        if rows == 0 || cols == 0 {
+            return match self {
+                Route::Custom(cells) if !cells.is_empty() => Err(Error::InvalidKey("route has cells outside of the empty grid".to_string())),
+                _ => Ok(Vec::new()),
+            };
+        }
+
+        Ok(match self {
+            Route::Rows => {
+                (0..rows).flat_map(|r| (0..cols).map(move |c| (r, c))).collect()
+            },
+            Route::AlternateRows => {
+                (0..rows)
+                    .flat_map(|r| (0..cols).map(move |c| (r, if r % 2 == 0 { c } else { cols - c - 1 })))
+                    .collect()
+            },
+
+            Route::Columns => {
+                (0..cols).flat_map(|c| (0..rows).map(move |r| (r, c))).collect()
+            },
+            Route::AlternateColumns => {
+                (0..cols)
+                    .flat_map(|c| (0..rows).map(move |r| (if c % 2 == 0 { r } else { rows - r - 1 }, c)))
+                    .collect()
+            },
+
+            Route::Diagonals | Route::AlternateDiagonals => {
+                let mut cells = Vec::with_capacity(rows * cols);
+
+                for d in 0..rows + cols - 1 {
+                    let mut diagonal = (d.saturating_sub(cols - 1)..=d.min(rows - 1))
+                        .map(|r| (r, d - r))
+                        .collect::<Vec<(usize, usize)>>();
+
+                    if *self == Route::AlternateDiagonals && d % 2 == 0 {
+                        diagonal.reverse();
+                    }
+
+                    cells.extend(diagonal);
+                }
+
+                cells
+            },
+
+            Route::Spiral { corner, rotation } => Self::spiral(rows, cols, *corner, *rotation),
+
+            Route::Custom(cells) => {
+                Self::check(cells, rows, cols)?;
+                cells.clone()
+            },
+        })
+    }
+
+    /// walks straight ahead, turning whenever it would leave the grid or reach a visited cell
+    fn spiral(rows: usize, cols: usize, corner: Corner, rotation: Rotation) -> Vec<(usize, usize)> {
+        // right, down, left, up; clockwise turns go forward in this list
+        const DIRECTIONS: [(isize, isize); 4] = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+
+        let (start, clockwise_direction) = match corner {
+            Corner::TopLeft => ((0, 0), 0),
+            Corner::TopRight => ((0, cols - 1), 1),
+            Corner::BottomRight => ((rows - 1, cols - 1), 2),
+            Corner::BottomLeft => ((rows - 1, 0), 3),
+        };
+
+        let (mut direction, turn) = match rotation {
+            Rotation::Clockwise => (clockwise_direction, 1),
+            Rotation::CounterClockwise => ((clockwise_direction + 1) % 4, 3),
+        };
+
+        let mut visited = vec![vec![false; cols]; rows];
+        let mut cells = Vec::with_capacity(rows * cols);
+        let (mut r, mut c) = start;
+
+        loop {
+            visited[r][c] = true;
+            cells.push((r, c));
+
+            if cells.len() == rows * cols {
+                return cells;
+            }
+
+            let next = |direction: usize| {
+                let (dr, dc) = DIRECTIONS[direction];
+                let (nr, nc) = (r as isize + dr, c as isize + dc);
+
+                (nr >= 0 && nc >= 0 && (nr as usize) < rows && (nc as usize) < cols && !visited[nr as usize][nc as usize])
+                    .then_some((nr as usize, nc as usize))
+            };
+
+            (r, c) = match next(direction) {
+                Some(cell) => cell,
+                None => {
+                    direction = (direction + turn) % 4;
+                    next(direction).expect("spiral has unvisited neighbour")
+                },
+            };
+        }
+    }
+
+    /// every cell of the grid exactly once
+    fn check(cells: &[(usize, usize)], rows: usize, cols: usize) -> Result<(), Error> {
+        if cells.len() != rows * cols {
+            return Err(Error::InvalidKeyLength { expected: rows * cols, found: cells.len() });
+        }
+
+        let mut visited = vec![vec![false; cols]; rows];
+
+        for &(r, c) in cells {
+            if r >= rows || c >= cols {
+                return Err(Error::InvalidKey(format!("cell ({}, {}) is outside of the {}×{} grid", r, c, rows, cols)));
+            }
+            if visited[r][c] {
+                return Err(Error::InvalidKey(format!("cell ({}, {}) is visited twice", r, c)));
+            }
+
+            visited[r][c] = true;
+        }
+
+        Ok(())
+    }
+}
+
+
+pub struct RouteCipher {
+    row_len: u32,
+    write: Route,
+    read: Route,
+}
+
+impl RouteCipher {
+    pub fn new(row_len: u32, write: Route, read: Route) -> Result<Self, Error> {
+        if row_len == 0 {
+            return Err(Error::InvalidKey("row length must be greater than 0".to_string()));
+        }
+
+        Ok(Self { row_len, write, read })
+    }
+
+    /// writes the text into the grid along the write route, reads it off along the read route
+    pub fn encrypt(&self, s: &str) -> Result<String, Error> {
+        let text = self.parse_plain_text(s).chars().collect::<Vec<char>>();
+        let (rows, cols) = (text.len() / self.row_len as usize, self.row_len as usize);
+
+        Self::transpose(&text, &self.write.cells(rows, cols)?, &self.read.cells(rows, cols)?, cols)
+    }
+
+    /// writes the text into the grid along the read route, reads it off along the write route
+    pub fn decrypt(&self, s: &str) -> Result<String, Error> {
+        let text = s.chars().collect::<Vec<char>>();
+
+        if !text.len().is_multiple_of(self.row_len as usize) {
+            return Err(Error::MalformedCiphertext("length must be a multiple of the row length".to_string()));
+        }
+
+        let (rows, cols) = (text.len() / self.row_len as usize, self.row_len as usize);
+        let decrypted = Self::transpose(&text, &self.read.cells(rows, cols)?, &self.write.cells(rows, cols)?, cols)?;
+
+        Ok(decrypted.chars().filter(|&c| c != NULL).collect())
+    }
+
+    fn transpose(text: &[char], write: &[(usize, usize)], read: &[(usize, usize)], cols: usize) -> Result<String, Error> {
+        let mut grid = vec![NULL; text.len()];
+
+        for (&c, &(r, col)) in text.iter().zip(write) {
+            grid[r * cols + col] = c;
+        }
+
+        Ok(read.iter().map(|&(r, c)| grid[r * cols + c]).collect())
+    }
+
+    fn parse_plain_text(&self, s: &str) -> String {
+        let rem = s.chars().count() % self.row_len as usize;
+        let mut s = s.to_string();
+
+        // Add NULL characters to make the text length a multiple of row_len
+        if rem != 0 {
+            let nulls = self.row_len as usize - rem;
+            s.push_str(&NULL.to_string().repeat(nulls));
+        }
+
+        s
+    }
+}
+
+impl Cipher for RouteCipher {
+    /// the routes & row length are part of the constructor
+    type Key = ();
+
+    fn encrypt(&self, text: &str, _key: &()) -> Result<String, Error> {
+        self.encrypt(text)
+    }
+
+    fn decrypt(&self, text: &str, _key: &()) -> Result<String, Error> {
+        self.decrypt(text)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spiral(corner: Corner, rotation: Rotation) -> Route {
+        Route::Spiral { corner, rotation }
+    }
+
+    #[test]
+    fn test_spirals() {
+        // 0 1 2
+        // 3 4 5
+        let cells = |route: Route| route.cells(2, 3).unwrap().iter().map(|&(r, c)| r * 3 + c).collect::<Vec<usize>>();
+
+        assert_eq!(cells(spiral(Corner::TopLeft, Rotation::Clockwise)), vec![0, 1, 2, 5, 4, 3]);
+        assert_eq!(cells(spiral(Corner::TopLeft, Rotation::CounterClockwise)), vec![0, 3, 4, 5, 2, 1]);
+        assert_eq!(cells(spiral(Corner::TopRight, Rotation::Clockwise)), vec![2, 5, 4, 3, 0, 1]);
+        assert_eq!(cells(spiral(Corner::TopRight, Rotation::CounterClockwise)), vec![2, 1, 0, 3, 4, 5]);
+        assert_eq!(cells(spiral(Corner::BottomRight, Rotation::Clockwise)), vec![5, 4, 3, 0, 1, 2]);
+        assert_eq!(cells(spiral(Corner::BottomRight, Rotation::CounterClockwise)), vec![5, 2, 1, 0, 3, 4]);
+        assert_eq!(cells(spiral(Corner::BottomLeft, Rotation::Clockwise)), vec![3, 0, 1, 2, 5, 4]);
+        assert_eq!(cells(spiral(Corner::BottomLeft, Rotation::CounterClockwise)), vec![3, 4, 5, 2, 1, 0]);
+
+        // inward
+        let route = spiral(Corner::TopLeft, Rotation::Clockwise).cells(3, 3).unwrap();
+        assert_eq!(route.last(), Some(&(1, 1)));
+    }
+
+    #[test]
+    fn test_encrypt() {
+        let cipher = RouteCipher::new(4, Route::Rows, spiral(Corner::TopRight, Rotation::Clockwise)).unwrap();
+        let encrypted = cipher.encrypt("WEAREDISCOVERED").unwrap();
+        assert_eq!(encrypted, format!("RSE{}DERCEWEAIVOD", NULL));
+        assert_eq!(cipher.decrypt(&encrypted).unwrap(), "WEAREDISCOVERED");
+
+        // both routes the same, nothing moves
+        let cipher = RouteCipher::new(5, Route::Diagonals, Route::Diagonals).unwrap();
+        assert_eq!(cipher.encrypt("ABCDEFGHIJ").unwrap(), "ABCDEFGHIJ");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let routes = [
+            Route::Rows,
+            Route::AlternateRows,
+            Route::Columns,
+            Route::AlternateColumns,
+            Route::Diagonals,
+            Route::AlternateDiagonals,
+            spiral(Corner::TopLeft, Rotation::Clockwise),
+            spiral(Corner::BottomRight, Rotation::CounterClockwise),
+            spiral(Corner::BottomLeft, Rotation::Clockwise),
+        ];
+        let text = "WE ARE DISCOVERED, FLEE AT ONCE";
+
+        for write in routes.iter() {
+            for read in routes.iter() {
+                for row_len in [1, 4, 7, 40] {
+                    let cipher = RouteCipher::new(row_len, write.clone(), read.clone()).unwrap();
+                    let encrypted = cipher.encrypt(text).unwrap();
+                    assert_eq!(cipher.decrypt(&encrypted).unwrap(), text, "{:?} {:?} {}", write, read, row_len);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_custom() {
+        // read off the 2×2 grid as: bottom right, top left, bottom left, top right
+        let custom = Route::Custom(vec![(1, 1), (0, 0), (1, 0), (0, 1)]);
+        let cipher = RouteCipher::new(2, Route::Rows, custom).unwrap();
+
+        assert_eq!(cipher.encrypt("ABCD").unwrap(), "DACB");
+        assert_eq!(cipher.decrypt("DACB").unwrap(), "ABCD");
+
+        // the custom route only fits a 2×2 grid
+        assert_eq!(cipher.encrypt("ABCDEF"), Err(Error::InvalidKeyLength { expected: 6, found: 4 }));
+
+        let twice = Route::Custom(vec![(0, 0), (0, 0), (1, 0), (0, 1)]);
+        assert!(RouteCipher::new(2, Route::Rows, twice).unwrap().encrypt("ABCD").is_err());
+
+        let outside = Route::Custom(vec![(0, 0), (0, 2), (1, 0), (0, 1)]);
+        assert!(RouteCipher::new(2, Route::Rows, outside).unwrap().encrypt("ABCD").is_err());
+    }
+}
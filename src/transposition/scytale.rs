@@ -4,7 +4,7 @@
  * Reference: https://en.wikipedia.org/wiki/Scytale
 *===================================================================*/
 
-use crate::utils::{consts::NULL};
+use crate::utils::{cipher::Cipher, consts::NULL, error::Error};
 
 struct Vec2 {
     x: usize,
@@ -16,11 +16,15 @@ pub struct Scytale {
 }
 
 impl Scytale {
-    pub fn new(row_len: u32) -> Self {
-        Self { row_len }
+    pub fn new(row_len: u32) -> Result<Self, Error> {
+        if row_len == 0 {
+            return Err(Error::InvalidKey("row length must be greater than 0".to_string()));
+        }
+
+        Ok(Self { row_len })
     }
 
-    pub fn encrypt(&self, s: &str) -> String {
+    pub fn encrypt(&self, s: &str) -> Result<String, Error> {
         let text = self.parse_plain_text(s).chars().collect::<Vec<char>>();
 
         let x = self.row_len as usize;
@@ -36,12 +40,16 @@ impl Scytale {
             }
         }
 
-        encrypted.iter().collect()
+        Ok(encrypted.iter().collect())
     }
 
-    pub fn decrypt(&self, text: &str) -> String {
+    pub fn decrypt(&self, text: &str) -> Result<String, Error> {
         let text = text.chars().collect::<Vec<char>>();
 
+        if !text.len().is_multiple_of(self.row_len as usize) {
+            return Err(Error::MalformedCiphertext("length must be a multiple of the row length".to_string()));
+        }
+
         let x = self.row_len as usize;
         let y = text.len() / x;
         let grid = Vec2 { x, y }; 
@@ -58,11 +66,11 @@ impl Scytale {
             }
         }
 
-        decrypted.iter().collect()
+        Ok(decrypted.iter().collect())
     }
 
     fn parse_plain_text(&self, s: &str) -> String {
-        let rem = s.len() % self.row_len as usize;
+        let rem = s.chars().count() % self.row_len as usize;
         let mut s = s.to_string();
 
         // Add NULL characters to make the text length a multiple of row_len
@@ -75,6 +83,19 @@ impl Scytale {
     }
 }
 
+impl Cipher for Scytale {
+    /// the number of rows is part of the constructor
+    type Key = ();
+
+    fn encrypt(&self, text: &str, _key: &()) -> Result<String, Error> {
+        self.encrypt(text)
+    }
+
+    fn decrypt(&self, text: &str, _key: &()) -> Result<String, Error> {
+        self.decrypt(text)
+    }
+}
+
 
 
 #[cfg(test)]
@@ -83,25 +104,28 @@ mod tests {
 
     #[test]
     fn test_parse_plain_text() {
-        let scytale = Scytale::new(5);
+        let scytale = Scytale::new(5).unwrap();
         let parsed = scytale.parse_plain_text("HELLO WORLD!");
         assert_eq!(parsed, format!("HELLO WORLD!{}{}{}", NULL, NULL, NULL));
     }
 
     #[test]
     fn test_encrypt() {
-        let scytale = Scytale::new(3);
-        let encrypted = scytale.encrypt("HELLO WORLD");
+        let scytale = Scytale::new(3).unwrap();
+        let encrypted = scytale.encrypt("HELLO WORLD").unwrap();
         assert_eq!(encrypted, format!("HLWLEOODL R{}", NULL));
 
-        let scytale = Scytale::new(4);
-        assert_eq!(scytale.encrypt("I am hurt very badly help"), format!("I tra p h ydh{}auv le�mrebyl{}", NULL, NULL));
+        let scytale = Scytale::new(4).unwrap();
+        assert_eq!(scytale.encrypt("I am hurt very badly help").unwrap(), format!("I tra p h ydh{}auv le�mrebyl{}", NULL, NULL));
     }
 
     #[test]
     fn test_decrypt() {
-        let scytale = Scytale::new(3);
-        let decrypted = scytale.decrypt(format!("HLWLEOODL R{}", NULL).as_str());
+        let scytale = Scytale::new(3).unwrap();
+        let decrypted = scytale.decrypt(format!("HLWLEOODL R{}", NULL).as_str()).unwrap();
         assert_eq!(decrypted, "HELLO WORLD");
+
+        assert!(scytale.decrypt("HLWLEOODL R").is_err());
+        assert!(Scytale::new(0).is_err());
     }
 }
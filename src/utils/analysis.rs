@@ -0,0 +1,387 @@
+/*===================================================================
+ * Author: denosauabh
+ * Description: Frequency analysis & statistics of text over a `CharSet`,
+                the first step of breaking any of the classical ciphers.
+ * Reference: https://en.wikipedia.org/wiki/Frequency_analysis
+              https://en.wikipedia.org/wiki/Index_of_coincidence
+              https://en.wikipedia.org/wiki/Letter_frequency
+
+ * Characters which aren't part of the charset (spaces, punctuation, ...) are skipped,
+ * so n-grams are taken over the text with those characters removed.
+
+ * Formula:
+
+ `fitness = -Σ log10 P(ngram) / #ngrams` (unseen n-grams get a small floor probability)
+ `IoC = Σ n_i * (n_i - 1) / (N * (N - 1))`
+ `χ²  = Σ (n_i - N * p_i)² / (N * p_i)`
+ `H   = -Σ f_i * log2(f_i)`
+
+*===================================================================*/
+
+use std::collections::HashMap;
+
+use crate::utils::char_set::CharSet;
+use crate::utils::consts::ENGLISH_SAMPLE;
+use crate::utils::error::Error;
+
+/// relative frequency of every character of a charset (in charset order), e.g. of a language
+#[derive(Clone, Debug)]
+pub struct Frequencies {
+    char_set: CharSet,
+    values: Vec<f64>,
+}
+
+impl Frequencies {
+    /// English letter frequencies, a..z
+    const ENGLISH: [f64; 26] = [
+        0.08167, 0.01492, 0.02782, 0.04253, 0.12702, 0.02228, 0.02015, 0.06094, 0.06966, 0.00153,
+        0.00772, 0.04025, 0.02406, 0.06749, 0.07507, 0.01929, 0.00095, 0.05987, 0.06327, 0.09056,
+        0.02758, 0.00978, 0.02360, 0.00150, 0.01974, 0.00074,
+    ];
+
+    /// `values` are normalized to sum up to 1
+    pub fn new(char_set: CharSet, values: Vec<f64>) -> Result<Self, Error> {
+        if values.len() != char_set.len() {
+            return Err(Error::InvalidCharSet(format!("{} frequencies for a charset of length {}", values.len(), char_set.len())));
+        }
+
+        let total = values.iter().sum::<f64>();
+
+        if values.iter().any(|&v| v < 0.0 || !v.is_finite()) || total <= 0.0 {
+            return Err(Error::InvalidCharSet("frequencies must be non negative & not all zero".to_string()));
+        }
+
+        Ok(Self { char_set, values: values.iter().map(|v| v / total).collect() })
+    }
+
+    pub fn english() -> Self {
+        Self { char_set: CharSet::from_alphabet_lowercase(), values: Self::ENGLISH.to_vec() }
+    }
+
+    pub fn english_uppercase() -> Self {
+        Self { char_set: CharSet::from_range('A', 'Z'), values: Self::ENGLISH.to_vec() }
+    }
+
+    /// reference frequencies measured on a sample text (corpus) of the language
+    pub fn from_text(text: &str, char_set: CharSet) -> Result<Self, Error> {
+        let values = Analysis::counts(text, &char_set).iter().map(|&n| n as f64).collect();
+        Self::new(char_set, values)
+    }
+
+    pub fn char_set(&self) -> &CharSet {
+        &self.char_set
+    }
+
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// frequency of `c`, 0 if it isn't part of the charset
+    pub fn of(&self, c: char) -> f64 {
+        self.char_set.index_of(c).map_or(0.0, |i| self.values[i])
+    }
+
+    /// index of coincidence of the language, `Σ p_i²`
+    pub fn index_of_coincidence(&self) -> f64 {
+        self.values.iter().map(|p| p * p).sum()
+    }
+}
+
+
+/// n-gram log probabilities of a language, measures how much a text looks like it (n-gram fitness)
+#[derive(Clone, Debug)]
+pub struct NgramModel {
+    char_set: CharSet,
+    n: usize,
+    log_probabilities: HashMap<String, f64>,
+    floor: f64,
+}
+
+impl NgramModel {
+    /// trains the model on a sample text (corpus) of the language
+    pub fn from_text(text: &str, char_set: CharSet, n: usize) -> Result<Self, Error> {
+        if n == 0 {
+            return Err(Error::InvalidKey("n-grams must be at least 1 character long".to_string()));
+        }
+
+        let frequencies = Analysis::ngram_frequencies(text, &char_set, n);
+
+        if frequencies.is_empty() {
+            return Err(Error::InvalidCharSet(format!("sample text has no {}-gram of the charset", n)));
+        }
+
+        let total = (Analysis::filter(text, &char_set).len() - n + 1) as f64;
+
+        Ok(Self {
+            char_set,
+            n,
+            log_probabilities: frequencies.into_iter().map(|(ngram, f)| (ngram, f.log10())).collect(),
+            floor: (0.01 / total).log10(),
+        })
+    }
+
+    /// English n-grams over a..z
+    pub fn english(n: usize) -> Result<Self, Error> {
+        Self::from_text(&ENGLISH_SAMPLE.to_lowercase(), CharSet::from_alphabet_lowercase(), n)
+    }
+
+    pub fn char_set(&self) -> &CharSet {
+        &self.char_set
+    }
+
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    /// average negative log probability of the n-grams of the text, the lower the closer to the language.
+    /// Characters outside the charset are tried lowercased, then skipped.
+    /// Infinite for a text shorter than n.
+    pub fn score(&self, text: &str) -> f64 {
+        let chars = text
+            .chars()
+            .filter_map(|c| {
+                if self.char_set.contains(c) {
+                    return Some(c);
+                }
+                c.to_lowercase().next().filter(|&lower| self.char_set.contains(lower))
+            })
+            .collect::<Vec<char>>();
+
+        if chars.len() < self.n {
+            return f64::INFINITY;
+        }
+
+        let ngrams = chars.windows(self.n);
+        let count = ngrams.len() as f64;
+
+        -ngrams
+            .map(|ngram| *self.log_probabilities.get(&ngram.iter().collect::<String>()).unwrap_or(&self.floor))
+            .sum::<f64>() / count
+    }
+}
+
+
+pub struct Analysis;
+
+impl Analysis {
+    /// the characters of the text which are part of the charset
+    pub fn filter(text: &str, char_set: &CharSet) -> Vec<char> {
+        text.chars().filter(|&c| char_set.contains(c)).collect()
+    }
+
+    /// number of occurrences of every character of the charset, in charset order
+    pub fn counts(text: &str, char_set: &CharSet) -> Vec<usize> {
+        let mut counts = vec![0; char_set.len()];
+
+        for c in text.chars() {
+            if let Ok(i) = char_set.index_of(c) {
+                counts[i] += 1;
+            }
+        }
+
+        counts
+    }
+
+    /// relative unigram frequencies, in charset order
+    pub fn frequencies(text: &str, char_set: &CharSet) -> Vec<f64> {
+        let counts = Self::counts(text, char_set);
+        let total = counts.iter().sum::<usize>();
+
+        counts
+            .iter()
+            .map(|&n| if total == 0 { 0.0 } else { n as f64 / total as f64 })
+            .collect()
+    }
+
+    /// relative frequencies of the overlapping n-grams of the text
+    pub fn ngram_frequencies(text: &str, char_set: &CharSet, n: usize) -> HashMap<String, f64> {
+        let chars = Self::filter(text, char_set);
+        let mut frequencies = HashMap::new();
+
+        if n == 0 || chars.len() < n {
+            return frequencies;
+        }
+
+        let total = (chars.len() - n + 1) as f64;
+
+        for ngram in chars.windows(n) {
+            *frequencies.entry(ngram.iter().collect::<String>()).or_insert(0.0) += 1.0 / total;
+        }
+
+        frequencies
+    }
+
+    pub fn bigram_frequencies(text: &str, char_set: &CharSet) -> HashMap<String, f64> {
+        Self::ngram_frequencies(text, char_set, 2)
+    }
+
+    pub fn trigram_frequencies(text: &str, char_set: &CharSet) -> HashMap<String, f64> {
+        Self::ngram_frequencies(text, char_set, 3)
+    }
+
+    /// n-grams sorted from the most to the least frequent (ties alphabetically)
+    pub fn most_frequent(frequencies: &HashMap<String, f64>) -> Vec<(String, f64)> {
+        let mut sorted = frequencies
+            .iter()
+            .map(|(ngram, &f)| (ngram.clone(), f))
+            .collect::<Vec<(String, f64)>>();
+
+        sorted.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        sorted
+    }
+
+    /// probability that two characters drawn from the text are equal,
+    /// ~0.067 for English, ~1/len for random text over the charset
+    pub fn index_of_coincidence(text: &str, char_set: &CharSet) -> f64 {
+        let counts = Self::counts(text, char_set);
+        let total = counts.iter().sum::<usize>();
+
+        if total < 2 {
+            return 0.0;
+        }
+
+        let coincidences = counts.iter().map(|&n| n * n.saturating_sub(1)).sum::<usize>();
+        coincidences as f64 / (total * (total - 1)) as f64
+    }
+
+    /// χ² statistic of the text against the reference distribution, the lower the closer.
+    /// Infinite for text without any character of the reference charset.
+    pub fn chi_squared(text: &str, reference: &Frequencies) -> f64 {
+        let counts = Self::counts(text, reference.char_set());
+        let total = counts.iter().sum::<usize>() as f64;
+
+        if total == 0.0 {
+            return f64::INFINITY;
+        }
+
+        counts
+            .iter()
+            .zip(reference.values())
+            .filter(|&(_, &p)| p > 0.0)
+            .map(|(&n, &p)| {
+                let expected = total * p;
+                (n as f64 - expected).powi(2) / expected
+            })
+            .sum()
+    }
+
+    /// Shannon entropy of the unigram distribution, in bits per character
+    pub fn entropy(text: &str, char_set: &CharSet) -> f64 {
+        Self::frequencies(text, char_set)
+            .iter()
+            .filter(|&&f| f > 0.0)
+            .map(|&f| -f * f.log2())
+            .sum()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENGLISH_TEXT: &str = "it was the best of times it was the worst of times it was the age of wisdom \
+        it was the age of foolishness it was the epoch of belief it was the epoch of incredulity \
+        it was the season of light it was the season of darkness it was the spring of hope";
+
+    #[test]
+    fn test_frequencies() {
+        let char_set = CharSet::from_string("abc");
+
+        assert_eq!(Analysis::counts("abca b!", &char_set), vec![2, 2, 1]);
+        assert_eq!(Analysis::frequencies("aabc", &char_set), vec![0.5, 0.25, 0.25]);
+        assert_eq!(Analysis::frequencies("", &char_set), vec![0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_ngram_frequencies() {
+        let char_set = CharSet::from_alphabet_lowercase();
+
+        let bigrams = Analysis::bigram_frequencies("ab ab ab", &char_set);
+        assert_eq!(bigrams.len(), 2);
+        assert!((bigrams["ab"] - 3.0 / 5.0).abs() < 1e-9);
+        assert!((bigrams["ba"] - 2.0 / 5.0).abs() < 1e-9);
+
+        let trigrams = Analysis::trigram_frequencies(ENGLISH_TEXT, &char_set);
+        let top = Analysis::most_frequent(&trigrams);
+        assert_eq!(top[0].0, "ast"); // "it was the" -> "itwasthe"
+        assert!(top.iter().take(5).any(|(trigram, _)| trigram == "the"));
+
+        assert!(Analysis::trigram_frequencies("ab", &char_set).is_empty());
+    }
+
+    #[test]
+    fn test_index_of_coincidence() {
+        let char_set = CharSet::from_alphabet_lowercase();
+
+        let ioc = Analysis::index_of_coincidence(ENGLISH_TEXT, &char_set);
+        assert!(ioc > 0.06 && ioc < 0.08, "{}", ioc);
+
+        let uniform = "abcdefghijklmnopqrstuvwxyz".repeat(10);
+        let ioc = Analysis::index_of_coincidence(&uniform, &char_set);
+        assert!(ioc < 0.04, "{}", ioc);
+
+        assert_eq!(Analysis::index_of_coincidence("aaaa", &char_set), 1.0);
+        assert_eq!(Analysis::index_of_coincidence("a", &char_set), 0.0);
+
+        assert!((Frequencies::english().index_of_coincidence() - 0.0655).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_chi_squared() {
+        let english = Frequencies::english();
+        let shifted = ENGLISH_TEXT
+            .chars()
+            .map(|c| if c.is_ascii_lowercase() { (((c as u8 - b'a' + 3) % 26) + b'a') as char } else { c })
+            .collect::<String>();
+
+        assert!(Analysis::chi_squared(ENGLISH_TEXT, &english) < Analysis::chi_squared(&shifted, &english));
+        assert!(Analysis::chi_squared(&ENGLISH_TEXT.to_uppercase(), &Frequencies::english_uppercase()) < 100.0);
+        assert_eq!(Analysis::chi_squared("123", &english), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_entropy() {
+        let char_set = CharSet::from_alphabet_lowercase();
+
+        assert_eq!(Analysis::entropy("aaaa", &char_set), 0.0);
+        assert_eq!(Analysis::entropy("abab", &char_set), 1.0);
+        assert!((Analysis::entropy(&"abcdefghijklmnopqrstuvwxyz".repeat(2), &char_set) - 26f64.log2()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_ngram_model() {
+        let model = NgramModel::english(3).unwrap();
+        assert_eq!(model.n(), 3);
+
+        let english = model.score("meet me at the usual place at ten rather than eight");
+        let shifted = model.score("phhw ph dw wkh xvxdo sodfh dw whq udwkhu wkdq hljkw");
+        let random = model.score("xqzjvkwpqzxjkvbqwz");
+
+        assert!(english < shifted && shifted < random, "{} {} {}", english, shifted, random);
+
+        // uppercase is scored like lowercase, other characters are skipped
+        assert_eq!(model.score("Meet me, AT ten!"), model.score("meetmeatten"));
+        assert_eq!(model.score("ab"), f64::INFINITY);
+
+        assert!(NgramModel::english(0).is_err());
+        assert!(NgramModel::from_text("abc", CharSet::from_numbers(), 2).is_err());
+    }
+
+    #[test]
+    fn test_custom_frequencies() {
+        let char_set = CharSet::from_string("01");
+        let reference = Frequencies::new(char_set.clone(), vec![3.0, 1.0]).unwrap();
+        assert_eq!(reference.values(), &[0.75, 0.25]);
+        assert_eq!(reference.of('0'), 0.75);
+        assert_eq!(reference.of('x'), 0.0);
+
+        let measured = Frequencies::from_text("0001", char_set.clone()).unwrap();
+        assert_eq!(measured.values(), reference.values());
+        assert_eq!(Analysis::chi_squared("00010001", &reference), 0.0);
+
+        assert!(Frequencies::new(char_set.clone(), vec![1.0]).is_err());
+        assert!(Frequencies::new(char_set.clone(), vec![0.0, 0.0]).is_err());
+        assert!(Frequencies::from_text("abc", char_set).is_err());
+    }
+}
@@ -0,0 +1,691 @@
+/*===================================================================
+ * Author: denosauabh
+ * Description: Arbitrary precision unsigned integer for the public-key ciphers
+ * Reference: Knuth, The Art of Computer Programming Vol. 2, 4.3.1 (Algorithm D)
+              https://en.wikipedia.org/wiki/Montgomery_modular_multiplication
+
+ * Little-endian 64-bit limbs without leading zero limbs, so 0 has no limbs at all.
+ * Multiplication is schoolbook, division is Knuth's Algorithm D, modular exponentiation
+   with an odd modulus (every RSA / Diffie-Hellman modulus) runs in Montgomery form,
+   which needs no division per step. Fast enough for 4096-bit parameters, not constant time.
+ *===================================================================*/
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Rem, Shl, Shr, Sub};
+use std::str::FromStr;
+
+use rand::Rng;
+
+use crate::utils::error::Error;
+use crate::utils::integer::Integer;
+
+#[derive(Clone, PartialEq, Eq, Hash, Default)]
+pub struct BigUint {
+    limbs: Vec<u64>,
+}
+
+impl BigUint {
+    pub fn new() -> Self {
+        Self { limbs: Vec::new() }
+    }
+
+    fn from_limbs(limbs: Vec<u64>) -> Self {
+        let mut n = Self { limbs };
+        n.normalize();
+        n
+    }
+
+    fn normalize(&mut self) {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+    }
+
+    /// big-endian bytes, leading zeros are fine
+    pub fn from_bytes_be(bytes: &[u8]) -> Self {
+        let limbs = bytes
+            .rchunks(8)
+            .map(|chunk| chunk.iter().fold(0u64, |limb, &b| (limb << 8) | b as u64))
+            .collect();
+
+        Self::from_limbs(limbs)
+    }
+
+    /// big-endian bytes without leading zeros, empty for 0
+    pub fn to_bytes_be(&self) -> Vec<u8> {
+        let bytes = self.limbs.iter().rev().flat_map(|limb| limb.to_be_bytes()).collect::<Vec<u8>>();
+        let zeros = bytes.iter().take_while(|&&b| b == 0).count();
+
+        bytes[zeros..].to_vec()
+    }
+
+    /// digits in base 2..=36, `_` separators are skipped
+    pub fn from_str_radix(s: &str, radix: u32) -> Result<Self, Error> {
+        if !(2..=36).contains(&radix) {
+            return Err(Error::Unsupported(format!("radix {}", radix)));
+        }
+
+        let digits = s.chars().filter(|&c| c != '_').collect::<Vec<char>>();
+
+        if digits.is_empty() {
+            return Err(Error::MalformedCiphertext("empty number".to_string()));
+        }
+
+        let mut n = Self::new();
+
+        for (i, c) in digits.into_iter().enumerate() {
+            let digit = c.to_digit(radix).ok_or(Error::UnknownCharacter { character: c, position: Some(i) })?;
+            n = n.mul_small(radix as u64).add_small(digit as u64);
+        }
+
+        Ok(n)
+    }
+
+    /// digits in base 2..=36, lowercase
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        assert!((2..=36).contains(&radix), "radix {} isn't in 2..=36", radix);
+
+        if self.is_zero() {
+            return "0".to_string();
+        }
+
+        let mut digits = Vec::new();
+        let mut n = self.clone();
+
+        while !n.is_zero() {
+            let (q, r) = n.divmod_small(radix as u64);
+            digits.push(char::from_digit(r as u32, radix).expect("remainder is below the radix"));
+            n = q;
+        }
+
+        digits.iter().rev().collect()
+    }
+
+    /// `self - other`, `None` if it would be negative
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        if self < other {
+            return None;
+        }
+
+        let mut limbs = self.limbs.clone();
+        let mut borrow = false;
+
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let o = other.limbs.get(i).copied().unwrap_or(0);
+
+            if o == 0 && !borrow && i >= other.limbs.len() {
+                break;
+            }
+
+            let (d, b1) = limb.overflowing_sub(o);
+            let (d, b2) = d.overflowing_sub(borrow as u64);
+            *limb = d;
+            borrow = b1 || b2;
+        }
+
+        Some(Self::from_limbs(limbs))
+    }
+
+    /// (quotient, remainder), panics on division by zero like the primitive types
+    pub fn divmod(&self, divisor: &Self) -> (Self, Self) {
+        assert!(!divisor.is_zero(), "attempt to divide by zero");
+
+        if self < divisor {
+            return (Self::new(), self.clone());
+        }
+        if divisor.limbs.len() == 1 {
+            let (q, r) = self.divmod_small(divisor.limbs[0]);
+            return (q, Self::from(r));
+        }
+
+        // D1. normalize, the top limb of the divisor gets its highest bit set
+        let shift = divisor.limbs.last().expect("divisor isn't zero").leading_zeros() as usize;
+        let v = (divisor << shift).limbs;
+        let mut u = (self << shift).limbs;
+        u.resize(self.limbs.len() + 1, 0);
+
+        let n = v.len();
+        let m = u.len() - n - 1;
+        let mut q = vec![0u64; m + 1];
+
+        let (v1, v2) = (v[n - 1] as u128, v[n - 2] as u128);
+
+        for j in (0..=m).rev() {
+            // D3. estimate the quotient limb from the top two limbs
+            let top = ((u[j + n] as u128) << 64) | u[j + n - 1] as u128;
+            let mut qhat = top / v1;
+            let mut rhat = top % v1;
+
+            while qhat >> 64 != 0 || qhat * v2 > ((rhat << 64) | u[j + n - 2] as u128) {
+                qhat -= 1;
+                rhat += v1;
+
+                if rhat >> 64 != 0 {
+                    break;
+                }
+            }
+
+            // D4. multiply & subtract
+            let mut borrow = 0i128;
+            let mut carry = 0u128;
+
+            for i in 0..n {
+                let p = qhat * v[i] as u128 + carry;
+                carry = p >> 64;
+
+                let t = u[i + j] as i128 - borrow - (p as u64) as i128;
+                u[i + j] = t as u64;
+                borrow = (t < 0) as i128;
+            }
+
+            let t = u[j + n] as i128 - borrow - carry as i128;
+            u[j + n] = t as u64;
+
+            // D6. the estimate was one too large, add back
+            if t < 0 {
+                qhat -= 1;
+                let mut carry = 0u128;
+
+                for i in 0..n {
+                    let s = u[i + j] as u128 + v[i] as u128 + carry;
+                    u[i + j] = s as u64;
+                    carry = s >> 64;
+                }
+
+                u[j + n] = u[j + n].wrapping_add(carry as u64);
+            }
+
+            q[j] = qhat as u64;
+        }
+
+        // D8. unnormalize the remainder
+        u.truncate(n);
+        (Self::from_limbs(q), Self::from_limbs(u) >> shift)
+    }
+
+    fn divmod_small(&self, divisor: u64) -> (Self, u64) {
+        let mut q = vec![0u64; self.limbs.len()];
+        let mut r = 0u128;
+
+        for i in (0..self.limbs.len()).rev() {
+            let cur = (r << 64) | self.limbs[i] as u128;
+            q[i] = (cur / divisor as u128) as u64;
+            r = cur % divisor as u128;
+        }
+
+        (Self::from_limbs(q), r as u64)
+    }
+
+    fn mul_small(&self, m: u64) -> Self {
+        let mut limbs = Vec::with_capacity(self.limbs.len() + 1);
+        let mut carry = 0u128;
+
+        for &limb in &self.limbs {
+            let p = limb as u128 * m as u128 + carry;
+            limbs.push(p as u64);
+            carry = p >> 64;
+        }
+
+        limbs.push(carry as u64);
+        Self::from_limbs(limbs)
+    }
+
+    fn add_small(&self, a: u64) -> Self {
+        self + &Self::from(a)
+    }
+
+    fn add_ref(&self, other: &Self) -> Self {
+        let (long, short) = if self.limbs.len() >= other.limbs.len() { (self, other) } else { (other, self) };
+
+        let mut limbs = Vec::with_capacity(long.limbs.len() + 1);
+        let mut carry = false;
+
+        for (i, &limb) in long.limbs.iter().enumerate() {
+            let (s, c1) = limb.overflowing_add(short.limbs.get(i).copied().unwrap_or(0));
+            let (s, c2) = s.overflowing_add(carry as u64);
+            limbs.push(s);
+            carry = c1 || c2;
+        }
+
+        limbs.push(carry as u64);
+        Self::from_limbs(limbs)
+    }
+
+    fn mul_ref(&self, other: &Self) -> Self {
+        if self.is_zero() || other.is_zero() {
+            return Self::new();
+        }
+
+        let mut limbs = vec![0u64; self.limbs.len() + other.limbs.len()];
+
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u128;
+
+            for (j, &b) in other.limbs.iter().enumerate() {
+                let t = a as u128 * b as u128 + limbs[i + j] as u128 + carry;
+                limbs[i + j] = t as u64;
+                carry = t >> 64;
+            }
+
+            limbs[i + other.limbs.len()] = carry as u64;
+        }
+
+        Self::from_limbs(limbs)
+    }
+
+    /// Montgomery exponentiation for an odd modulus, R = 2^(64 · limbs)
+    fn pow_mod_odd(&self, e: &Self, m: &Self) -> Self {
+        let n = m.limbs.len();
+
+        // -m⁻¹ mod 2⁶⁴ by Newton's iteration, every step doubles the correct bits
+        let mut inv = 1u64;
+        for _ in 0..6 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(m.limbs[0].wrapping_mul(inv)));
+        }
+        let m_inv = inv.wrapping_neg();
+
+        let r = &(&BigUint::one() << (64 * n)) % m;
+        let to_montgomery = |x: &BigUint| &(x << (64 * n)) % m;
+
+        let base = to_montgomery(&(self % m));
+        let mut result = r;
+
+        for i in (0..e.bits()).rev() {
+            result = Self::montgomery_mul(&result, &result, m, m_inv);
+
+            if e.bit(i) {
+                result = Self::montgomery_mul(&result, &base, m, m_inv);
+            }
+        }
+
+        // out of Montgomery form, result · R⁻¹
+        Self::montgomery_mul(&result, &BigUint::one(), m, m_inv)
+    }
+
+    /// `a · b · R⁻¹ mod m` (CIOS), `a` & `b` below `m`
+    #[allow(clippy::needless_range_loop)]
+    fn montgomery_mul(a: &Self, b: &Self, m: &Self, m_inv: u64) -> Self {
+        let n = m.limbs.len();
+        let mut t = vec![0u64; n + 2];
+
+        for i in 0..n {
+            let ai = a.limbs.get(i).copied().unwrap_or(0) as u128;
+            let mut carry = 0u128;
+
+            for j in 0..n {
+                let s = t[j] as u128 + ai * b.limbs.get(j).copied().unwrap_or(0) as u128 + carry;
+                t[j] = s as u64;
+                carry = s >> 64;
+            }
+
+            let s = t[n] as u128 + carry;
+            t[n] = s as u64;
+            t[n + 1] = (s >> 64) as u64;
+
+            let q = t[0].wrapping_mul(m_inv) as u128;
+            let mut carry = (t[0] as u128 + q * m.limbs[0] as u128) >> 64;
+
+            for j in 1..n {
+                let s = t[j] as u128 + q * m.limbs[j] as u128 + carry;
+                t[j - 1] = s as u64;
+                carry = s >> 64;
+            }
+
+            let s = t[n] as u128 + carry;
+            t[n - 1] = s as u64;
+            t[n] = t[n + 1] + (s >> 64) as u64;
+            t[n + 1] = 0;
+        }
+
+        let t = Self::from_limbs(t);
+        t.checked_sub(m).unwrap_or(t)
+    }
+}
+
+impl Integer for BigUint {
+    fn zero() -> Self {
+        Self::new()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    fn bits(&self) -> u64 {
+        match self.limbs.last() {
+            Some(top) => self.limbs.len() as u64 * 64 - top.leading_zeros() as u64,
+            None => 0,
+        }
+    }
+
+    fn bit(&self, i: u64) -> bool {
+        self.limbs
+            .get((i / 64) as usize)
+            .is_some_and(|limb| (limb >> (i % 64)) & 1 == 1)
+    }
+
+    fn to_u128(&self) -> Option<u128> {
+        match self.limbs[..] {
+            [] => Some(0),
+            [a] => Some(a as u128),
+            [a, b] => Some(((b as u128) << 64) | a as u128),
+            _ => None,
+        }
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Some(self.mul_ref(other))
+    }
+
+    /// random bits of the length of the bound, rejected until below it
+    fn random_below<R: Rng + ?Sized>(bound: &Self, rng: &mut R) -> Self {
+        assert!(!bound.is_zero(), "cannot sample below 0");
+
+        let bits = bound.bits();
+        let top_mask = if bits.is_multiple_of(64) { u64::MAX } else { (1 << (bits % 64)) - 1 };
+
+        loop {
+            let mut limbs = (0..bound.limbs.len()).map(|_| rng.gen::<u64>()).collect::<Vec<u64>>();
+            *limbs.last_mut().expect("bound isn't zero") &= top_mask;
+
+            let n = Self::from_limbs(limbs);
+            if &n < bound {
+                return n;
+            }
+        }
+    }
+
+    fn mul_mod(&self, other: &Self, m: &Self) -> Self {
+        &self.mul_ref(other) % m
+    }
+
+    fn pow_mod(&self, e: &Self, m: &Self) -> Self {
+        assert!(!m.is_zero(), "attempt to calculate the remainder with a divisor of zero");
+
+        if m.is_odd() && m.limbs.len() > 1 {
+            return self.pow_mod_odd(e, m);
+        }
+
+        let base = self % m;
+        let mut result = &BigUint::one() % m;
+
+        for i in (0..e.bits()).rev() {
+            result = result.mul_mod(&result, m);
+
+            if e.bit(i) {
+                result = result.mul_mod(&base, m);
+            }
+        }
+
+        result
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(n: u64) -> Self {
+        Self::from_limbs(vec![n])
+    }
+}
+
+impl From<u128> for BigUint {
+    fn from(n: u128) -> Self {
+        Self::from_limbs(vec![n as u64, (n >> 64) as u64])
+    }
+}
+
+/// decimal, or hexadecimal with a `0x` prefix
+impl FromStr for BigUint {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        match s.strip_prefix("0x") {
+            Some(hex) => Self::from_str_radix(hex, 16),
+            None => Self::from_str_radix(s, 10),
+        }
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad_integral(true, "", &self.to_str_radix(10))
+    }
+}
+
+impl fmt::Debug for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl fmt::LowerHex for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad_integral(true, "0x", &self.to_str_radix(16))
+    }
+}
+
+impl Shl<usize> for &BigUint {
+    type Output = BigUint;
+
+    fn shl(self, shift: usize) -> BigUint {
+        if self.is_zero() {
+            return BigUint::new();
+        }
+
+        let (limbs, bits) = (shift / 64, shift % 64);
+        let mut result = vec![0u64; limbs];
+
+        if bits == 0 {
+            result.extend_from_slice(&self.limbs);
+        } else {
+            let mut carry = 0;
+
+            for &limb in &self.limbs {
+                result.push((limb << bits) | carry);
+                carry = limb >> (64 - bits);
+            }
+
+            result.push(carry);
+        }
+
+        BigUint::from_limbs(result)
+    }
+}
+
+impl Shr<usize> for &BigUint {
+    type Output = BigUint;
+
+    fn shr(self, shift: usize) -> BigUint {
+        let (limbs, bits) = (shift / 64, shift % 64);
+
+        if limbs >= self.limbs.len() {
+            return BigUint::new();
+        }
+
+        let rest = &self.limbs[limbs..];
+        let result = if bits == 0 {
+            rest.to_vec()
+        } else {
+            (0..rest.len())
+                .map(|i| (rest[i] >> bits) | rest.get(i + 1).map_or(0, |&next| next << (64 - bits)))
+                .collect()
+        };
+
+        BigUint::from_limbs(result)
+    }
+}
+
+impl Shl<usize> for BigUint {
+    type Output = BigUint;
+
+    fn shl(self, shift: usize) -> BigUint {
+        &self << shift
+    }
+}
+
+impl Shr<usize> for BigUint {
+    type Output = BigUint;
+
+    fn shr(self, shift: usize) -> BigUint {
+        &self >> shift
+    }
+}
+
+/// `op` for `&a op &b`, `a op b` & `a op &b`
+macro_rules! binary_op {
+    ($trait:ident, $method:ident, |$a:ident, $b:ident| $body:expr) => {
+        impl $trait<&BigUint> for &BigUint {
+            type Output = BigUint;
+
+            fn $method(self, other: &BigUint) -> BigUint {
+                let ($a, $b) = (self, other);
+                $body
+            }
+        }
+
+        impl $trait<BigUint> for BigUint {
+            type Output = BigUint;
+
+            fn $method(self, other: BigUint) -> BigUint {
+                (&self).$method(&other)
+            }
+        }
+
+        impl $trait<&BigUint> for BigUint {
+            type Output = BigUint;
+
+            fn $method(self, other: &BigUint) -> BigUint {
+                (&self).$method(other)
+            }
+        }
+    };
+}
+
+binary_op!(Add, add, |a, b| a.add_ref(b));
+binary_op!(Sub, sub, |a, b| a.checked_sub(b).expect("attempt to subtract with overflow"));
+binary_op!(Mul, mul, |a, b| a.mul_ref(b));
+binary_op!(Div, div, |a, b| a.divmod(b).0);
+binary_op!(Rem, rem, |a, b| a.divmod(b).1);
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn big(s: &str) -> BigUint {
+        s.parse().unwrap()
+    }
+
+    // 1024-bit MODP prime of RFC 2409 (Oakley group 2), 2¹⁰²⁴ - 2⁹⁶⁰ - 1 + 2⁶⁴ · (⌊2⁸⁹⁴ π⌋ + 129093)
+    const P1024: &str = "0xffffffffffffffffc90fdaa22168c234c4c6628b80dc1cd129024e088a67cc74020bbea63b139b22514a08798e3404ddef9519b3cd3a431b302b0a6df25f14374fe1356d6d51c245e485b576625e7ec6f44c42e9a637ed6b0bff5cb6f406b7edee386bfb5a899fa5ae9f24117c4b1fe649286651ece65381ffffffffffffffff";
+
+    #[test]
+    fn test_parse_and_format() {
+        let n = big("123456789012345678901234567890123456789012345678901234567890");
+        assert_eq!(n.to_string(), "123456789012345678901234567890123456789012345678901234567890");
+        assert_eq!(format!("{:x}", big("0xdeadbeef_cafebabe_0123456789abcdef")), "deadbeefcafebabe0123456789abcdef");
+        assert_eq!(format!("{:#x}", big("255")), "0xff");
+        assert_eq!(BigUint::new().to_string(), "0");
+        assert_eq!(big("000042"), BigUint::from(42u64));
+
+        assert_eq!("12a4".parse::<BigUint>(), Err(Error::UnknownCharacter { character: 'a', position: Some(2) }));
+        assert!("".parse::<BigUint>().is_err());
+
+        let bytes = [0u8, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let n = BigUint::from_bytes_be(&bytes);
+        assert_eq!(n, big("0x010203040506070809"));
+        assert_eq!(n.to_bytes_be(), bytes[2..].to_vec());
+        assert!(BigUint::new().to_bytes_be().is_empty());
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = big("0xffffffffffffffffffffffffffffffff");
+        let one = BigUint::one();
+
+        assert_eq!(&a + &one, big("0x100000000000000000000000000000000"));
+        assert_eq!(big("0x100000000000000000000000000000000") - one.clone(), a);
+        assert_eq!(one.checked_sub(&a), None);
+        assert_eq!(&a * &a, big("0xfffffffffffffffffffffffffffffffe00000000000000000000000000000001"));
+        assert_eq!(&a * &BigUint::new(), BigUint::new());
+
+        assert_eq!(&a << 4, big("0xffffffffffffffffffffffffffffffff0"));
+        assert_eq!(&a >> 100, big("0xfffffff"));
+        assert_eq!(&a >> 200, BigUint::new());
+        assert_eq!((a.bits(), a.bit(127), a.bit(128)), (128, true, false));
+        assert_eq!(big("12345678901234567890123456789").to_u128(), Some(12345678901234567890123456789));
+        assert!(BigUint::new() < one && one < a);
+    }
+
+    #[test]
+    fn test_divmod() {
+        let a = big("0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef");
+        let b = big("0xfedcba0987654321fedcba0987654321");
+
+        let (q, r) = a.divmod(&b);
+        assert!(r < b);
+        assert_eq!(&(&q * &b) + &r, a);
+
+        // single limb divisor, dividend below the divisor
+        assert_eq!(big("1000000000000000000000000000000") % big("7"), big("1"));
+        assert_eq!(big("5").divmod(&big("0x10000000000000000")), (BigUint::new(), big("5")));
+
+        // random numbers of 64 up to 4096 bits
+        let mut rng = StdRng::seed_from_u64(7);
+        for bits in [64u64, 128, 1024, 2048, 4096] {
+            let bound = &BigUint::one() << bits as usize;
+
+            for _ in 0..20 {
+                let a = BigUint::random_below(&bound, &mut rng);
+                let b = &BigUint::random_below(&(&bound >> rng.gen_range(0..bits as usize)), &mut rng) + &BigUint::one();
+
+                let (q, r) = a.divmod(&b);
+                assert!(r < b);
+                assert_eq!(&(&q * &b) + &r, a);
+            }
+        }
+    }
+
+    #[test]
+    fn test_pow_mod() {
+        let p = big(P1024);
+
+        // Fermat, a^(p-1) = 1 for a prime p
+        for a in ["2", "3", "123456789123456789123456789"] {
+            assert_eq!(big(a).pow_mod(&(&p - &BigUint::one()), &p), BigUint::one());
+        }
+
+        // an even modulus takes the plain path, both agree with u128
+        let m = 0xfffffffffffffffffffffffffffffffeu128;
+        assert_eq!(BigUint::from(12345u64).pow_mod(&BigUint::from(65537u64), &BigUint::from(m)), BigUint::from(12345u128.pow_mod(&65537, &m)));
+        assert_eq!(big("7").pow_mod(&big("0"), &big("1")), BigUint::new());
+    }
+
+    #[test]
+    fn test_inverse_mod() {
+        let p = big(P1024);
+        let a = big("0xcafebabe_deadbeef_12345678_9abcdef0_0fedcba9_87654321");
+
+        let inverse = a.inverse_mod(&p).unwrap();
+        assert_eq!(a.mul_mod(&inverse, &p), BigUint::one());
+
+        assert_eq!(big("6").inverse_mod(&big("9")), None);
+        assert_eq!(big("65537").gcd(&big("3120")), BigUint::one());
+    }
+}
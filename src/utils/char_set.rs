@@ -8,7 +8,9 @@
 
 use std::collections::HashSet;
 
-#[derive(Clone)]
+use crate::utils::error::Error;
+
+#[derive(Clone, Debug)]
 pub struct CharSet {
     pub chars: Vec<char>,
 }
@@ -28,13 +30,10 @@ impl CharSet {
 
     // https://symbl.cc/en/unicode-table/#arabic-supplement
     pub fn from_unicode(start: u32, end: u32) -> Self {
-        let mut chars = Vec::new();
+        let chars: Vec<char> = (start..end)
+            .filter_map(std::char::from_u32)
+            .collect();
 
-        let mut index: usize = 0;
-        for i in start..end {
-            chars.insert(index, std::char::from_u32(i).unwrap());
-            index += 1;
-        }
         Self { chars }
     }
 
@@ -71,10 +70,9 @@ impl CharSet {
         s.chars().all(|c| self.chars.contains(&c))
     }
 
-    pub fn panic_if_invalid(&self, s: &str) {
-        if !self.validate(s) {
-            panic!("Invalid character in string");
-        }
+    /// like `validate`, but reports the first character which doesn't exist in charset
+    pub fn check(&self, s: &str) -> Result<(), Error> {
+        self.indices_of(s).map(|_| ())
     }
 
     pub fn contains(&self, c: char) -> bool {
@@ -85,6 +83,10 @@ impl CharSet {
         self.chars.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.chars.is_empty()
+    }
+
     pub fn first(&self) -> Option<&char> {
         self.chars.first()
     }
@@ -93,25 +95,41 @@ impl CharSet {
         self.chars.iter().last()
     }
 
-    pub fn index_of(&self, c: char) -> usize {
-        if let Some(index) = self.chars.iter().position(|&x| x == c) {
-            index
-        } else {
-            panic!("character does't exist in charset");
-        }
+    pub fn index_of(&self, c: char) -> Result<usize, Error> {
+        self.chars
+            .iter()
+            .position(|&x| x == c)
+            .ok_or(Error::UnknownCharacter { character: c, position: None })
     }
 
-    pub fn char_at(&self, index: usize) -> char {
-        if let Some(c) = self.chars.get(index).cloned() {
-            c
-        } else {
-            panic!("Index out of bounds");
-        }
+    /// index of every character of the text, errors with the position of the first unknown character
+    pub fn indices_of(&self, s: &str) -> Result<Vec<usize>, Error> {
+        s.chars()
+            .enumerate()
+            .map(|(i, c)| {
+                self.index_of(c).map_err(|_| Error::UnknownCharacter { character: c, position: Some(i) })
+            })
+            .collect()
+    }
+
+    pub fn char_at(&self, index: usize) -> Result<char, Error> {
+        self.chars
+            .get(index)
+            .cloned()
+            .ok_or(Error::IndexOutOfBounds { index, len: self.len() })
+    }
+}
+
+impl Default for CharSet {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 
 // Converter
+/// pairs of charset indices as numbers, "te" -> 1904, the last character of an odd
+/// length text paired with the charset length, "z" -> 2625, so every block is two characters wide.
 pub struct Converter {
     char_set: CharSet,
     pub pad_length: usize,
@@ -124,48 +142,59 @@ impl Converter {
         Self { char_set, pad_length }
     }
 
-    pub fn convert_to_numvec(&self, text: &str) -> Result<Vec<u32>, String> {
-        let mut numstr = Vec::with_capacity(text.len() / 2 + 1);
+    /// the largest block `convert_to_numvec` can give, a modulus must be larger than it
+    pub fn largest_block(&self) -> Result<u64, Error> {
+        let len = self.char_set.len();
 
-        for chunk in text.chars().collect::<Vec<char>>().chunks(2) {
-            let p = if chunk.len() == 2 {
-                let a = self.char_set.index_of(chunk[0]);
-                let b = self.char_set.index_of(chunk[1]);
+        if len == 0 {
+            return Err(Error::InvalidCharSet("an empty charset has no blocks".to_string()));
+        }
 
-                format!("{:0width$}{:0width$}", a, b, width = self.pad_length).parse::<u32>()
-                    .map_err(|e| format!("Failed to parse p: {}", e))?
-            } else {
-                let a = self.char_set.index_of(chunk[0]);
+        format!("{}{:0width$}", len, len - 1, width = self.pad_length).parse::<u64>()
+            .map_err(|_| Error::InvalidCharSet(format!("charset of length {} is too large", len)))
+    }
+
+    /// pairs of indices, the trailing character of an odd length text is paired with
+    /// the charset length, which is never an index, so it can't be mistaken for a pair
+    pub fn convert_to_numvec(&self, text: &str) -> Result<Vec<u32>, Error> {
+        let indices = self.char_set.indices_of(text)?;
+        let mut numstr = Vec::with_capacity(indices.len() / 2 + 1);
 
-                format!("{:0width$}", a, width = self.pad_length * 2).parse::<u32>()
-                    .map_err(|e| format!("Failed to parse p: {}", e))?
-            };
+        for chunk in indices.chunks(2) {
+            let first = chunk.get(1).map_or(self.char_set.len(), |_| chunk[0]);
+            let last = chunk[chunk.len() - 1];
+            let p = format!("{:0width$}{:0width$}", first, last, width = self.pad_length);
 
-            numstr.push(p);
+            numstr.push(p.parse::<u32>().map_err(|_| Error::InvalidCharSet(format!("{} doesn't fit in a u32", p)))?);
         }
 
         Ok(numstr)
     }
 
-    pub fn numvec_to_string(&self, numvec: Vec<u32>) -> String {
+    pub fn numvec_to_string(&self, numvec: Vec<u32>) -> Result<String, Error> {
         let mut text = String::new();
 
-        for p in numvec.iter() {
+        for (i, p) in numvec.iter().enumerate() {
             let p_str = format!("{:0width$}", p, width = self.pad_length * 2);
 
-            let a = p_str[..self.pad_length].parse::<usize>().unwrap();
-
-            if a != 0 {
-                text.push(self.char_set.char_at(a));
+            if p_str.len() != self.pad_length * 2 {
+                return Err(Error::MalformedCiphertext(format!("{} is more than {} digits", p, self.pad_length * 2)));
             }
 
-            if self.pad_length * 2 == p_str.len() {
-                let b = p_str[self.pad_length..].parse::<usize>().unwrap();
-                text.push(self.char_set.char_at(b));
+            // `p_str` only has ascii digits, so these can't fail
+            let a = p_str[..self.pad_length].parse::<usize>().unwrap();
+            let b = p_str[self.pad_length..].parse::<usize>().unwrap();
+
+            // only the last block can be a single character (of an odd length text)
+            if a != self.char_set.len() {
+                text.push(self.char_set.char_at(a)?);
+            } else if i + 1 < numvec.len() {
+                return Err(Error::MalformedCiphertext(format!("{} is a single character before the last block", p)));
             }
+            text.push(self.char_set.char_at(b)?);
         }
 
-        text
+        Ok(text)
     }
 }
 
@@ -232,10 +261,11 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "Invalid character in string")]
-    fn test_panic_if_invalid() {
+    fn test_check() {
         let charset = CharSet::from_range('a', 'z');
-        charset.panic_if_invalid("Hello");
+        assert!(charset.check("hello").is_ok());
+        assert_eq!(charset.check("Hello"), Err(Error::UnknownCharacter { character: 'H', position: Some(0) }));
+        assert_eq!(charset.check("hellO"), Err(Error::UnknownCharacter { character: 'O', position: Some(4) }));
     }
 
     #[test]
@@ -265,36 +295,41 @@ mod tests {
     #[test]
     fn test_index_of() {
         let charset = CharSet::from_string("abcdef");
-        assert_eq!(charset.index_of('a'), 0);
-        assert_eq!(charset.index_of('c'), 2);
+        assert_eq!(charset.index_of('a'), Ok(0));
+        assert_eq!(charset.index_of('c'), Ok(2));
 
         let charset_num = CharSet::from_string("1234567890");
-        assert_eq!(charset_num.index_of('1'), 0);
-        assert_eq!(charset_num.index_of('0'), 9);
+        assert_eq!(charset_num.index_of('1'), Ok(0));
+        assert_eq!(charset_num.index_of('0'), Ok(9));
     }
 
     #[test]
     fn test_char_at() {
         let charset = CharSet::from_string("abcdef");
-        assert_eq!(charset.char_at(1), 'b');
+        assert_eq!(charset.char_at(1), Ok('b'));
 
         let charset_num = CharSet::from_numbers();
-        assert_eq!(charset_num.char_at(1), '1');
-        assert_eq!(charset_num.char_at(9), '9');
+        assert_eq!(charset_num.char_at(1), Ok('1'));
+        assert_eq!(charset_num.char_at(9), Ok('9'));
+    }
+
+    #[test]
+    fn test_char_at_out_of_bounds() {
+        let charset = CharSet::from_string("abcdef");
+        assert_eq!(charset.char_at(10), Err(Error::IndexOutOfBounds { index: 10, len: 6 }));
     }
 
     #[test]
-    #[should_panic(expected = "Index out of bounds")]
-    fn test_char_at_panic() {
+    fn test_index_of_unknown() {
         let charset = CharSet::from_string("abcdef");
-        charset.char_at(10);
+        assert_eq!(charset.index_of('z'), Err(Error::UnknownCharacter { character: 'z', position: None }));
     }
 
     #[test]
-    #[should_panic(expected = "character does't exist in charset")]
-    fn test_index_of_panic() {
+    fn test_indices_of() {
         let charset = CharSet::from_string("abcdef");
-        charset.index_of('z');
+        assert_eq!(charset.indices_of("fade"), Ok(vec![5, 0, 3, 4]));
+        assert_eq!(charset.indices_of("faze"), Err(Error::UnknownCharacter { character: 'z', position: Some(2) }));
     }
 
 
@@ -305,13 +340,15 @@ mod tests {
         let converter = Converter::new(char_set);
         
         let result = converter.convert_to_numvec("tellmethreetimes").unwrap();
-        assert_eq!(result, vec![1904, 1111, 1204, 1907, 1704, 0419, 0812, 0418]);
+        assert_eq!(result, vec![1904, 1111, 1204, 1907, 1704, 419, 812, 418]);
 
         let result = converter.convert_to_numvec("hello").unwrap();
-        assert_eq!(result, vec![0704, 1111, 14]);
+        assert_eq!(result, vec![704, 1111, 2614]);
 
         let result = converter.convert_to_numvec("z").unwrap();
-        assert_eq!(result, vec![25]);
+        assert_eq!(result, vec![2625]);
+        assert_eq!(converter.largest_block(), Ok(2625));
+        assert!(Converter::new(CharSet::from_string("")).largest_block().is_err());
     }
 
     #[test]
@@ -319,14 +356,25 @@ mod tests {
         let char_set = CharSet::from_alphabet_lowercase();
         let converter = Converter::new(char_set);
         
-        let result = converter.numvec_to_string(vec![1904, 1111, 1204, 1907, 1704, 0419, 0812, 0418]);
+        let result = converter.numvec_to_string(vec![1904, 1111, 1204, 1907, 1704, 419, 812, 418]).unwrap();
         assert_eq!(result, "tellmethreetimes");
 
-        let result = converter.numvec_to_string(vec![0704, 1111, 14]);
+        let result = converter.numvec_to_string(vec![704, 1111, 2614]).unwrap();
         assert_eq!(result, "hello");
 
-        let result = converter.numvec_to_string(vec![25]);
+        let result = converter.numvec_to_string(vec![2625]).unwrap();
         assert_eq!(result, "z");
+
+        assert!(converter.numvec_to_string(vec![2727]).is_err());
+        assert!(converter.numvec_to_string(vec![2625, 1]).is_err());
+
+        // a leading 0 is always the character at index 0 (a)
+        assert_eq!(converter.numvec_to_string(vec![1, 2604]).unwrap(), "abe");
+
+        for text in ["flap", "ab", "a", "aa", "aaa", "abe"] {
+            let numvec = converter.convert_to_numvec(text).unwrap();
+            assert_eq!(converter.numvec_to_string(numvec).unwrap(), text);
+        }
     }
 
     #[test]
@@ -0,0 +1,61 @@
+/*===================================================================
+ * Author: denosauabh
+ * Description: Common interface shared by the classical ciphers, so they can
+                be driven generically (pipelines, test harnesses, analysis)
+
+ * Every cipher keeps its own inherent `encrypt` & `decrypt` functions,
+ * the trait only unifies how the key is passed in and how failures are reported.
+ *===================================================================*/
+
+use crate::utils::error::Error;
+
+pub trait Cipher {
+    /// whatever the cipher needs besides the text, e.g. `i32` for Additive, `String` for Vigenère,
+    /// `()` when all of the key material is passed to the constructor
+    type Key;
+
+    fn encrypt(&self, text: &str, key: &Self::Key) -> Result<String, Error>;
+
+    fn decrypt(&self, text: &str, key: &Self::Key) -> Result<String, Error>;
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simple_substitution::additive::AdditiveCipher;
+    use crate::polyalphebatic_substitution::vigenere::VigenèreCipher;
+    use crate::transposition::columnar::Columnar;
+    use crate::utils::char_set::CharSet;
+
+    fn round_trip<C: Cipher>(cipher: &C, text: &str, key: &C::Key) -> String {
+        let encrypted = cipher.encrypt(text, key).unwrap();
+        cipher.decrypt(&encrypted, key).unwrap()
+    }
+
+    #[test]
+    fn test_generic_round_trip() {
+        let additive = AdditiveCipher::new(CharSet::from_alphabet_lowercase());
+        assert_eq!(round_trip(&additive, "hello", &3), "hello");
+
+        let vigenere = VigenèreCipher::new(CharSet::from_alphabet_lowercase());
+        assert_eq!(round_trip(&vigenere, "attackingtonight", &"lemon".to_string()), "attackingtonight");
+
+        let columnar = Columnar::new();
+        assert_eq!(round_trip(&columnar, "WEAREDISCOVEREDFLEEATONCE", &"ZEBRAS".to_string()), "WEAREDISCOVEREDFLEEATONCE");
+    }
+
+    #[test]
+    fn test_pipeline() {
+        // chain two different ciphers only through the trait
+        let additive = AdditiveCipher::new(CharSet::from_alphabet_lowercase());
+        let vigenere = VigenèreCipher::new(CharSet::from_alphabet_lowercase());
+
+        let text = "wasteallyouroil";
+        let step1 = Cipher::encrypt(&additive, text, &5).unwrap();
+        let step2 = Cipher::encrypt(&vigenere, &step1, &"key".to_string()).unwrap();
+
+        let back = Cipher::decrypt(&vigenere, &step2, &"key".to_string()).unwrap();
+        assert_eq!(Cipher::decrypt(&additive, &back, &5).unwrap(), text);
+    }
+}
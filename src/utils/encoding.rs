@@ -0,0 +1,119 @@
+/*===================================================================
+ * Author: denosauabh
+ * Description: Hex & Base64 (RFC 4648) encoding of raw bytes, used to print
+                the output of block ciphers & modes of operation.
+ * Reference: https://datatracker.ietf.org/doc/html/rfc4648
+ *===================================================================*/
+
+use crate::utils::error::Error;
+
+pub struct Encoding;
+
+impl Encoding {
+    const BASE64_ALPHABET: &'static [u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    pub fn hex_encode(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    pub fn hex_decode(text: &str) -> Result<Vec<u8>, Error> {
+        let chars = text.chars().collect::<Vec<char>>();
+
+        if !chars.len().is_multiple_of(2) {
+            return Err(Error::MalformedCiphertext("hex must have an even number of digits".to_string()));
+        }
+
+        chars
+            .chunks(2)
+            .map(|pair| {
+                let pair = pair.iter().collect::<String>();
+                u8::from_str_radix(&pair, 16)
+                    .map_err(|_| Error::MalformedCiphertext(format!("{:?} is not a hex byte", pair)))
+            })
+            .collect()
+    }
+
+    pub fn base64_encode(bytes: &[u8]) -> String {
+        let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+        for chunk in bytes.chunks(3) {
+            // 3 bytes -> 24 bits -> 4 sextets
+            let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+            let n = ((b[0] as u32) << 16) | ((b[1] as u32) << 8) | b[2] as u32;
+
+            for i in 0..4 {
+                if i <= chunk.len() {
+                    let sextet = (n >> (18 - 6 * i)) & 0x3F;
+                    encoded.push(Self::BASE64_ALPHABET[sextet as usize] as char);
+                } else {
+                    encoded.push('=');
+                }
+            }
+        }
+
+        encoded
+    }
+
+    pub fn base64_decode(text: &str) -> Result<Vec<u8>, Error> {
+        let text = text.trim_end_matches('=');
+        let mut decoded = Vec::with_capacity(text.len() * 3 / 4);
+
+        let mut buffer: u32 = 0;
+        let mut bits = 0;
+
+        for c in text.chars() {
+            let sextet = Self::BASE64_ALPHABET
+                .iter()
+                .position(|&x| x as char == c)
+                .ok_or(Error::MalformedCiphertext(format!("{:?} is not a base64 character", c)))?;
+
+            buffer = (buffer << 6) | sextet as u32;
+            bits += 6;
+
+            if bits >= 8 {
+                bits -= 8;
+                decoded.push((buffer >> bits) as u8);
+                buffer &= (1 << bits) - 1;
+            }
+        }
+
+        Ok(decoded)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex() {
+        assert_eq!(Encoding::hex_encode(&[0x00, 0xab, 0x10]), "00ab10");
+        assert_eq!(Encoding::hex_decode("00ab10"), Ok(vec![0x00, 0xab, 0x10]));
+        assert_eq!(Encoding::hex_decode("00AB10"), Ok(vec![0x00, 0xab, 0x10]));
+
+        assert!(Encoding::hex_decode("abc").is_err());
+        assert!(Encoding::hex_decode("zz").is_err());
+    }
+
+    #[test]
+    fn test_base64() {
+        // https://datatracker.ietf.org/doc/html/rfc4648#section-10
+        let vectors = [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ];
+
+        for (plain, encoded) in vectors {
+            assert_eq!(Encoding::base64_encode(plain.as_bytes()), encoded);
+            assert_eq!(Encoding::base64_decode(encoded), Ok(plain.as_bytes().to_vec()));
+        }
+
+        assert!(Encoding::base64_decode("Zm9v!").is_err());
+    }
+}
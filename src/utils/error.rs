@@ -0,0 +1,105 @@
+/*===================================================================
+ * Author: denosauabh
+ * Description: Crate-wide error type, returned by every public encrypt/decrypt/constructor
+                instead of panicking on a bad key or input.
+ *===================================================================*/
+
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// character is not part of the `CharSet`, position is its index in the text (if known)
+    UnknownCharacter { character: char, position: Option<usize> },
+
+    /// index is outside of the `CharSet`
+    IndexOutOfBounds { index: usize, len: usize },
+
+    /// value has no multiplicative inverse modulo `modulus`, e.g. Affine `k` or Hill determinant
+    NonInvertibleKey { value: i128, modulus: i128 },
+
+    /// key doesn't have the length the cipher needs
+    InvalidKeyLength { expected: usize, found: usize },
+
+    /// any other unusable key
+    InvalidKey(String),
+
+    /// known plaintext blocks that don't form an invertible matrix, so no unique key can be solved for
+    DependentBlocks { determinant: i128, modulus: i128 },
+
+    /// fewer known plaintext blocks than it takes to solve for a key
+    NotEnoughBlocks { needed: usize, found: usize },
+
+    /// text length must be a multiple of the block size of the cipher
+    InvalidTextLength { multiple_of: usize, found: usize },
+
+    /// charset (or a combination of charsets) the cipher can't work with
+    InvalidCharSet(String),
+
+    /// ciphertext that couldn't have been produced by the cipher
+    MalformedCiphertext(String),
+
+    /// message longer than the cipher (e.g. RSA with its padding) can take in one block, in bytes
+    MessageTooLong { max: usize, found: usize },
+
+    /// signature that doesn't match the message & public key
+    InvalidSignature,
+
+    /// operation that isn't available (yet) for the given configuration
+    Unsupported(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::UnknownCharacter { character, position: Some(position) } => {
+                write!(f, "character {:?} at position {} doesn't exist in charset", character, position)
+            },
+            Error::UnknownCharacter { character, position: None } => {
+                write!(f, "character {:?} doesn't exist in charset", character)
+            },
+            Error::IndexOutOfBounds { index, len } => {
+                write!(f, "index {} is out of bounds for charset of length {}", index, len)
+            },
+            Error::NonInvertibleKey { value, modulus } => {
+                write!(f, "{} is not invertible modulo {}", value, modulus)
+            },
+            Error::InvalidKeyLength { expected, found } => {
+                write!(f, "invalid key length: expected {}, found {}", expected, found)
+            },
+            Error::InvalidKey(reason) => write!(f, "invalid key: {}", reason),
+            Error::DependentBlocks { determinant, modulus } => {
+                write!(f, "plaintext blocks are not independent modulo {} (determinant {})", modulus, determinant)
+            },
+            Error::NotEnoughBlocks { needed, found } => {
+                write!(f, "not enough blocks: {} needed, found {}", needed, found)
+            },
+            Error::InvalidTextLength { multiple_of, found } => {
+                write!(f, "invalid text length: {} is not a multiple of {}", found, multiple_of)
+            },
+            Error::InvalidCharSet(reason) => write!(f, "invalid charset: {}", reason),
+            Error::MalformedCiphertext(reason) => write!(f, "malformed ciphertext: {}", reason),
+            Error::MessageTooLong { max, found } => {
+                write!(f, "message too long: {} bytes, at most {} fit", found, max)
+            },
+            Error::InvalidSignature => write!(f, "invalid signature"),
+            Error::Unsupported(reason) => write!(f, "unsupported: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let err = Error::UnknownCharacter { character: 'z', position: Some(3) };
+        assert_eq!(err.to_string(), "character 'z' at position 3 doesn't exist in charset");
+
+        let err = Error::NonInvertibleKey { value: 13, modulus: 26 };
+        assert_eq!(err.to_string(), "13 is not invertible modulo 26");
+    }
+}
@@ -0,0 +1,244 @@
+/*===================================================================
+ * Author: denosauabh
+ * Description: Unsigned integers the public-key ciphers are generic over,
+                `u64` & `u128` for toy parameters, `BigUint` for real sized ones
+ *===================================================================*/
+
+use std::fmt::{Debug, Display};
+use std::ops::{Add, Div, Mul, Rem, Sub};
+use std::str::FromStr;
+
+use rand::Rng;
+
+use crate::utils::error::Error;
+
+pub trait Integer:
+    Clone
+    + Ord
+    + Debug
+    + Display
+    + FromStr
+    + From<u64>
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    /// the widest value the type holds in bits, `None` when it grows as needed
+    const MAX_BITS: Option<u64> = None;
+
+    fn zero() -> Self {
+        Self::from(0)
+    }
+
+    fn one() -> Self {
+        Self::from(1)
+    }
+
+    fn is_zero(&self) -> bool {
+        *self == Self::zero()
+    }
+
+    fn is_odd(&self) -> bool {
+        self.bit(0)
+    }
+
+    /// number of significant bits, 0 for 0
+    fn bits(&self) -> u64;
+
+    /// bit `i`, counted from the least significant one
+    fn bit(&self, i: u64) -> bool;
+
+    fn to_u128(&self) -> Option<u128>;
+
+    fn checked_mul(&self, other: &Self) -> Option<Self>;
+
+    /// uniformly random in `0..bound`
+    fn random_below<R: Rng + ?Sized>(bound: &Self, rng: &mut R) -> Self;
+
+    /// `self * other mod m` without overflowing
+    fn mul_mod(&self, other: &Self, m: &Self) -> Self;
+
+    /// `self^e mod m`, square & multiply from the most significant bit
+    fn pow_mod(&self, e: &Self, m: &Self) -> Self {
+        let base = self.clone() % m.clone();
+        let mut result = Self::one() % m.clone();
+
+        for i in (0..e.bits()).rev() {
+            result = result.mul_mod(&result, m);
+
+            if e.bit(i) {
+                result = result.mul_mod(&base, m);
+            }
+        }
+
+        result
+    }
+
+    fn gcd(&self, other: &Self) -> Self {
+        let (mut a, mut b) = (self.clone(), other.clone());
+
+        while !b.is_zero() {
+            (a, b) = (b.clone(), a % b);
+        }
+
+        a
+    }
+
+    /// `x` with `self * x = 1 mod m`, extended Euclid with the coefficients kept modulo `m`
+    fn inverse_mod(&self, m: &Self) -> Option<Self> {
+        if m.is_zero() {
+            return None;
+        }
+
+        let (mut r0, mut r1) = (m.clone(), self.clone() % m.clone());
+        let (mut t0, mut t1) = (Self::zero(), Self::one() % m.clone());
+
+        while !r1.is_zero() {
+            let q = r0.clone() / r1.clone();
+            (r0, r1) = (r1.clone(), r0 - q.clone() * r1);
+
+            // t0 - q * t1 mod m
+            let qt = (q % m.clone()).mul_mod(&t1, m);
+            let t = if t0 >= qt { t0 - qt } else { m.clone() - (qt - t0) };
+            (t0, t1) = (t1, t);
+        }
+
+        (r0 == Self::one()).then_some(t0)
+    }
+}
+
+/// `NonInvertibleKey` when the numbers fit, the numbers spelled out otherwise
+pub fn non_invertible<T: Integer>(value: &T, modulus: &T) -> Error {
+    match (value.to_u128().and_then(|v| i128::try_from(v).ok()), modulus.to_u128().and_then(|m| i128::try_from(m).ok())) {
+        (Some(value), Some(modulus)) => Error::NonInvertibleKey { value, modulus },
+        _ => Error::InvalidKey(format!("{} is not invertible modulo {}", value, modulus)),
+    }
+}
+
+/// exponent inverse for a prime modulus `p`, `d` such that `(x^e)^d = x mod p`
+/// (Fermat, `e * d = 1 mod p - 1`), only exists when `gcd(e, p - 1) = 1`
+pub fn pow_inverse<T: Integer>(e: &T, p: &T) -> Result<T, Error> {
+    if *p < T::from(3) {
+        return Err(Error::InvalidKey(format!("modulus {} is too small", p)));
+    }
+
+    let order = p.clone() - T::one();
+    e.inverse_mod(&order).ok_or_else(|| non_invertible(e, &order))
+}
+
+/// a valid exponent for a prime modulus `p`, `1 < e < p - 1` & `gcd(e, p - 1) = 1`
+pub fn is_valid_exponent<T: Integer>(e: &T, p: &T) -> bool {
+    *p > T::from(3) && *e > T::one() && *e < p.clone() - T::one() && e.gcd(&(p.clone() - T::one())) == T::one()
+}
+
+impl Integer for u64 {
+    const MAX_BITS: Option<u64> = Some(u64::BITS as u64);
+
+    fn bits(&self) -> u64 {
+        (u64::BITS - self.leading_zeros()) as u64
+    }
+
+    fn bit(&self, i: u64) -> bool {
+        i < 64 && (self >> i) & 1 == 1
+    }
+
+    fn to_u128(&self) -> Option<u128> {
+        Some(*self as u128)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        u64::checked_mul(*self, *other)
+    }
+
+    fn random_below<R: Rng + ?Sized>(bound: &Self, rng: &mut R) -> Self {
+        rng.gen_range(0..*bound)
+    }
+
+    fn mul_mod(&self, other: &Self, m: &Self) -> Self {
+        ((*self as u128 * *other as u128) % *m as u128) as u64
+    }
+}
+
+impl Integer for u128 {
+    const MAX_BITS: Option<u64> = Some(u128::BITS as u64);
+
+    fn bits(&self) -> u64 {
+        (u128::BITS - self.leading_zeros()) as u64
+    }
+
+    fn bit(&self, i: u64) -> bool {
+        i < 128 && (self >> i) & 1 == 1
+    }
+
+    fn to_u128(&self) -> Option<u128> {
+        Some(*self)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        u128::checked_mul(*self, *other)
+    }
+
+    fn random_below<R: Rng + ?Sized>(bound: &Self, rng: &mut R) -> Self {
+        rng.gen_range(0..*bound)
+    }
+
+    /// double & add, no wider type to multiply in
+    fn mul_mod(&self, other: &Self, m: &Self) -> Self {
+        let add = |a: u128, b: u128| if a >= m - b { a - (m - b) } else { a + b };
+
+        let (a, b) = (self % m, other % m);
+        let mut result = 0;
+
+        for i in (0..b.bits()).rev() {
+            result = add(result, result);
+
+            if b.bit(i) {
+                result = add(result, a);
+            }
+        }
+
+        result
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pow_mod() {
+        assert_eq!(4u64.pow_mod(&13, &497), 445);
+        assert_eq!(5u64.pow_mod(&0, &1), 0);
+
+        // moduli above 64 bits, base * base doesn't fit in a u128
+        let m: u128 = 340282366920938463463374607431768211297; // 2¹²⁸ - 159, prime
+        assert_eq!((m - 1).mul_mod(&(m - 1), &m), 1);
+        assert_eq!(3u128.pow_mod(&(m - 1), &m), 1);
+        assert_eq!(0xdeadbeefcafebabe1234567890u128.pow_mod(&65537, &m), 0xb6b4e253f4b0e607e8e4a05986c1535e);
+    }
+
+    #[test]
+    fn test_inverse_mod() {
+        assert_eq!(3u64.inverse_mod(&26), Some(9));
+        assert_eq!(13u64.inverse_mod(&26), None);
+        assert_eq!(65537u128.inverse_mod(&3120), Some(2753));
+        assert_eq!(12u64.gcd(&18), 6);
+
+        assert_eq!(non_invertible(&13u64, &26), Error::NonInvertibleKey { value: 13, modulus: 26 });
+        assert_eq!(non_invertible(&u128::MAX, &7), Error::InvalidKey(format!("{} is not invertible modulo 7", u128::MAX)));
+    }
+
+    #[test]
+    fn test_pow_inverse() {
+        // 2632 = 2³ · 7 · 47
+        let d = pow_inverse(&769u64, &2633).unwrap();
+        assert_eq!(1904u64.pow_mod(&769, &2633).pow_mod(&d, &2633), 1904);
+        assert_eq!(pow_inverse(&7u64, &2633), Err(Error::NonInvertibleKey { value: 7, modulus: 2632 }));
+
+        assert!(is_valid_exponent(&769u128, &2633));
+        assert!(!is_valid_exponent(&2632u128, &2633));
+    }
+}
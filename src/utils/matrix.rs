@@ -0,0 +1,189 @@
+/*===================================================================
+ * Author: denosauabh
+ * Description: Square matrices over the integers modulo m, as used by the Hill cipher
+ * Reference: https://en.wikipedia.org/wiki/Adjugate_matrix
+
+ * The inverse of a matrix K modulo m exists iff gcd(det(K), m) = 1:
+ `K^-1 = det(K)^-1 * adj(K) (mod m)`
+
+ * Determinants are computed by cofactor (Laplace) expansion, which is plenty
+ * for the small block sizes of a classical cipher.
+*===================================================================*/
+
+use crate::utils::error::Error;
+use crate::utils::mod_arithmetic::ModArithmetic;
+
+pub struct ModMatrix;
+
+impl ModMatrix {
+    /// checks that the matrix is square & non empty, returns its dimension
+    pub fn dimension(matrix: &[Vec<i32>]) -> Result<usize, Error> {
+        let n = matrix.len();
+
+        if n == 0 {
+            return Err(Error::InvalidKey("matrix is empty".to_string()));
+        }
+
+        match matrix.iter().find(|row| row.len() != n) {
+            Some(row) => Err(Error::InvalidKeyLength { expected: n, found: row.len() }),
+            None => Ok(n),
+        }
+    }
+
+    pub fn determinant(matrix: &[Vec<i32>], m: i32) -> Result<i32, Error> {
+        Self::dimension(matrix)?;
+
+        let reduced = matrix
+            .iter()
+            .map(|row| row.iter().map(|&x| ModArithmetic::modm(x, m) as i64).collect())
+            .collect::<Vec<Vec<i64>>>();
+
+        Ok(Self::laplace(&reduced, m as i64) as i32)
+    }
+
+    /// transpose of the cofactor matrix, `K * adj(K) = det(K) * I`
+    #[allow(clippy::needless_range_loop)]
+    pub fn adjugate(matrix: &[Vec<i32>], m: i32) -> Result<Vec<Vec<i32>>, Error> {
+        let n = Self::dimension(matrix)?;
+
+        if n == 1 {
+            return Ok(vec![vec![1]]);
+        }
+
+        let mut adjugate = vec![vec![0; n]; n];
+
+        for i in 0..n {
+            for j in 0..n {
+                let minor = Self::minor(matrix, i, j);
+                let cofactor = Self::determinant(&minor, m)?;
+                let sign = if (i + j) % 2 == 0 { 1 } else { -1 };
+
+                // transposed
+                adjugate[j][i] = ModArithmetic::modm(sign * cofactor, m);
+            }
+        }
+
+        Ok(adjugate)
+    }
+
+    pub fn inverse(matrix: &[Vec<i32>], m: i32) -> Result<Vec<Vec<i32>>, Error> {
+        let det = Self::determinant(matrix, m)?;
+        let det_inv = ModArithmetic::mod_inverse(det, m)?;
+
+        Ok(Self::adjugate(matrix, m)?
+            .iter()
+            .map(|row| row.iter().map(|&x| ModArithmetic::mult(x, det_inv, m)).collect())
+            .collect())
+    }
+
+    /// `matrix * v (mod m)`, with `v` as a column vector
+    pub fn mul_vec(matrix: &[Vec<i32>], v: &[i32], m: i32) -> Vec<i32> {
+        matrix
+            .iter()
+            .map(|row| {
+                let sum = row
+                    .iter()
+                    .zip(v)
+                    .map(|(&a, &b)| (a as i64 * b as i64).rem_euclid(m as i64))
+                    .sum::<i64>();
+
+                sum.rem_euclid(m as i64) as i32
+            })
+            .collect()
+    }
+
+    /// `a * b (mod m)`
+    pub fn mul(a: &[Vec<i32>], b: &[Vec<i32>], m: i32) -> Vec<Vec<i32>> {
+        let b_t = Self::transpose(b);
+
+        a.iter()
+            .map(|row| b_t.iter().map(|column| Self::mul_vec(std::slice::from_ref(row), column, m)[0]).collect())
+            .collect()
+    }
+
+    pub fn transpose(matrix: &[Vec<i32>]) -> Vec<Vec<i32>> {
+        let columns = matrix.first().map_or(0, |row| row.len());
+
+        (0..columns)
+            .map(|j| matrix.iter().map(|row| row[j]).collect())
+            .collect()
+    }
+
+    /// matrix without row `i` & column `j`
+    fn minor(matrix: &[Vec<i32>], i: usize, j: usize) -> Vec<Vec<i32>> {
+        matrix
+            .iter()
+            .enumerate()
+            .filter(|&(r, _)| r != i)
+            .map(|(_, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|&(c, _)| c != j)
+                    .map(|(_, &x)| x)
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn laplace(matrix: &[Vec<i64>], m: i64) -> i64 {
+        let n = matrix.len();
+
+        match n {
+            1 => matrix[0][0].rem_euclid(m),
+            2 => (matrix[0][0] * matrix[1][1] - matrix[0][1] * matrix[1][0]).rem_euclid(m),
+            _ => {
+                // expand along the first row
+                (0..n).fold(0, |det, j| {
+                    let minor = matrix[1..]
+                        .iter()
+                        .map(|row| [&row[..j], &row[j + 1..]].concat())
+                        .collect::<Vec<Vec<i64>>>();
+
+                    let term = matrix[0][j] * Self::laplace(&minor, m) % m;
+                    let det = if j % 2 == 0 { det + term } else { det - term };
+
+                    det.rem_euclid(m)
+                })
+            },
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_determinant() {
+        assert_eq!(ModMatrix::determinant(&[vec![3, 5], vec![6, 1]], 26), Ok(25)); // 3 - 30 = -27
+        assert_eq!(ModMatrix::determinant(&[vec![6, 24, 1], vec![13, 16, 10], vec![20, 17, 15]], 26), Ok(25)); // 441
+
+        assert_eq!(ModMatrix::determinant(&[vec![1, 2], vec![3]], 26), Err(Error::InvalidKeyLength { expected: 2, found: 1 }));
+        assert!(ModMatrix::determinant(&[], 26).is_err());
+    }
+
+    #[test]
+    fn test_inverse() {
+        // https://en.wikipedia.org/wiki/Hill_cipher#Decryption
+        let key = [vec![6, 24, 1], vec![13, 16, 10], vec![20, 17, 15]];
+        let inverse = vec![vec![8, 5, 10], vec![21, 8, 21], vec![21, 12, 8]];
+
+        assert_eq!(ModMatrix::inverse(&key, 26), Ok(inverse.clone()));
+
+        // K * K^-1 = I
+        for (j, column) in (0..3).map(|j| inverse.iter().map(|row| row[j]).collect::<Vec<i32>>()).enumerate() {
+            let expected = (0..3).map(|i| if i == j { 1 } else { 0 }).collect::<Vec<i32>>();
+            assert_eq!(ModMatrix::mul_vec(&key, &column, 26), expected);
+        }
+
+        let identity = vec![vec![1, 0, 0], vec![0, 1, 0], vec![0, 0, 1]];
+        assert_eq!(ModMatrix::mul(&inverse, &key, 26), identity);
+        assert_eq!(ModMatrix::transpose(&[vec![1, 2, 3], vec![4, 5, 6]]), vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+
+        assert_eq!(
+            ModMatrix::inverse(&[vec![2, 2], vec![3, 4]], 26),
+            Err(Error::NonInvertibleKey { value: 2, modulus: 26 })
+        );
+    }
+}
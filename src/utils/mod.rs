@@ -1,3 +1,12 @@
 pub mod consts;
 pub mod char_set;
 pub mod mod_arithmetic;
+pub mod cipher;
+pub mod error;
+pub mod encoding;
+pub mod matrix;
+pub mod analysis;
+pub mod polybius;
+pub mod integer;
+pub mod bignum;
+pub mod prime;
@@ -1,3 +1,5 @@
+use crate::utils::{error::Error, integer::{self, Integer}};
+
 pub struct ModArithmetic;
 
 impl ModArithmetic {
@@ -24,65 +26,40 @@ impl ModArithmetic {
         Self::mult(p as i32, k, m as i32) as usize
     }
 
-    pub fn div(p: i32, x: i32, k: i32) -> i32 {
-        if let Ok(xi) = Self::mod_inverse(x, k) {
-            Self::mult(p, xi, k)
-        } else {
-            panic!("Modular inverse does not exist");
-        }
+    pub fn div(p: i32, x: i32, k: i32) -> Result<i32, Error> {
+        let xi = Self::mod_inverse(x, k)?;
+        Ok(Self::mult(p, xi, k))
     }
-    pub fn div_usize(p: usize, k: i32, m: usize) -> usize {
-        Self::div(p as i32, k, m as i32) as usize
+    pub fn div_usize(p: usize, k: i32, m: usize) -> Result<usize, Error> {
+        Self::div(p as i32, k, m as i32).map(|x| x as usize)
     }
     
     pub fn pow(p: u64, e: u64, m: u64) -> u64 {
         ModArithmetic::pow_u128(p as u128, e as u128, m as u128) as u64
     }
 
+    /// `p^e mod m`, safe for moduli above 64 bits (see `Integer::mul_mod`)
     pub fn pow_u128(p: u128, e: u128, m: u128) -> u128 {
-        let mut result = 1;
-        let mut base = p % m;
-        let mut exponent = e;
-
-        while exponent > 0 {
-            if exponent % 2 == 1 {
-                result = (result * base) % m;
-            }
-            exponent /= 2;
-            base = (base * base) % m;
-        }
-
-        result
+        p.pow_mod(&e, &m)
     }
 
-    /// CURRENTLY BROKEN!!
-    pub fn pow_inverse(e: u128, n: u128) -> Result<u128, String> {
-        let mut t = 0i128;
-        let mut newt = 1i128;
-        let mut r = n as i128;
-        let mut newr = e as i128;
-
-        while newr != 0 {
-            let quotient = r / newr;
-            (t, newt) = (newt, t - quotient * newt);
-            (r, newr) = (newr, r - quotient * newr);
-        }
-
-        if r > 1 {
-            return Err("e is not invertible".to_string());
-        }
-        
-        Ok(if t < 0 { t + n as i128 } else { t } as u128)
+    /// exponent inverse for a prime modulus `p`, `d` such that `(x^e)^d = x mod p`
+    /// (Fermat, `e * d = 1 mod p - 1`), only exists when `gcd(e, p - 1) = 1`
+    pub fn pow_inverse(e: u128, p: u128) -> Result<u128, Error> {
+        integer::pow_inverse(&e, &p)
     }
 
-
+    /// a valid exponent for a prime modulus `p`, `1 < e < p - 1` & `gcd(e, p - 1) = 1`
+    pub fn is_valid_exponent(e: u128, p: u128) -> bool {
+        integer::is_valid_exponent(&e, &p)
+    }
 
     // Extended Euclidean Algorithm
-    pub fn mod_inverse_i128(k: i128, m: i128) -> Result<i128, &'static str> {
+    pub fn mod_inverse_i128(k: i128, m: i128) -> Result<i128, Error> {
         let mut t = 0;
         let mut newt = 1;
         let mut r = m;
-        let mut newr = k.abs();
+        let mut newr = k.rem_euclid(m);
     
         while newr != 0 {
             let quotient = r / newr;
@@ -91,7 +68,7 @@ impl ModArithmetic {
         }
     
         if r > 1 {
-            Err("a and m are not coprime, inverse doesn't exist")
+            Err(Error::NonInvertibleKey { value: k, modulus: m })
         } else {
             Ok((if t < 0 { t + m } else { t }) % m)
         }
@@ -99,11 +76,8 @@ impl ModArithmetic {
 
 
 
-    pub fn mod_inverse(k: i32, m: i32) -> Result<i32, &'static str> {
-        match Self::mod_inverse_i128(k as i128, m as i128) {
-            Ok(x) => Ok(x as i32),
-            Err(e) => Err(e),
-        }
+    pub fn mod_inverse(k: i32, m: i32) -> Result<i32, Error> {
+        Self::mod_inverse_i128(k as i128, m as i128).map(|x| x as i32)
     }
 
     pub fn euclidean_gcd(a: i32, b: i32) -> i32 {
@@ -170,20 +144,47 @@ mod tests {
 
     #[test]
     fn test_div() {
-        assert_eq!(super::ModArithmetic::div(3, 9, 26), 9);
-        assert_eq!(super::ModArithmetic::div(3, 21, 26), 15);
+        assert_eq!(super::ModArithmetic::div(3, 9, 26), Ok(9));
+        assert_eq!(super::ModArithmetic::div(3, 21, 26), Ok(15));
+        assert!(super::ModArithmetic::div(3, 13, 26).is_err());
     }
 
     #[test]
     fn test_div_usize() {
-        assert_eq!(super::ModArithmetic::div_usize(5, 3, 26), 19);
-        assert_eq!(super::ModArithmetic::div_usize(25, 3, 26), 17);
+        assert_eq!(super::ModArithmetic::div_usize(5, 3, 26), Ok(19));
+        assert_eq!(super::ModArithmetic::div_usize(25, 3, 26), Ok(17));
     }
 
     #[test]
     fn mod_inverse() {
         assert_eq!(super::ModArithmetic::mod_inverse(3, 26), Ok(9));
         assert_eq!(super::ModArithmetic::mod_inverse(21, 26), Ok(5));
+        assert_eq!(super::ModArithmetic::mod_inverse(13, 26), Err(super::Error::NonInvertibleKey { value: 13, modulus: 26 }));
+    }
+
+    #[test]
+    fn pow_inverse() {
+        // 2632 = 2³ · 7 · 47
+        let d = super::ModArithmetic::pow_inverse(769, 2633).unwrap();
+        assert_eq!(769 * d % 2632, 1);
+        assert_eq!(super::ModArithmetic::pow(super::ModArithmetic::pow(1904, 769, 2633), d as u64, 2633), 1904);
+
+        assert_eq!(super::ModArithmetic::pow_inverse(7, 2633), Err(super::Error::NonInvertibleKey { value: 7, modulus: 2632 }));
+        assert!(super::ModArithmetic::pow_inverse(0, 2633).is_err());
+        assert!(super::ModArithmetic::pow_inverse(3, 2).is_err());
+
+        assert!(super::ModArithmetic::is_valid_exponent(769, 2633));
+        assert!(!super::ModArithmetic::is_valid_exponent(94, 2633));
+        assert!(!super::ModArithmetic::is_valid_exponent(1, 2633));
+        assert!(!super::ModArithmetic::is_valid_exponent(2633, 2633));
+    }
+
+    #[test]
+    fn pow_u128() {
+        // 2¹²⁸ - 159 is prime, (m - 1)² overflowed a u128
+        let m = u128::MAX - 158;
+        assert_eq!(super::ModArithmetic::pow_u128(m - 1, 2, m), 1);
+        assert_eq!(super::ModArithmetic::pow_u128(3, m - 1, m), 1);
     }
 
     #[test]
@@ -0,0 +1,365 @@
+/*===================================================================
+ * Author: denosauabh
+ * Description: Keyed Polybius square, the alphabet of a charset written into a grid
+                row by row, starting with the letters of a keyword
+ * Reference: https://en.wikipedia.org/wiki/Polybius_square
+
+ * e.g. keyword "PLAYFAIR EXAMPLE" over A..Z with J merged into I:
+
+        1 2 3 4 5
+    1   P L A Y F
+    2   I R E X M
+    3   B C D G H
+    4   K N O Q S
+    5   T U V W Z
+
+ * A character is written as the labels of its row & column, E -> 23 (ADFGVX labels them ADFGVX).
+ * The charset needs rows × columns characters (25 = 5×5, 36 = 6×6), a 26 letter alphabet fits
+ * a 5×5 square by merging one letter into another (J into I) or leaving one out (Q).
+ * `SquareOptions` holds the alphabet the square ciphers (Playfair, Bifid, ...) are built on.
+*===================================================================*/
+
+use crate::utils::char_set::CharSet;
+use crate::utils::error::Error;
+
+#[derive(Clone, Debug)]
+pub struct PolybiusSquare {
+    /// the grid row by row
+    chars: Vec<char>,
+    rows: usize,
+    cols: usize,
+
+    /// (from, into), `from` isn't part of the grid & is written as `into`
+    merges: Vec<(char, char)>,
+
+    row_labels: Vec<char>,
+    col_labels: Vec<char>,
+}
+
+impl PolybiusSquare {
+    pub fn new(keyword: &str, char_set: &CharSet) -> Result<Self, Error> {
+        Self::with_merge(keyword, char_set, None)
+    }
+
+    /// `merge` (from, into), e.g. `Some(('J', 'I'))`
+    pub fn with_merge(keyword: &str, char_set: &CharSet, merge: Option<(char, char)>) -> Result<Self, Error> {
+        let len = char_set.len() - usize::from(merge.is_some_and(|(from, _)| char_set.contains(from)));
+        let size = len.isqrt();
+
+        if size == 0 || size * size != len {
+            return Err(Error::InvalidCharSet(format!("{} characters don't fill a square", len)));
+        }
+
+        Self::with_dimensions(keyword, char_set, merge.as_slice(), size, size)
+    }
+
+    /// `rows × cols` grid, every merge (from, into) takes a character out of the charset
+    pub fn with_dimensions(keyword: &str, char_set: &CharSet, merges: &[(char, char)], rows: usize, cols: usize) -> Result<Self, Error> {
+        for &(from, into) in merges {
+            if !char_set.contains(from) || !char_set.contains(into) || from == into || merges.iter().any(|&(f, _)| f == into) {
+                return Err(Error::InvalidCharSet(format!("can't merge {:?} into {:?}", from, into)));
+            }
+        }
+
+        let alphabet = char_set
+            .chars
+            .iter()
+            .copied()
+            .filter(|&c| merges.iter().all(|&(from, _)| c != from))
+            .collect::<Vec<char>>();
+
+        if rows == 0 || cols == 0 || rows * cols != alphabet.len() {
+            return Err(Error::InvalidCharSet(format!("{} characters don't fill a {}×{} grid", alphabet.len(), rows, cols)));
+        }
+
+        let mut square = Self {
+            chars: Vec::with_capacity(alphabet.len()),
+            rows,
+            cols,
+            merges: merges.to_vec(),
+            row_labels: Self::default_labels(rows)?,
+            col_labels: Self::default_labels(cols)?,
+        };
+
+        // the keyword without repeated letters, then the rest of the alphabet
+        for (i, c) in keyword.chars().enumerate() {
+            let c = square.normalize(c);
+
+            if !alphabet.contains(&c) {
+                return Err(Error::UnknownCharacter { character: c, position: Some(i) });
+            }
+            if !square.chars.contains(&c) {
+                square.chars.push(c);
+            }
+        }
+
+        for c in alphabet {
+            if !square.chars.contains(&c) {
+                square.chars.push(c);
+            }
+        }
+
+        Ok(square)
+    }
+
+    /// row & column labels, one per row / column, `1..` by default
+    pub fn labelled(mut self, row_labels: &str, col_labels: &str) -> Result<Self, Error> {
+        let row_labels = CharSet::from_string(row_labels).chars;
+        let col_labels = CharSet::from_string(col_labels).chars;
+
+        if row_labels.len() != self.rows || col_labels.len() != self.cols {
+            return Err(Error::InvalidCharSet(format!(
+                "{}×{} grid needs {} row & {} column labels, found {} & {}",
+                self.rows, self.cols, self.rows, self.cols, row_labels.len(), col_labels.len()
+            )));
+        }
+
+        self.row_labels = row_labels;
+        self.col_labels = col_labels;
+
+        Ok(self)
+    }
+
+    /// keyword from a passphrase, the characters of the charset in it,
+    /// other characters tried in the other case, then skipped.
+    /// "Attack at dawn!" over A..Z -> "ATTACKATDAWN"
+    pub fn keyword(passphrase: &str, char_set: &CharSet) -> String {
+        passphrase
+            .chars()
+            .filter_map(|c| {
+                [c, c.to_ascii_uppercase(), c.to_ascii_lowercase()]
+                    .into_iter()
+                    .find(|&c| char_set.contains(c))
+            })
+            .collect()
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// the grid row by row
+    pub fn chars(&self) -> &[char] {
+        &self.chars
+    }
+
+    /// the character as it is written in the grid
+    pub fn normalize(&self, c: char) -> char {
+        self.merges
+            .iter()
+            .find(|&&(from, _)| from == c)
+            .map_or(c, |&(_, into)| into)
+    }
+
+    pub fn contains(&self, c: char) -> bool {
+        self.chars.contains(&self.normalize(c))
+    }
+
+    /// (row, column) of the character
+    pub fn position(&self, c: char) -> Result<(usize, usize), Error> {
+        let c = self.normalize(c);
+
+        self.chars
+            .iter()
+            .position(|&x| x == c)
+            .map(|i| (i / self.cols, i % self.cols))
+            .ok_or(Error::UnknownCharacter { character: c, position: None })
+    }
+
+    pub fn char_at(&self, row: usize, col: usize) -> Result<char, Error> {
+        if row >= self.rows {
+            return Err(Error::IndexOutOfBounds { index: row, len: self.rows });
+        }
+        if col >= self.cols {
+            return Err(Error::IndexOutOfBounds { index: col, len: self.cols });
+        }
+
+        Ok(self.chars[row * self.cols + col])
+    }
+
+    /// row & column label of the character
+    pub fn encode(&self, c: char) -> Result<(char, char), Error> {
+        let (row, col) = self.position(c)?;
+        Ok((self.row_labels[row], self.col_labels[col]))
+    }
+
+    /// character at the row & column labels
+    pub fn decode(&self, row_label: char, col_label: char) -> Result<char, Error> {
+        let row = self.row_labels.iter().position(|&l| l == row_label);
+        let col = self.col_labels.iter().position(|&l| l == col_label);
+
+        match (row, col) {
+            (Some(row), Some(col)) => self.char_at(row, col),
+            (None, _) => Err(Error::UnknownCharacter { character: row_label, position: None }),
+            (_, None) => Err(Error::UnknownCharacter { character: col_label, position: None }),
+        }
+    }
+
+    /// 1..9 then a..z
+    fn default_labels(n: usize) -> Result<Vec<char>, Error> {
+        (1..=n as u32)
+            .map(|i| char::from_digit(i, 36).ok_or(Error::InvalidCharSet(format!("no default labels for {} rows / columns", n))))
+            .collect()
+    }
+}
+
+/// the alphabet of a square cipher, its unkeyed square is built once & checked up front,
+/// keyed squares are built from the same charset & merge
+#[derive(Clone, Debug)]
+pub struct SquareOptions {
+    char_set: CharSet,
+    merge: Option<(char, char)>,
+    plain: PolybiusSquare,
+
+    /// pads & splits the digraphs of Playfair, Two-Square & Four-Square
+    filler: Option<char>,
+}
+
+impl SquareOptions {
+    /// the charset (without the merged letter) must fill a square
+    pub fn new(char_set: CharSet, merge: Option<(char, char)>) -> Result<Self, Error> {
+        let plain = PolybiusSquare::with_merge("", &char_set, merge)?;
+        Ok(Self { char_set, merge, plain, filler: None })
+    }
+
+    /// the filler must be part of the square
+    pub fn with_filler(mut self, filler: char) -> Result<Self, Error> {
+        if !self.plain.contains(filler) {
+            return Err(Error::UnknownCharacter { character: filler, position: None });
+        }
+
+        self.filler = Some(filler);
+        Ok(self)
+    }
+
+    /// the filler as it is written in the square
+    pub fn filler(&self) -> Result<char, Error> {
+        self.filler
+            .map(|c| self.plain.normalize(c))
+            .ok_or(Error::InvalidCharSet("digraphs need a filler".to_string()))
+    }
+
+    /// the square without a keyword
+    pub fn plain(&self) -> &PolybiusSquare {
+        &self.plain
+    }
+
+    pub fn keyed(&self, keyword: &str) -> Result<PolybiusSquare, Error> {
+        PolybiusSquare::with_merge(keyword, &self.char_set, self.merge)
+    }
+}
+
+/// A..Z with J merged into I, X as filler
+impl Default for SquareOptions {
+    fn default() -> Self {
+        let options = Self::new(CharSet::from_range('A', 'Z'), Some(('J', 'I'))).expect("25 letters fill a 5×5 square");
+        options.with_filler('X').expect("X is part of the square")
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn uppercase() -> CharSet {
+        CharSet::from_range('A', 'Z')
+    }
+
+    #[test]
+    fn test_keyed() {
+        let square = PolybiusSquare::with_merge("PLAYFAIREXAMPLE", &uppercase(), Some(('J', 'I'))).unwrap();
+
+        assert_eq!((square.rows(), square.cols()), (5, 5));
+        assert_eq!(square.chars().iter().collect::<String>(), "PLAYFIREXMBCDGHKNOQSTUVWZ");
+        assert_eq!(square.position('E').unwrap(), (1, 2));
+        assert_eq!(square.position('J').unwrap(), (1, 0));
+        assert_eq!(square.char_at(4, 4).unwrap(), 'Z');
+        assert!(square.char_at(5, 0).is_err());
+    }
+
+    #[test]
+    fn test_unkeyed() {
+        let square = PolybiusSquare::new("", &CharSet::from_string("ABCDEFGHIKLMNOPQRSTUVWXYZ")).unwrap();
+        assert_eq!(square.position('A').unwrap(), (0, 0));
+        assert_eq!(square.position('K').unwrap(), (1, 4));
+        assert!(square.position('J').is_err());
+
+        // 6×6 over letters & digits
+        let square = PolybiusSquare::new("PRIVACY", &CharSet::from_string("ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789")).unwrap();
+        assert_eq!((square.rows(), square.cols()), (6, 6));
+        assert_eq!(square.char_at(1, 0).unwrap(), 'Y');
+    }
+
+    #[test]
+    fn test_labels() {
+        let square = PolybiusSquare::with_merge("", &uppercase(), Some(('J', 'I'))).unwrap();
+        assert_eq!(square.encode('E').unwrap(), ('1', '5'));
+        assert_eq!(square.encode('J').unwrap(), ('2', '4'));
+        assert_eq!(square.decode('2', '4').unwrap(), 'I');
+        assert!(square.decode('6', '1').is_err());
+
+        let square = square.labelled("ADFGX", "ADFGX").unwrap();
+        assert_eq!(square.encode('E').unwrap(), ('A', 'X'));
+        assert_eq!(square.decode('X', 'X').unwrap(), 'Z');
+        assert!(square.decode('1', '5').is_err());
+
+        let square = PolybiusSquare::new("", &CharSet::from_string("ABCD")).unwrap();
+        assert!(square.labelled("AB", "ABC").is_err());
+    }
+
+    #[test]
+    fn test_dimensions() {
+        // 26 letters in a 2×13 grid
+        let square = PolybiusSquare::with_dimensions("ZEBRA", &uppercase(), &[], 2, 13).unwrap();
+        assert_eq!(square.char_at(0, 12).unwrap(), 'K');
+        assert_eq!(square.encode('L').unwrap(), ('2', '1'));
+        assert_eq!(square.decode('1', 'c').unwrap(), 'J');
+
+        // 24 letters, J into I & Q into K
+        let square = PolybiusSquare::with_dimensions("", &uppercase(), &[('J', 'I'), ('Q', 'K')], 4, 6).unwrap();
+        assert_eq!(square.position('Q').unwrap(), square.position('K').unwrap());
+
+        assert!(PolybiusSquare::with_dimensions("", &uppercase(), &[], 5, 5).is_err());
+        assert!(PolybiusSquare::with_dimensions("", &uppercase(), &[('J', 'I'), ('I', 'K')], 4, 6).is_err());
+    }
+
+    #[test]
+    fn test_passphrase() {
+        let keyword = PolybiusSquare::keyword("Attack at dawn!", &uppercase());
+        assert_eq!(keyword, "ATTACKATDAWN");
+
+        let square = PolybiusSquare::with_merge(&keyword, &uppercase(), Some(('J', 'I'))).unwrap();
+        assert_eq!(square.chars().iter().take(8).collect::<String>(), "ATCKDWNB");
+    }
+
+    #[test]
+    fn test_square_options() {
+        let options = SquareOptions::default();
+        assert_eq!(options.filler(), Ok('X'));
+        assert_eq!(options.plain().position('J').unwrap(), (1, 3));
+        assert_eq!(options.keyed("ZEBRAS").unwrap().char_at(0, 0).unwrap(), 'Z');
+
+        let options = SquareOptions::new(CharSet::from_string("abcdefghiklmnopqrstuvwxyz"), None).unwrap();
+        assert!(options.filler().is_err());
+        assert!(options.clone().with_filler('j').is_err());
+        assert_eq!(options.with_filler('z').unwrap().filler(), Ok('z'));
+
+        assert!(SquareOptions::new(uppercase(), None).is_err());
+    }
+
+    #[test]
+    fn test_invalid() {
+        // 26 letters without a merge
+        assert!(PolybiusSquare::new("", &uppercase()).is_err());
+        assert!(PolybiusSquare::with_merge("", &uppercase(), Some(('J', 'J'))).is_err());
+        assert!(PolybiusSquare::with_merge("", &uppercase(), Some(('J', '1'))).is_err());
+
+        // keyword outside of the alphabet
+        assert!(PolybiusSquare::with_merge("KEY1", &uppercase(), Some(('J', 'I'))).is_err());
+    }
+}
@@ -0,0 +1,153 @@
+/*===================================================================
+ * Author: denosauabh
+ * Description: Probable primes for the public-key ciphers, generic over `Integer`
+ * Reference: https://en.wikipedia.org/wiki/Miller–Rabin_primality_test
+              FIPS 186-5, Appendix B.3.3 & C.3.1
+
+ * Trial division by the small primes weeds out most candidates, the survivors go through
+   Miller-Rabin with random bases. A composite passes a round with probability at most 1/4.
+ *===================================================================*/
+
+use primes::{PrimeSet, Sieve};
+use rand::Rng;
+
+use crate::utils::{error::Error, integer::Integer};
+
+/// Miller-Rabin rounds, a composite survives all of them with probability at most 2⁻⁸⁰
+pub const MILLER_RABIN_ROUNDS: usize = 40;
+
+/// small primes tried as divisors before Miller-Rabin
+const TRIAL_DIVISORS: usize = 256;
+
+/// `n` is prime with probability at least `1 - 4^-rounds`, `false` is always right
+pub fn is_probable_prime<T: Integer, R: Rng + ?Sized>(n: &T, rounds: usize, rng: &mut R) -> bool {
+    for p in Sieve::new().iter().take(TRIAL_DIVISORS) {
+        let p = T::from(p);
+
+        if *n == p {
+            return true;
+        }
+        if (n.clone() % p).is_zero() {
+            return false;
+        }
+    }
+
+    // 1 isn't divisible by any of them
+    if *n < T::from(2) {
+        return false;
+    }
+
+    miller_rabin(n, rounds, rng)
+}
+
+/// `n` odd & larger than 3
+fn miller_rabin<T: Integer, R: Rng + ?Sized>(n: &T, rounds: usize, rng: &mut R) -> bool {
+    let one = T::one();
+    let n_minus_one = n.clone() - one.clone();
+
+    // n - 1 = 2^s · d with d odd
+    let s = (0..n_minus_one.bits()).take_while(|&i| !n_minus_one.bit(i)).count();
+    let d = (0..s).fold(n_minus_one.clone(), |d, _| d / T::from(2));
+
+    'witness: for _ in 0..rounds {
+        // a in 2..=n - 2
+        let a = T::random_below(&(n.clone() - T::from(3)), rng) + T::from(2);
+        let mut x = a.pow_mod(&d, n);
+
+        if x == one || x == n_minus_one {
+            continue;
+        }
+
+        for _ in 1..s {
+            x = x.mul_mod(&x, n);
+
+            if x == n_minus_one {
+                continue 'witness;
+            }
+        }
+
+        return false;
+    }
+
+    true
+}
+
+/// `2^k`
+pub fn power_of_two<T: Integer>(k: u64) -> T {
+    (0..k).fold(T::one(), |x, _| x.clone() + x)
+}
+
+/// a random probable prime of exactly `bits` bits with the two highest bits set,
+/// so the product of two of them has exactly `2 · bits` bits
+pub fn random_prime<T: Integer, R: Rng + ?Sized>(bits: u64, rng: &mut R) -> Result<T, Error> {
+    if bits < 3 || T::MAX_BITS.is_some_and(|max| bits > max) {
+        return Err(Error::Unsupported(format!("primes of {} bits", bits)));
+    }
+
+    let top = power_of_two::<T>(bits - 1) + power_of_two::<T>(bits - 2);
+    let rest = power_of_two::<T>(bits - 2);
+
+    loop {
+        let mut candidate = top.clone() + T::random_below(&rest, rng);
+
+        if !candidate.is_odd() {
+            candidate = candidate + T::one();
+        }
+
+        if is_probable_prime(&candidate, MILLER_RABIN_ROUNDS, rng) {
+            return Ok(candidate);
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::bignum::BigUint;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_is_probable_prime() {
+        let mut rng = StdRng::seed_from_u64(7);
+
+        let primes = [2u64, 3, 5, 1621, 2633, 1_000_000_007, 18446744073709551557];
+        let composites = [0u64, 1, 4, 2632, 1_000_000_007 * 3, 18446744073709551555];
+
+        assert!(primes.iter().all(|p| is_probable_prime(p, MILLER_RABIN_ROUNDS, &mut rng)));
+        assert!(!composites.iter().any(|c| is_probable_prime(c, MILLER_RABIN_ROUNDS, &mut rng)));
+
+        // a Carmichael number (6151 · 12301 · 18451) fools Fermat for every coprime base,
+        // a strong pseudoprime to the bases 2..=17 (10670053 · 32010157), neither fools random bases
+        assert!(!is_probable_prime(&1_396_066_334_401u64, MILLER_RABIN_ROUNDS, &mut rng));
+        assert!(!is_probable_prime(&341_550_071_728_321u64, MILLER_RABIN_ROUNDS, &mut rng));
+
+        // 2¹²⁷ - 1 & the 1024-bit MODP prime of RFC 2409
+        assert!(is_probable_prime(&(u128::MAX >> 1), MILLER_RABIN_ROUNDS, &mut rng));
+        let p: BigUint = "0xffffffffffffffffc90fdaa22168c234c4c6628b80dc1cd129024e088a67cc74020bbea63b139b22514a08798e3404ddef9519b3cd3a431b302b0a6df25f14374fe1356d6d51c245e485b576625e7ec6f44c42e9a637ed6b0bff5cb6f406b7edee386bfb5a899fa5ae9f24117c4b1fe649286651ece65381ffffffffffffffff".parse().unwrap();
+        assert!(is_probable_prime(&p, MILLER_RABIN_ROUNDS, &mut rng));
+        assert!(!is_probable_prime(&(&p * &p), MILLER_RABIN_ROUNDS, &mut rng));
+    }
+
+    #[test]
+    fn test_random_prime() {
+        let mut rng = StdRng::seed_from_u64(7);
+
+        for bits in [8u64, 20, 31] {
+            let p = random_prime::<u64, _>(bits, &mut rng).unwrap();
+            assert_eq!(p.bits(), bits);
+            assert!(p.bit(bits - 2));
+            assert!(primes::is_prime(p));
+        }
+
+        let p = random_prime::<u128, _>(64, &mut rng).unwrap();
+        assert_eq!(p.bits(), 64);
+
+        let p = random_prime::<BigUint, _>(512, &mut rng).unwrap();
+        assert_eq!(p.bits(), 512);
+
+        assert!(random_prime::<u64, _>(2, &mut rng).is_err());
+        assert!(random_prime::<u64, _>(65, &mut rng).is_err());
+        assert_eq!(power_of_two::<u128>(100), 1 << 100);
+    }
+}